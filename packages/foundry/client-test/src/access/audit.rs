@@ -0,0 +1,475 @@
+use ethers::contract::parse_log;
+use ethers::prelude::*;
+use eyre::Result;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::access::{
+    has_role, role_has_access, role_name, roles_of_function, roles_of_user, RoleSource,
+};
+use crate::diamond::load_diamond_state;
+use crate::t;
+use crate::utils::{format_selector, SelectorBook, DEFAULT_ADMIN_ROLE, LOOP_ADMIN_ROLE};
+use crate::Env;
+
+/// `UserRoleUpdated(address indexed user, uint8 indexed role, bool enabled)`
+#[derive(Debug, Clone, EthEvent)]
+#[ethevent(name = "UserRoleUpdated", abi = "UserRoleUpdated(address,uint8,bool)")]
+pub struct UserRoleUpdatedFilter {
+    #[ethevent(indexed)]
+    pub user: Address,
+    #[ethevent(indexed)]
+    pub role: u8,
+    pub enabled: bool,
+}
+
+/// `FunctionAccessChanged(bytes4 indexed functionSig, uint8 indexed role, bool enabled)`
+#[derive(Debug, Clone, EthEvent)]
+#[ethevent(
+    name = "FunctionAccessChanged",
+    abi = "FunctionAccessChanged(bytes4,uint8,bool)"
+)]
+pub struct FunctionAccessChangedFilter {
+    #[ethevent(indexed)]
+    pub function_sig: [u8; 4],
+    #[ethevent(indexed)]
+    pub role: u8,
+    pub enabled: bool,
+}
+
+/// Cambio de permisos reconstruido a partir de un log.
+#[derive(Debug, Clone)]
+pub enum PermissionChange {
+    UserRole {
+        block: Option<U64>,
+        user: Address,
+        role: u8,
+        enabled: bool,
+    },
+    FunctionAccess {
+        block: Option<U64>,
+        selector: [u8; 4],
+        role: u8,
+        enabled: bool,
+    },
+}
+
+/// Estado de permisos tras reproducir los eventos.
+#[derive(Debug, Clone, Default)]
+pub struct PermissionMatrix {
+    pub user_roles: BTreeMap<Address, BTreeSet<u8>>,
+    pub function_roles: BTreeMap<[u8; 4], BTreeSet<u8>>,
+    /// Pares vistos alguna vez, aunque hoy estén desactivados.
+    pub seen_user_roles: BTreeSet<(Address, u8)>,
+    pub seen_function_roles: BTreeSet<([u8; 4], u8)>,
+}
+
+impl PermissionMatrix {
+    pub fn apply(&mut self, change: &PermissionChange) {
+        match change {
+            PermissionChange::UserRole {
+                user,
+                role,
+                enabled,
+                ..
+            } => {
+                self.seen_user_roles.insert((*user, *role));
+                let roles = self.user_roles.entry(*user).or_default();
+                if *enabled {
+                    roles.insert(*role);
+                } else {
+                    roles.remove(role);
+                }
+            }
+            PermissionChange::FunctionAccess {
+                selector,
+                role,
+                enabled,
+                ..
+            } => {
+                self.seen_function_roles.insert((*selector, *role));
+                let roles = self.function_roles.entry(*selector).or_default();
+                if *enabled {
+                    roles.insert(*role);
+                } else {
+                    roles.remove(role);
+                }
+            }
+        }
+    }
+
+    /// Usuarios que tienen `role` en el estado reconstruido.
+    pub fn holders(&self, role: u8) -> Vec<Address> {
+        self.user_roles
+            .iter()
+            .filter(|(_, roles)| roles.contains(&role))
+            .map(|(user, _)| *user)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditFinding {
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PermissionAudit {
+    pub diamond: Address,
+    pub history: Vec<PermissionChange>,
+    pub matrix: PermissionMatrix,
+    pub findings: Vec<AuditFinding>,
+}
+
+/// Funciones administrativas que sólo deberían abrirse a los roles de admin.
+const SENSITIVE_FUNCTIONS: &[&str] = &[
+    "setTrustedBackendSigner(address)",
+    "withdrawDeposit(address)",
+    "setPercentPerPeriod(uint256)",
+    "setUserRole(address,uint8,bool)",
+    "setFunctionAccess(bytes4,uint8,bool)",
+    "createNewLoop(address,address,uint256,uint256)",
+];
+
+/// Reproduce `UserRoleUpdated` y `FunctionAccessChanged` del diamond en orden.
+pub async fn permission_history(
+    env: &Env,
+    diamond: Address,
+    from_block: u64,
+) -> Result<Vec<PermissionChange>> {
+    let filter = Filter::new()
+        .address(diamond)
+        .from_block(from_block)
+        .topic0(vec![
+            UserRoleUpdatedFilter::signature(),
+            FunctionAccessChangedFilter::signature(),
+        ]);
+    let mut logs = env.provider()?.get_logs(&filter).await?;
+    logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+    let mut history = Vec::with_capacity(logs.len());
+    for log in logs {
+        let block = log.block_number;
+        if let Ok(event) = parse_log::<UserRoleUpdatedFilter>(log.clone()) {
+            history.push(PermissionChange::UserRole {
+                block,
+                user: event.user,
+                role: event.role,
+                enabled: event.enabled,
+            });
+        } else if let Ok(event) = parse_log::<FunctionAccessChangedFilter>(log) {
+            history.push(PermissionChange::FunctionAccess {
+                block,
+                selector: event.function_sig,
+                role: event.role,
+                enabled: event.enabled,
+            });
+        }
+    }
+    Ok(history)
+}
+
+/// Organización dueña de un loop según los `LoopCreated` del system diamond.
+async fn loop_organization(env: &Env, loop_address: Address) -> Result<Option<Address>> {
+    let signature = H256::from(ethers::utils::keccak256(
+        "LoopCreated(uint256,address,address,address,uint256,uint256)",
+    ));
+    let filter = Filter::new()
+        .address(env.deployment_address("system_diamond")?)
+        .from_block(0u64)
+        .topic0(signature);
+    let logs = env.provider()?.get_logs(&filter).await?;
+
+    for log in logs {
+        // data = (loopAddress, organization, token, periodLength, percentPerPeriod)
+        if log.data.len() >= 64 && Address::from_slice(&log.data[12..32]) == loop_address {
+            return Ok(Some(Address::from_slice(&log.data[44..64])));
+        }
+    }
+    Ok(None)
+}
+
+/// Audita los permisos de un diamond: reconstruye la matriz desde eventos, la
+/// contrasta con los bitmaps vigentes (`userRoles`/`functionRoles`, por la
+/// faceta o del storage) y marca estados de riesgo.
+pub async fn audit_permissions(
+    env: &Env,
+    diamond: Address,
+    from_block: u64,
+) -> Result<PermissionAudit> {
+    let history = permission_history(env, diamond, from_block).await?;
+    let mut matrix = PermissionMatrix::default();
+    for change in &history {
+        matrix.apply(change);
+    }
+
+    let state = load_diamond_state(env, diamond).await?;
+    let book = SelectorBook::from_artifacts()?;
+    let mut findings = Vec::new();
+
+    // 1) Contraste con el estado real: `hasRole`/`roleHasAccess` de
+    //    AccessControlFacet o, si el diamond no la tiene (loops,
+    //    organizaciones), los bitmaps de AccessControlStorage
+    let source = RoleSource::of(&state);
+    if source == RoleSource::Storage {
+        findings.push(AuditFinding {
+            severity: Severity::Info,
+            message: t!("El diamond no expone AccessControlFacet: roles leídos del storage"),
+        });
+    }
+    let users: BTreeSet<Address> = matrix.seen_user_roles.iter().map(|(u, _)| *u).collect();
+    for user in users {
+        // Sin faceta no hay `hasRole`: se mira el bit en el bitmap del storage
+        let stored = match source {
+            RoleSource::Facet => None,
+            RoleSource::Storage => Some(roles_of_user(env, diamond, user, source).await?),
+        };
+        for role in matrix
+            .seen_user_roles
+            .iter()
+            .filter(|(u, _)| *u == user)
+            .map(|(_, r)| *r)
+        {
+            let expected = matrix
+                .user_roles
+                .get(&user)
+                .is_some_and(|r| r.contains(&role));
+            let (live, call) = match &stored {
+                None => (
+                    has_role(env, diamond, user, role).await?,
+                    format!("hasRole({:?}, {})", user, role_name(role)),
+                ),
+                Some(roles) => (
+                    roles.contains(role),
+                    format!("storage userRoles[{:?}] ∋ {}", user, role_name(role)),
+                ),
+            };
+            if live != expected {
+                findings.push(AuditFinding {
+                    severity: Severity::Critical,
+                    message: t!(
+                        "{call} = {live} pero los eventos indican {expected}",
+                        call = call,
+                        live = live,
+                        expected = expected
+                    ),
+                });
+            }
+        }
+    }
+    let selectors: BTreeSet<[u8; 4]> = matrix.seen_function_roles.iter().map(|(s, _)| *s).collect();
+    for selector in selectors {
+        let stored = match source {
+            RoleSource::Facet => None,
+            RoleSource::Storage => Some(roles_of_function(env, diamond, selector, source).await?),
+        };
+        for role in matrix
+            .seen_function_roles
+            .iter()
+            .filter(|(s, _)| *s == selector)
+            .map(|(_, r)| *r)
+        {
+            let expected = matrix
+                .function_roles
+                .get(&selector)
+                .is_some_and(|r| r.contains(&role));
+            let (live, call) = match &stored {
+                None => (
+                    role_has_access(env, diamond, role, selector).await?,
+                    format!(
+                        "roleHasAccess({}, {})",
+                        role_name(role),
+                        format_selector(selector)
+                    ),
+                ),
+                Some(roles) => (
+                    roles.contains(role),
+                    format!(
+                        "storage functionRoles[{}] ∋ {}",
+                        format_selector(selector),
+                        role_name(role)
+                    ),
+                ),
+            };
+            if live != expected {
+                findings.push(AuditFinding {
+                    severity: Severity::Critical,
+                    message: t!(
+                        "{call} = {live} pero los eventos indican {expected}",
+                        call = call,
+                        live = live,
+                        expected = expected
+                    ),
+                });
+            }
+        }
+    }
+
+    // 2) Funciones sensibles abiertas a roles inesperados
+    for signature in SENSITIVE_FUNCTIONS {
+        let selector = ethers::utils::id(signature);
+        let Some(roles) = matrix.function_roles.get(&selector) else {
+            continue;
+        };
+        for role in roles {
+            if *role != DEFAULT_ADMIN_ROLE && *role != LOOP_ADMIN_ROLE {
+                findings.push(AuditFinding {
+                    severity: Severity::Critical,
+                    message: t!(
+                        "{function} es invocable por {role}",
+                        function = signature,
+                        role = role_name(*role)
+                    ),
+                });
+            }
+        }
+    }
+
+    // 3) Admins sospechosos
+    let admins = matrix.holders(DEFAULT_ADMIN_ROLE);
+    if admins.contains(&Address::zero()) {
+        findings.push(AuditFinding {
+            severity: Severity::Critical,
+            message: t!("address(0) tiene DEFAULT_ADMIN_ROLE"),
+        });
+    }
+    if admins.is_empty() {
+        findings.push(AuditFinding {
+            severity: Severity::Warning,
+            message: t!("Ningún usuario tiene DEFAULT_ADMIN_ROLE"),
+        });
+    }
+
+    // 4) En un loop, el admin debería ser su organización
+    let is_loop = state
+        .routes
+        .contains_key(&ethers::utils::id("claimAndRegister(bytes)"));
+    if is_loop {
+        match loop_organization(env, diamond).await? {
+            Some(organization) => {
+                for admin in admins.iter().filter(|a| **a != organization) {
+                    findings.push(AuditFinding {
+                        severity: Severity::Warning,
+                        message: t!(
+                            "El admin {admin} del loop no es su organización {organization}",
+                            admin = ?admin,
+                            organization = ?organization
+                        ),
+                    });
+                }
+                if !admins.contains(&organization) {
+                    findings.push(AuditFinding {
+                        severity: Severity::Warning,
+                        message: t!(
+                            "La organización {organization} no es admin de su loop",
+                            organization = ?organization
+                        ),
+                    });
+                }
+            }
+            None => findings.push(AuditFinding {
+                severity: Severity::Warning,
+                message: t!("No se encontró el LoopCreated de este loop"),
+            }),
+        }
+    }
+
+    // Selectores con permisos que el diamond ya no sirve
+    for selector in matrix.function_roles.keys() {
+        if !state.routes.contains_key(selector) {
+            findings.push(AuditFinding {
+                severity: Severity::Info,
+                message: t!(
+                    "Permisos para {selector} ({function}), que el diamond no expone",
+                    selector = format_selector(*selector),
+                    function = book.resolve(*selector).unwrap_or("?")
+                ),
+            });
+        }
+    }
+
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    Ok(PermissionAudit {
+        diamond,
+        history,
+        matrix,
+        findings,
+    })
+}
+
+pub fn print_permission_audit(audit: &PermissionAudit, book: &SelectorBook) {
+    println!("Diamond {:?}", audit.diamond);
+    println!();
+    println!(
+        "{}",
+        t!("Historial ({count} eventos):", count = audit.history.len())
+    );
+    for change in &audit.history {
+        match change {
+            PermissionChange::UserRole {
+                block,
+                user,
+                role,
+                enabled,
+            } => println!(
+                "  #{:<8} {}",
+                block.unwrap_or_default(),
+                t!(
+                    "{sign} {role} a {user}",
+                    sign = if *enabled { "+" } else { "-" },
+                    role = role_name(*role),
+                    user = ?user
+                )
+            ),
+            PermissionChange::FunctionAccess {
+                block,
+                selector,
+                role,
+                enabled,
+            } => println!(
+                "  #{:<8} {}",
+                block.unwrap_or_default(),
+                t!(
+                    "{sign} {role} en {function}",
+                    sign = if *enabled { "+" } else { "-" },
+                    role = role_name(*role),
+                    function = book
+                        .resolve(*selector)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format_selector(*selector))
+                )
+            ),
+        }
+    }
+    println!();
+    println!("{}", t!("Usuarios:"));
+    for (user, roles) in &audit.matrix.user_roles {
+        let names: Vec<String> = roles.iter().map(|r| role_name(*r)).collect();
+        println!("  {:?}: {}", user, names.join("|"));
+    }
+    println!("{}", t!("Funciones:"));
+    for (selector, roles) in &audit.matrix.function_roles {
+        let names: Vec<String> = roles.iter().map(|r| role_name(*r)).collect();
+        println!(
+            "  {}: {}",
+            book.resolve(*selector)
+                .map(str::to_string)
+                .unwrap_or_else(|| format_selector(*selector)),
+            names.join("|")
+        );
+    }
+    println!();
+    if audit.findings.is_empty() {
+        println!("{}", t!("✅ Sin hallazgos"));
+    }
+    for finding in &audit.findings {
+        println!("[{:?}] {}", finding.severity, finding.message);
+    }
+}
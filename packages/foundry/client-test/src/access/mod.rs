@@ -0,0 +1,5 @@
+pub mod roles;
+pub use roles::*;
+
+pub mod audit;
+pub use audit::*;
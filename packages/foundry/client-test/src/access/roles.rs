@@ -0,0 +1,338 @@
+use ethers::prelude::*;
+use eyre::Result;
+use std::collections::BTreeSet;
+
+use crate::diamond::{load_diamond_state, DiamondState};
+use crate::storage::{read_function_roles, read_user_roles};
+use crate::t;
+use crate::utils::{
+    format_selector, RpcProvider, SelectorBook, SignerClient, DEFAULT_ADMIN_ROLE, LOOP_ADMIN_ROLE,
+};
+use crate::Env;
+
+/// Bitmap `bytes32` de roles tal como lo guarda `AccessControlStorage`:
+/// el rol `n` es el bit `1 << n`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RoleSet(pub [u8; 32]);
+
+impl RoleSet {
+    pub fn from_roles(roles: &[u8]) -> Self {
+        let mut bitmap = U256::zero();
+        for role in roles {
+            bitmap |= U256::one() << *role;
+        }
+        let mut bytes = [0u8; 32];
+        bitmap.to_big_endian(&mut bytes);
+        Self(bytes)
+    }
+
+    fn bits(&self) -> U256 {
+        U256::from_big_endian(&self.0)
+    }
+
+    pub fn contains(&self, role: u8) -> bool {
+        self.bits().bit(role as usize)
+    }
+
+    pub fn roles(&self) -> Vec<u8> {
+        (0..=u8::MAX).filter(|role| self.contains(*role)).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits().is_zero()
+    }
+
+    /// Réplica local de `_canCall`: comparte algún rol con la función o es admin.
+    pub fn can_call(&self, function_roles: &RoleSet) -> bool {
+        !(self.bits() & function_roles.bits()).is_zero() || self.contains(DEFAULT_ADMIN_ROLE)
+    }
+}
+
+impl std::fmt::Display for RoleSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "-");
+        }
+        let names: Vec<String> = self.roles().into_iter().map(role_name).collect();
+        write!(f, "{}", names.join("|"))
+    }
+}
+
+/// Nombre legible de un rol de `Constants.sol`.
+pub fn role_name(role: u8) -> String {
+    match role {
+        DEFAULT_ADMIN_ROLE => "DEFAULT_ADMIN_ROLE".to_string(),
+        LOOP_ADMIN_ROLE => "LOOP_ADMIN_ROLE".to_string(),
+        other => format!("ROLE_{}", other),
+    }
+}
+
+/// Acepta el nombre de la constante o el número del rol.
+pub fn parse_role(input: &str) -> Result<u8> {
+    match input {
+        "DEFAULT_ADMIN_ROLE" | "default_admin" => Ok(DEFAULT_ADMIN_ROLE),
+        "LOOP_ADMIN_ROLE" | "loop_admin" => Ok(LOOP_ADMIN_ROLE),
+        other => other
+            .trim_start_matches("ROLE_")
+            .parse()
+            .map_err(|_| eyre::eyre!(t!("❌ Rol inválido: {role}", role = input))),
+    }
+}
+
+fn access_control(env: &Env, diamond: Address) -> Result<Contract<RpcProvider>> {
+    Ok(env.contract_at(diamond, "AccessControlFacet")?)
+}
+
+pub fn set_function_access_call(
+    env: &Env,
+    diamond: Address,
+    selector: [u8; 4],
+    role: u8,
+    enabled: bool,
+) -> Result<ContractCall<SignerClient, ()>> {
+    let contract = access_control(env, diamond)?.connect(env.signer()?);
+    Ok(contract
+        .method::<([u8; 4], u8, bool), ()>("setFunctionAccess", (selector, role, enabled))?)
+}
+
+pub async fn set_function_access(
+    env: &Env,
+    diamond: Address,
+    selector: [u8; 4],
+    role: u8,
+    enabled: bool,
+) -> Result<H256> {
+    let call = set_function_access_call(env, diamond, selector, role, enabled)?;
+    let pending = call.send().await?;
+    Ok(pending.tx_hash())
+}
+
+pub fn set_user_role_call(
+    env: &Env,
+    diamond: Address,
+    user: Address,
+    role: u8,
+    enabled: bool,
+) -> Result<ContractCall<SignerClient, ()>> {
+    let contract = access_control(env, diamond)?.connect(env.signer()?);
+    Ok(contract.method::<(Address, u8, bool), ()>("setUserRole", (user, role, enabled))?)
+}
+
+pub async fn set_user_role(
+    env: &Env,
+    diamond: Address,
+    user: Address,
+    role: u8,
+    enabled: bool,
+) -> Result<H256> {
+    let call = set_user_role_call(env, diamond, user, role, enabled)?;
+    let pending = call.send().await?;
+    Ok(pending.tx_hash())
+}
+
+pub async fn can_call(
+    env: &Env,
+    diamond: Address,
+    user: Address,
+    selector: [u8; 4],
+) -> Result<bool> {
+    Ok(access_control(env, diamond)?
+        .method::<(Address, [u8; 4]), bool>("canCall", (user, selector))?
+        .call()
+        .await?)
+}
+
+pub async fn user_roles(env: &Env, diamond: Address, user: Address) -> Result<RoleSet> {
+    let bitmap: [u8; 32] = access_control(env, diamond)?
+        .method::<Address, [u8; 32]>("userRoles", user)?
+        .call()
+        .await?;
+    Ok(RoleSet(bitmap))
+}
+
+pub async fn function_roles(env: &Env, diamond: Address, selector: [u8; 4]) -> Result<RoleSet> {
+    let bitmap: [u8; 32] = access_control(env, diamond)?
+        .method::<[u8; 4], [u8; 32]>("functionRoles", selector)?
+        .call()
+        .await?;
+    Ok(RoleSet(bitmap))
+}
+
+pub async fn has_role(env: &Env, diamond: Address, user: Address, role: u8) -> Result<bool> {
+    Ok(access_control(env, diamond)?
+        .method::<(Address, u8), bool>("hasRole", (user, role))?
+        .call()
+        .await?)
+}
+
+pub async fn role_has_access(
+    env: &Env,
+    diamond: Address,
+    role: u8,
+    selector: [u8; 4],
+) -> Result<bool> {
+    Ok(access_control(env, diamond)?
+        .method::<(u8, [u8; 4]), bool>("roleHasAccess", (role, selector))?
+        .call()
+        .await?)
+}
+
+/// De dónde se leen los bitmaps de `AccessControlStorage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoleSource {
+    /// Getters de AccessControlFacet (`userRoles`, `functionRoles`).
+    Facet,
+    /// `eth_getStorageAt` sobre `keccak256("access-control.storage")`: los
+    /// loops y las organizaciones usan `onlyAuthorized` sin tener la faceta.
+    Storage,
+}
+
+impl RoleSource {
+    pub fn of(state: &DiamondState) -> Self {
+        if state
+            .routes
+            .contains_key(&ethers::utils::id("userRoles(address)"))
+        {
+            Self::Facet
+        } else {
+            Self::Storage
+        }
+    }
+}
+
+/// Consulta al loupe si el diamond enruta los getters de AccessControlFacet.
+pub async fn role_source(env: &Env, diamond: Address) -> Result<RoleSource> {
+    let facet: Address = env
+        .contract_at(diamond, "DiamondLoupeFacet")?
+        .method::<[u8; 4], Address>("facetAddress", ethers::utils::id("userRoles(address)"))?
+        .call()
+        .await?;
+    Ok(if facet.is_zero() {
+        RoleSource::Storage
+    } else {
+        RoleSource::Facet
+    })
+}
+
+/// `userRoles(user)` por la faceta o directamente del storage.
+pub async fn roles_of_user(
+    env: &Env,
+    diamond: Address,
+    user: Address,
+    source: RoleSource,
+) -> Result<RoleSet> {
+    match source {
+        RoleSource::Facet => user_roles(env, diamond, user).await,
+        RoleSource::Storage => Ok(RoleSet(read_user_roles(env, diamond, user).await?)),
+    }
+}
+
+/// `functionRoles(selector)` por la faceta o directamente del storage.
+pub async fn roles_of_function(
+    env: &Env,
+    diamond: Address,
+    selector: [u8; 4],
+    source: RoleSource,
+) -> Result<RoleSet> {
+    match source {
+        RoleSource::Facet => function_roles(env, diamond, selector).await,
+        RoleSource::Storage => Ok(RoleSet(read_function_roles(env, diamond, selector).await?)),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionPermission {
+    pub selector: [u8; 4],
+    pub signature: Option<String>,
+    pub roles: RoleSet,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserPermission {
+    pub user: Address,
+    pub roles: RoleSet,
+    pub callable: Vec<[u8; 4]>,
+}
+
+/// Informe "quién puede llamar qué" de un diamond.
+#[derive(Debug, Clone)]
+pub struct AccessReport {
+    pub diamond: Address,
+    /// `Storage` si el diamond no expone AccessControlFacet (p.ej. los loops).
+    pub source: RoleSource,
+    pub functions: Vec<FunctionPermission>,
+    pub users: Vec<UserPermission>,
+}
+
+/// Construye el informe de permisos para los selectores del diamond y los `users` dados.
+pub async fn access_report(env: &Env, diamond: Address, users: &[Address]) -> Result<AccessReport> {
+    let state = load_diamond_state(env, diamond).await?;
+    let book = SelectorBook::from_artifacts()?;
+
+    let source = RoleSource::of(&state);
+
+    let selectors: BTreeSet<[u8; 4]> = state.routes.keys().copied().collect();
+    let mut functions = Vec::with_capacity(selectors.len());
+    for selector in selectors {
+        functions.push(FunctionPermission {
+            selector,
+            signature: book.resolve(selector).map(str::to_string),
+            roles: roles_of_function(env, diamond, selector, source).await?,
+        });
+    }
+
+    let mut report_users = Vec::with_capacity(users.len());
+    for user in users {
+        let roles = roles_of_user(env, diamond, *user, source).await?;
+        let callable = functions
+            .iter()
+            .filter(|f| roles.can_call(&f.roles))
+            .map(|f| f.selector)
+            .collect();
+        report_users.push(UserPermission {
+            user: *user,
+            roles,
+            callable,
+        });
+    }
+
+    Ok(AccessReport {
+        diamond,
+        source,
+        functions,
+        users: report_users,
+    })
+}
+
+pub fn print_access_report(report: &AccessReport) {
+    println!("Diamond {:?}", report.diamond);
+    if report.source == RoleSource::Storage {
+        println!(
+            "{}",
+            t!("El diamond no expone AccessControlFacet: roles leídos del storage")
+        );
+    }
+    println!();
+    println!("{:<10} {:<48} ROLES", "SELECTOR", t!("FUNCIÓN"));
+    for function in &report.functions {
+        println!(
+            "{:<10} {:<48} {}",
+            format_selector(function.selector),
+            function.signature.as_deref().unwrap_or("?"),
+            function.roles
+        );
+    }
+    println!();
+    for user in &report.users {
+        println!("{:?} roles: {}", user.user, user.roles);
+        for selector in &user.callable {
+            let name = report
+                .functions
+                .iter()
+                .find(|f| f.selector == *selector)
+                .and_then(|f| f.signature.clone())
+                .unwrap_or_else(|| format_selector(*selector));
+            println!("    {}", t!("puede llamar {function}", function = name));
+        }
+    }
+}
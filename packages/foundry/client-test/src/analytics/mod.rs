@@ -0,0 +1,4 @@
+pub mod participation;
+pub mod report;
+pub use participation::*;
+pub use report::*;
@@ -0,0 +1,204 @@
+use ethers::contract::parse_log;
+use ethers::prelude::*;
+use eyre::Result;
+use std::collections::BTreeMap;
+
+use crate::events::{ClaimFilter, RegisterFilter};
+use crate::functions::current_period;
+use crate::t;
+use crate::Env;
+
+/// Qué pasó con un periodo para el que el usuario se registró.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodStatus {
+    Claimed(U256),
+    /// El periodo cerró sin que cobrara.
+    Missed,
+    /// Es el periodo en curso y aún puede cobrar.
+    Pending,
+    /// Registrado para un periodo que todavía no empieza.
+    Upcoming,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PeriodRecord {
+    pub period: U256,
+    pub status: PeriodStatus,
+}
+
+/// Historial de un usuario en un loop.
+#[derive(Debug, Clone)]
+pub struct LoopParticipation {
+    pub loop_address: Address,
+    pub current_period: U256,
+    pub periods: Vec<PeriodRecord>,
+    pub earned: U256,
+    pub current_streak: u64,
+    pub longest_streak: u64,
+}
+
+/// Rachas de periodos consecutivos cobrados: `(actual, más larga)`.
+/// Un periodo en curso aún sin cobrar no rompe la actual.
+pub fn streaks(periods: &[PeriodRecord], current_period: U256) -> (u64, u64) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<U256> = None;
+    for record in periods {
+        match record.status {
+            PeriodStatus::Claimed(_) => {
+                let consecutive = previous.is_some_and(|p| p + 1 == record.period);
+                run = if consecutive { run + 1 } else { 1 };
+                previous = Some(record.period);
+                longest = longest.max(run);
+            }
+            PeriodStatus::Missed => {
+                run = 0;
+                previous = None;
+            }
+            PeriodStatus::Pending | PeriodStatus::Upcoming => {}
+        }
+    }
+
+    // La racha sigue viva si el último cobro fue en este periodo o en el anterior
+    let current = match previous {
+        Some(last) if last + 1 >= current_period => run,
+        _ => 0,
+    };
+    (current, longest)
+}
+
+/// Historial de `user` en todos los loops en los que se registró, desde `from_block`.
+pub async fn user_participation(
+    env: &Env,
+    user: Address,
+    from_block: u64,
+) -> Result<Vec<LoopParticipation>> {
+    let provider = env.provider()?;
+    // Sin filtro de dirección: `sender`/`claimer` van indexados en topic1
+    let filter = Filter::new()
+        .from_block(from_block)
+        .topic0(vec![RegisterFilter::signature(), ClaimFilter::signature()])
+        .topic1(H256::from(user));
+    let logs = provider.get_logs(&filter).await?;
+
+    let mut registered: BTreeMap<Address, BTreeMap<U256, Option<U256>>> = BTreeMap::new();
+    for log in &logs {
+        if let Ok(event) = parse_log::<RegisterFilter>(log.clone()) {
+            registered
+                .entry(log.address)
+                .or_default()
+                .entry(event.period_number)
+                .or_insert(None);
+        }
+    }
+    for log in logs {
+        let loop_address = log.address;
+        if let Ok(event) = parse_log::<ClaimFilter>(log) {
+            registered
+                .entry(loop_address)
+                .or_default()
+                .insert(event.period_number, Some(event.payout));
+        }
+    }
+
+    let mut history = Vec::with_capacity(registered.len());
+    for (loop_address, periods) in registered {
+        // Otro contrato con un evento de la misma firma: no es un loop
+        let Ok(current) = current_period(env, loop_address).await else {
+            continue;
+        };
+        let periods: Vec<PeriodRecord> = periods
+            .into_iter()
+            .map(|(period, payout)| {
+                let status = match payout {
+                    Some(payout) => PeriodStatus::Claimed(payout),
+                    None if period < current => PeriodStatus::Missed,
+                    None if period == current => PeriodStatus::Pending,
+                    None => PeriodStatus::Upcoming,
+                };
+                PeriodRecord { period, status }
+            })
+            .collect();
+        let earned = periods
+            .iter()
+            .fold(U256::zero(), |total, r| match r.status {
+                PeriodStatus::Claimed(payout) => total + payout,
+                _ => total,
+            });
+        let (current_streak, longest_streak) = streaks(&periods, current);
+        history.push(LoopParticipation {
+            loop_address,
+            current_period: current,
+            periods,
+            earned,
+            current_streak,
+            longest_streak,
+        });
+    }
+    Ok(history)
+}
+
+pub fn print_user_participation(user: Address, history: &[LoopParticipation]) {
+    if history.is_empty() {
+        println!(
+            "{}",
+            t!("{user} no se ha registrado en ningún loop", user = ?user)
+        );
+        return;
+    }
+    for loop_history in history {
+        println!(
+            "{}",
+            t!(
+                "Loop {address}: ganado {earned} | racha actual {current} | racha más larga {longest}",
+                address = ?loop_history.loop_address,
+                earned = loop_history.earned,
+                current = loop_history.current_streak,
+                longest = loop_history.longest_streak
+            )
+        );
+        for record in &loop_history.periods {
+            let status = match record.status {
+                PeriodStatus::Claimed(payout) => t!("cobrado {payout}", payout = payout),
+                PeriodStatus::Missed => t!("perdido"),
+                PeriodStatus::Pending => t!("en curso"),
+                PeriodStatus::Upcoming => t!("registrado"),
+            };
+            println!("    {} {:>6}: {}", t!("periodo"), record.period, status);
+        }
+    }
+}
+
+pub fn participation_json(user: Address, history: &[LoopParticipation]) -> serde_json::Value {
+    let loops: Vec<serde_json::Value> = history
+        .iter()
+        .map(|loop_history| {
+            let periods: Vec<serde_json::Value> = loop_history
+                .periods
+                .iter()
+                .map(|record| {
+                    let (status, payout) = match record.status {
+                        PeriodStatus::Claimed(payout) => ("claimed", Some(payout.to_string())),
+                        PeriodStatus::Missed => ("missed", None),
+                        PeriodStatus::Pending => ("pending", None),
+                        PeriodStatus::Upcoming => ("upcoming", None),
+                    };
+                    serde_json::json!({
+                        "period": record.period.as_u64(),
+                        "status": status,
+                        "payout": payout,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "loop": loop_history.loop_address,
+                "current_period": loop_history.current_period.as_u64(),
+                "earned": loop_history.earned.to_string(),
+                "current_streak": loop_history.current_streak,
+                "longest_streak": loop_history.longest_streak,
+                "periods": periods,
+            })
+        })
+        .collect();
+    serde_json::json!({ "user": user, "loops": loops })
+}
@@ -0,0 +1,269 @@
+use ethers::prelude::*;
+use ethers::utils::format_units;
+use eyre::Result;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::events::{loop_history, LoopEvent};
+use crate::functions::{current_period, loop_details, LoopDetails};
+use crate::t;
+use crate::Env;
+
+/// Límite de la proyección de runway, para no iterar sin fin con umbrales a 0.
+const MAX_PROJECTED_PERIODS: u64 = 10_000;
+
+/// Participación y pagos de un periodo.
+#[derive(Debug, Clone, Default)]
+pub struct PeriodStats {
+    pub period: U256,
+    /// Usuarios que se registraron para este periodo (`Register.periodNumber`).
+    pub registered: u64,
+    /// Usuarios que cobraron en este periodo.
+    pub claimed: u64,
+    /// Pago individual observado (el de los `Claim` del periodo).
+    pub payout_per_user: U256,
+    pub distributed: U256,
+}
+
+impl PeriodStats {
+    /// Fracción de registrados que llegó a cobrar.
+    pub fn claim_rate(&self) -> f64 {
+        if self.registered == 0 {
+            return 0.0;
+        }
+        self.claimed as f64 / self.registered as f64
+    }
+}
+
+/// Informe completo de un loop.
+#[derive(Debug, Clone)]
+pub struct LoopAnalytics {
+    pub loop_address: Address,
+    pub details: LoopDetails,
+    pub decimals: u8,
+    pub current_period: U256,
+    pub periods: Vec<PeriodStats>,
+    pub total_distributed: U256,
+    pub total_withdrawn: U256,
+    pub balance: U256,
+    pub threshold: U256,
+    /// Periodos hasta bajar del umbral si todos los registrados cobran.
+    pub runway_full: Option<u64>,
+    /// Periodos hasta bajar del umbral con la tasa de cobro observada.
+    pub runway_observed: Option<u64>,
+}
+
+/// Cuántos periodos tarda `balance` en bajar de `threshold` si cada periodo se
+/// reparte `rate` (0..1) del balance. `None` si no baja nunca (o supera el límite).
+pub fn projected_runway(balance: U256, threshold: U256, rate: f64) -> Option<u64> {
+    if balance < threshold {
+        return Some(0);
+    }
+    if rate <= 0.0 {
+        return None;
+    }
+    // Se trabaja en f64: basta para una proyección y evita desbordar con wei
+    let (mut balance, threshold) = (u256_to_f64(balance), u256_to_f64(threshold));
+    for period in 1..=MAX_PROJECTED_PERIODS {
+        balance -= balance * rate;
+        if balance < threshold {
+            return Some(period);
+        }
+    }
+    None
+}
+
+fn u256_to_f64(value: U256) -> f64 {
+    value.to_string().parse().unwrap_or(f64::MAX)
+}
+
+/// Construye el informe a partir de los eventos desde `from_block`.
+pub async fn loop_analytics(
+    env: &Env,
+    loop_address: Address,
+    from_block: u64,
+    threshold: U256,
+) -> Result<LoopAnalytics> {
+    let details = loop_details(env, loop_address).await?;
+    let current = current_period(env, loop_address).await?;
+    let token = env.contract_at(details.token, "TestToken")?;
+    let balance: U256 = token
+        .method::<Address, U256>("balanceOf", loop_address)?
+        .call()
+        .await?;
+    let decimals: u8 = token.method::<(), u8>("decimals", ())?.call().await?;
+
+    let mut periods: BTreeMap<U256, PeriodStats> = BTreeMap::new();
+    let mut total_withdrawn = U256::zero();
+    for event in loop_history(env, loop_address, from_block).await? {
+        match event {
+            LoopEvent::Register { event, .. } => {
+                let stats = periods.entry(event.period_number).or_default();
+                stats.registered += 1;
+            }
+            LoopEvent::Claim { event, .. } => {
+                let stats = periods.entry(event.period_number).or_default();
+                stats.claimed += 1;
+                stats.payout_per_user = stats.payout_per_user.max(event.payout);
+                stats.distributed += event.payout;
+            }
+            LoopEvent::Withdraw { event, .. } => total_withdrawn += event.amount,
+        }
+    }
+    let periods: Vec<PeriodStats> = periods
+        .into_iter()
+        .map(|(period, stats)| PeriodStats { period, ..stats })
+        .collect();
+    let total_distributed = periods
+        .iter()
+        .fold(U256::zero(), |total, p| total + p.distributed);
+
+    // Tasa de reparto por periodo: percentPerPeriod si cobran todos los
+    // registrados, escalada por la tasa de cobro media de los periodos cerrados
+    let full_rate = details.percent_per_period.as_u64() as f64 / 100.0;
+    let closed: Vec<&PeriodStats> = periods
+        .iter()
+        .filter(|p| p.period < current && p.registered > 0)
+        .collect();
+    let observed_rate = if closed.is_empty() {
+        full_rate
+    } else {
+        full_rate * closed.iter().map(|p| p.claim_rate()).sum::<f64>() / closed.len() as f64
+    };
+
+    Ok(LoopAnalytics {
+        loop_address,
+        details,
+        decimals,
+        current_period: current,
+        periods,
+        total_distributed,
+        total_withdrawn,
+        balance,
+        threshold,
+        runway_full: projected_runway(balance, threshold, full_rate),
+        runway_observed: projected_runway(balance, threshold, observed_rate),
+    })
+}
+
+fn amount(value: U256, decimals: u8) -> String {
+    format_units(value, decimals as u32).unwrap_or_else(|_| value.to_string())
+}
+
+pub fn analytics_json(report: &LoopAnalytics) -> Value {
+    let periods: Vec<Value> = report
+        .periods
+        .iter()
+        .map(|p| {
+            json!({
+                "period": p.period.as_u64(),
+                "registered": p.registered,
+                "claimed": p.claimed,
+                "claim_rate": p.claim_rate(),
+                "payout_per_user": amount(p.payout_per_user, report.decimals),
+                "distributed": amount(p.distributed, report.decimals),
+            })
+        })
+        .collect();
+    json!({
+        "loop": format!("{:?}", report.loop_address),
+        "token": format!("{:?}", report.details.token),
+        "period_length": report.details.period_length.as_u64(),
+        "percent_per_period": report.details.percent_per_period.as_u64(),
+        "current_period": report.current_period.as_u64(),
+        "total_distributed": amount(report.total_distributed, report.decimals),
+        "total_withdrawn": amount(report.total_withdrawn, report.decimals),
+        "balance": amount(report.balance, report.decimals),
+        "threshold": amount(report.threshold, report.decimals),
+        "runway_periods_full": report.runway_full,
+        "runway_periods_observed": report.runway_observed,
+        "periods": periods,
+    })
+}
+
+/// Una fila por periodo.
+pub fn analytics_csv(report: &LoopAnalytics) -> String {
+    let mut csv =
+        String::from("period,registered,claimed,claim_rate,payout_per_user,distributed\n");
+    for p in &report.periods {
+        csv.push_str(&format!(
+            "{},{},{},{:.4},{},{}\n",
+            p.period,
+            p.registered,
+            p.claimed,
+            p.claim_rate(),
+            amount(p.payout_per_user, report.decimals),
+            amount(p.distributed, report.decimals)
+        ));
+    }
+    csv
+}
+
+pub fn write_analytics(
+    report: &LoopAnalytics,
+    csv: Option<&Path>,
+    json: Option<&Path>,
+) -> Result<()> {
+    if let Some(path) = csv {
+        fs::write(path, analytics_csv(report))?;
+    }
+    if let Some(path) = json {
+        fs::write(path, serde_json::to_string_pretty(&analytics_json(report))?)?;
+    }
+    Ok(())
+}
+
+pub fn print_loop_analytics(report: &LoopAnalytics) {
+    let runway = |periods: Option<u64>| match periods {
+        Some(n) => t!("{count} periodos", count = n),
+        None => t!("sin límite"),
+    };
+    println!(
+        "{}",
+        t!(
+            "Loop {address} (periodo en curso {period})",
+            address = ?report.loop_address,
+            period = report.current_period
+        )
+    );
+    println!();
+    println!(
+        "{:>8} {:>10} {:>8} {:>7} {:>20} {:>20}",
+        t!("PERIODO"),
+        t!("REGISTROS"),
+        "CLAIMS",
+        t!("TASA"),
+        t!("PAGO/USUARIO"),
+        t!("REPARTIDO")
+    );
+    for p in &report.periods {
+        println!(
+            "{:>8} {:>10} {:>8} {:>6.1}% {:>20} {:>20}",
+            p.period,
+            p.registered,
+            p.claimed,
+            p.claim_rate() * 100.0,
+            amount(p.payout_per_user, report.decimals),
+            amount(p.distributed, report.decimals)
+        );
+    }
+    println!();
+    for (label, value) in [
+        (t!("Total repartido:"), report.total_distributed),
+        (t!("Total retirado:"), report.total_withdrawn),
+        (t!("Balance actual:"), report.balance),
+    ] {
+        println!("{:<18}{}", label, amount(value, report.decimals));
+    }
+    println!(
+        "{}",
+        t!(
+            "Runway hasta {threshold}: {full} (todos cobran), {observed} (tasa observada)",
+            threshold = amount(report.threshold, report.decimals),
+            full = runway(report.runway_full),
+            observed = runway(report.runway_observed)
+        )
+    );
+}
@@ -0,0 +1,210 @@
+use ethers::prelude::*;
+
+use crate::batch::{decode_result, BatchReader, ViewCall};
+use crate::error::{ClientError, ClientResult};
+use crate::functions::LoopDetails;
+use crate::t;
+
+#[derive(Debug, Clone, EthCall)]
+#[ethcall(name = "getClaimerStatus", abi = "getClaimerStatus(address)")]
+struct GetClaimerStatusCall {
+    user: Address,
+}
+
+#[derive(Debug, Clone, EthAbiType, EthAbiCodec)]
+struct GetClaimerStatusReturn {
+    is_registered: bool,
+    has_claimed: bool,
+}
+
+#[derive(Debug, Clone, EthCall)]
+#[ethcall(name = "getLoopDetails", abi = "getLoopDetails()")]
+struct GetLoopDetailsCall;
+
+#[derive(Debug, Clone, EthAbiType, EthAbiCodec)]
+struct GetLoopDetailsReturn {
+    token: Address,
+    period_length: U256,
+    percent_per_period: U256,
+    first_period_start: U256,
+}
+
+#[derive(Debug, Clone, EthCall)]
+#[ethcall(name = "getCurrentPeriodData", abi = "getCurrentPeriodData()")]
+struct GetCurrentPeriodDataCall;
+
+#[derive(Debug, Clone, EthAbiType, EthAbiCodec)]
+struct GetCurrentPeriodDataReturn {
+    total_registered_users: U256,
+    max_payout: U256,
+}
+
+#[derive(Debug, Clone, EthCall)]
+#[ethcall(name = "balanceOf", abi = "balanceOf(address)")]
+struct BalanceOfCall {
+    holder: Address,
+}
+
+/// `getClaimerStatus(user)` de un loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimerStatus {
+    pub loop_address: Address,
+    pub user: Address,
+    pub is_registered: bool,
+    pub has_claimed: bool,
+}
+
+/// `getCurrentPeriodData()` de un loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodData {
+    pub total_registered_users: U256,
+    pub max_payout: U256,
+}
+
+/// Detalles y periodo en curso de un loop, leídos en el mismo lote.
+#[derive(Debug, Clone, Copy)]
+pub struct LoopSnapshot {
+    pub loop_address: Address,
+    pub details: LoopDetails,
+    pub period_data: PeriodData,
+}
+
+impl From<GetLoopDetailsReturn> for LoopDetails {
+    fn from(value: GetLoopDetailsReturn) -> Self {
+        Self {
+            token: value.token,
+            period_length: value.period_length,
+            percent_per_period: value.percent_per_period,
+            first_period_start: value.first_period_start,
+        }
+    }
+}
+
+impl From<GetCurrentPeriodDataReturn> for PeriodData {
+    fn from(value: GetCurrentPeriodDataReturn) -> Self {
+        Self {
+            total_registered_users: value.total_registered_users,
+            max_payout: value.max_payout,
+        }
+    }
+}
+
+impl BatchReader {
+    /// Estado de cada par `(loop, user)`, en el mismo orden.
+    pub async fn claimer_statuses(
+        &self,
+        pairs: &[(Address, Address)],
+    ) -> ClientResult<Vec<ClientResult<ClaimerStatus>>> {
+        let calls: Vec<ViewCall> = pairs
+            .iter()
+            .map(|(loop_address, user)| {
+                ViewCall::new(*loop_address, GetClaimerStatusCall { user: *user })
+            })
+            .collect();
+        let results = self.call_decoded::<GetClaimerStatusReturn>(&calls).await?;
+        Ok(pairs
+            .iter()
+            .zip(results)
+            .map(|((loop_address, user), result)| {
+                result.map(|status| ClaimerStatus {
+                    loop_address: *loop_address,
+                    user: *user,
+                    is_registered: status.is_registered,
+                    has_claimed: status.has_claimed,
+                })
+            })
+            .collect())
+    }
+
+    /// Estado de muchos usuarios en un mismo loop.
+    pub async fn loop_claimer_statuses(
+        &self,
+        loop_address: Address,
+        users: &[Address],
+    ) -> ClientResult<Vec<ClientResult<ClaimerStatus>>> {
+        let pairs: Vec<(Address, Address)> =
+            users.iter().map(|user| (loop_address, *user)).collect();
+        self.claimer_statuses(&pairs).await
+    }
+
+    pub async fn loop_details(
+        &self,
+        loops: &[Address],
+    ) -> ClientResult<Vec<ClientResult<LoopDetails>>> {
+        let calls: Vec<ViewCall> = loops
+            .iter()
+            .map(|loop_address| ViewCall::new(*loop_address, GetLoopDetailsCall))
+            .collect();
+        Ok(self
+            .call_decoded::<GetLoopDetailsReturn>(&calls)
+            .await?
+            .into_iter()
+            .map(|result| result.map(Into::into))
+            .collect())
+    }
+
+    pub async fn current_period_data(
+        &self,
+        loops: &[Address],
+    ) -> ClientResult<Vec<ClientResult<PeriodData>>> {
+        let calls: Vec<ViewCall> = loops
+            .iter()
+            .map(|loop_address| ViewCall::new(*loop_address, GetCurrentPeriodDataCall))
+            .collect();
+        Ok(self
+            .call_decoded::<GetCurrentPeriodDataReturn>(&calls)
+            .await?
+            .into_iter()
+            .map(|result| result.map(Into::into))
+            .collect())
+    }
+
+    /// `getLoopDetails` y `getCurrentPeriodData` de cada loop en un solo lote.
+    pub async fn loop_snapshots(
+        &self,
+        loops: &[Address],
+    ) -> ClientResult<Vec<ClientResult<LoopSnapshot>>> {
+        let calls: Vec<ViewCall> = loops
+            .iter()
+            .flat_map(|loop_address| {
+                [
+                    ViewCall::new(*loop_address, GetLoopDetailsCall),
+                    ViewCall::new(*loop_address, GetCurrentPeriodDataCall),
+                ]
+            })
+            .collect();
+        let mut results = self.call(&calls).await?.into_iter();
+        let mut next = || {
+            results
+                .next()
+                .unwrap_or_else(|| Err(ClientError::decode(t!("faltan resultados en el lote"))))
+        };
+        let mut snapshots = Vec::with_capacity(loops.len());
+        for loop_address in loops {
+            let (details, period_data) = (next(), next());
+            snapshots.push(
+                decode_result::<GetLoopDetailsReturn>(details).and_then(|details| {
+                    Ok(LoopSnapshot {
+                        loop_address: *loop_address,
+                        details: details.into(),
+                        period_data: decode_result::<GetCurrentPeriodDataReturn>(period_data)?
+                            .into(),
+                    })
+                }),
+            );
+        }
+        Ok(snapshots)
+    }
+
+    /// `balanceOf(holder)` de cada par `(token, holder)`.
+    pub async fn token_balances(
+        &self,
+        pairs: &[(Address, Address)],
+    ) -> ClientResult<Vec<ClientResult<U256>>> {
+        let calls: Vec<ViewCall> = pairs
+            .iter()
+            .map(|(token, holder)| ViewCall::new(*token, BalanceOfCall { holder: *holder }))
+            .collect();
+        self.call_decoded::<U256>(&calls).await
+    }
+}
@@ -0,0 +1,10 @@
+//! Lecturas en lote: agrupa muchas llamadas `view` (estado de claimers, detalles
+//! y periodo de loops) en unos pocos viajes al nodo, con Multicall3 si está
+//! desplegado o con lotes JSON-RPC si no.
+
+pub mod loop_reads;
+pub use loop_reads::*;
+pub mod multicall3;
+pub use multicall3::*;
+pub mod reader;
+pub use reader::*;
@@ -0,0 +1,68 @@
+use ethers::abi::{AbiDecode, AbiEncode};
+use ethers::contract::multicall_contract::{Aggregate3Call, Aggregate3Return, Call3};
+use ethers::prelude::*;
+use tracing::{info, instrument};
+
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+use crate::utils::load_deployed_bytecode;
+
+/// Dirección canónica de Multicall3, la misma en todas las redes.
+pub use ethers::contract::MULTICALL_ADDRESS;
+
+/// `true` si hay código en `address`.
+pub async fn multicall3_deployed<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    address: Address,
+) -> ClientResult<bool> {
+    Ok(!provider.get_code(address, None).await?.is_empty())
+}
+
+/// Devuelve la dirección de Multicall3. Si no está y la red es anvil lo instala
+/// con `anvil_setCode` a partir del artifact de `contracts/utils/Multicall3.sol`;
+/// en cualquier otra red es un error de configuración.
+#[instrument(skip_all)]
+pub async fn ensure_multicall3<P: JsonRpcClient>(provider: &Provider<P>) -> ClientResult<Address> {
+    if multicall3_deployed(provider, MULTICALL_ADDRESS).await? {
+        return Ok(MULTICALL_ADDRESS);
+    }
+    let client = provider.client_version().await?;
+    if !client.to_lowercase().starts_with("anvil") {
+        return Err(ClientError::config(t!(
+            "Multicall3 no está desplegado en {address} y la red no es anvil ({client})",
+            address = ?MULTICALL_ADDRESS,
+            client = client
+        )));
+    }
+    let code = Bytes::from(load_deployed_bytecode("Multicall3")?);
+    provider
+        .request::<_, serde_json::Value>("anvil_setCode", (MULTICALL_ADDRESS, code))
+        .await?;
+    info!(address = ?MULTICALL_ADDRESS, "Multicall3 instalado en anvil");
+    Ok(MULTICALL_ADDRESS)
+}
+
+/// `aggregate3` con `allowFailure` en todas las llamadas: un revert no tumba el lote.
+pub fn aggregate3_calldata(calls: &[(Address, Bytes)]) -> Bytes {
+    let calls = calls
+        .iter()
+        .map(|(target, data)| Call3 {
+            target: *target,
+            allow_failure: true,
+            call_data: data.clone(),
+        })
+        .collect();
+    Aggregate3Call { calls }.encode().into()
+}
+
+/// Decodifica el retorno de `aggregate3`: `(success, returnData)` por llamada.
+pub fn decode_aggregate3(data: &[u8]) -> ClientResult<Vec<(bool, Bytes)>> {
+    let decoded = Aggregate3Return::decode(data).map_err(|e| {
+        ClientError::decode(t!("retorno de aggregate3 inválido: {error}", error = e))
+    })?;
+    Ok(decoded
+        .return_data
+        .into_iter()
+        .map(|result| (result.success, result.return_data))
+        .collect())
+}
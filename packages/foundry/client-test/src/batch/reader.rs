@@ -0,0 +1,222 @@
+use ethers::abi::{AbiDecode, AbiEncode};
+use ethers::prelude::*;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::{debug, instrument};
+
+use crate::batch::{aggregate3_calldata, decode_aggregate3, ensure_multicall3};
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+use crate::transport::{
+    BatchClient, BatchResponse, HttpBatch, MeteredTransport, RpcFailure, TransportError,
+};
+use crate::Env;
+
+/// Llamadas por `aggregate3` o por lote JSON-RPC. Con 500 un barrido de miles
+/// de claimers son unos pocos viajes y cada `eth_call` queda lejos del gas cap.
+pub const DEFAULT_CHUNK_SIZE: usize = 500;
+
+/// Llamada `view` ya codificada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewCall {
+    pub target: Address,
+    pub data: Bytes,
+}
+
+impl ViewCall {
+    pub fn new(target: Address, call: impl AbiEncode) -> Self {
+        Self {
+            target,
+            data: call.encode().into(),
+        }
+    }
+}
+
+/// Cómo se agrupan las llamadas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchBackend {
+    /// Un `eth_call` a `aggregate3` por tramo.
+    Multicall3(Address),
+    /// Un lote JSON-RPC de `eth_call` por tramo.
+    JsonRpc,
+}
+
+/// Ejecuta muchas llamadas `view` en pocos viajes. Cada llamada tiene su propio
+/// resultado: un revert (p.ej. una dirección que no es un loop) no invalida el resto.
+pub struct BatchReader {
+    client: Arc<dyn BatchClient>,
+    backend: BatchBackend,
+    chunk_size: usize,
+    round_trips: AtomicUsize,
+}
+
+impl BatchReader {
+    pub fn new(client: Arc<dyn BatchClient>, backend: BatchBackend) -> Self {
+        Self {
+            client,
+            backend,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            round_trips: AtomicUsize::new(0),
+        }
+    }
+
+    /// Lotes JSON-RPC contra `env.rpc_url`, sin depender de Multicall3.
+    pub fn json_rpc(env: &Env) -> ClientResult<Self> {
+        Ok(Self::new(
+            Arc::new(MeteredTransport::new(HttpBatch::new(&env.rpc_url)?)),
+            BatchBackend::JsonRpc,
+        ))
+    }
+
+    /// Usa Multicall3 si está (o si se puede instalar en anvil) y, si no, lotes JSON-RPC.
+    #[instrument(skip_all)]
+    pub async fn connect(env: &Env) -> ClientResult<Self> {
+        let backend = match ensure_multicall3(&env.provider()?).await {
+            Ok(address) => BatchBackend::Multicall3(address),
+            Err(ClientError::Config(reason)) => {
+                debug!(%reason, "sin Multicall3: se usan lotes JSON-RPC");
+                BatchBackend::JsonRpc
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(Self::new(
+            Arc::new(MeteredTransport::new(HttpBatch::new(&env.rpc_url)?)),
+            backend,
+        ))
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    pub fn backend(&self) -> BatchBackend {
+        self.backend
+    }
+
+    /// Viajes al nodo hechos hasta ahora.
+    pub fn round_trips(&self) -> usize {
+        self.round_trips.load(Ordering::Relaxed)
+    }
+
+    /// Ejecuta `calls` y devuelve, en el mismo orden, lo que devolvió cada una.
+    /// El `Err` exterior es un fallo del transporte; los interiores, reverts.
+    #[instrument(skip_all, fields(calls = calls.len(), backend = ?self.backend))]
+    pub async fn call(&self, calls: &[ViewCall]) -> ClientResult<Vec<ClientResult<Bytes>>> {
+        let mut results = Vec::with_capacity(calls.len());
+        for chunk in calls.chunks(self.chunk_size) {
+            match self.backend {
+                BatchBackend::JsonRpc => {
+                    let requests: Vec<(String, Value)> = chunk
+                        .iter()
+                        .map(|call| eth_call(call.target, &call.data))
+                        .collect();
+                    for response in self.send(&requests).await? {
+                        results.push(call_result(response));
+                    }
+                }
+                BatchBackend::Multicall3(multicall) => {
+                    let pairs: Vec<(Address, Bytes)> = chunk
+                        .iter()
+                        .map(|call| (call.target, call.data.clone()))
+                        .collect();
+                    let request = eth_call(multicall, &aggregate3_calldata(&pairs));
+                    let response = self.send(&[request]).await?.remove(0);
+                    let aggregated = call_result(response)?;
+                    let outcomes = decode_aggregate3(&aggregated)?;
+                    if outcomes.len() != chunk.len() {
+                        return Err(ClientError::decode(t!(
+                            "aggregate3 devolvió {got} resultados para {sent} llamadas",
+                            got = outcomes.len(),
+                            sent = chunk.len()
+                        )));
+                    }
+                    for (success, data) in outcomes {
+                        results.push(if success {
+                            Ok(data)
+                        } else {
+                            Err(ClientError::revert(data))
+                        });
+                    }
+                }
+            }
+        }
+        debug!(round_trips = self.round_trips(), "lote completado");
+        Ok(results)
+    }
+
+    /// Como `call`, decodificando cada retorno como `R`.
+    pub async fn call_decoded<R: AbiDecode>(
+        &self,
+        calls: &[ViewCall],
+    ) -> ClientResult<Vec<ClientResult<R>>> {
+        Ok(self
+            .call(calls)
+            .await?
+            .into_iter()
+            .map(decode_result)
+            .collect())
+    }
+
+    async fn send(&self, requests: &[(String, Value)]) -> ClientResult<Vec<BatchResponse>> {
+        self.round_trips.fetch_add(1, Ordering::Relaxed);
+        let responses = self
+            .client
+            .request_batch(requests)
+            .await
+            .map_err(ProviderError::from)?;
+        if responses.len() != requests.len() {
+            return Err(ClientError::Transport(
+                TransportError::Batch(t!(
+                    "{got} respuestas para {sent} peticiones",
+                    got = responses.len(),
+                    sent = requests.len()
+                ))
+                .into(),
+            ));
+        }
+        Ok(responses)
+    }
+}
+
+fn eth_call(to: Address, data: &Bytes) -> (String, Value) {
+    (
+        "eth_call".to_string(),
+        json!([{ "to": to, "data": data }, "latest"]),
+    )
+}
+
+/// Decodifica el retorno de una llamada del lote como `R`.
+pub fn decode_result<R: AbiDecode>(result: ClientResult<Bytes>) -> ClientResult<R> {
+    let data = result?;
+    R::decode(&data).map_err(|e| {
+        ClientError::decode(t!(
+            "retorno inesperado ({len} bytes): {error}",
+            len = data.len(),
+            error = e
+        ))
+    })
+}
+
+/// Un `eth_call` que revierte trae los datos del revert en `error.data`.
+fn call_result(response: BatchResponse) -> ClientResult<Bytes> {
+    match response {
+        Ok(value) => serde_json::from_value(value).map_err(|e| {
+            ClientError::decode(t!("retorno de eth_call inválido: {error}", error = e))
+        }),
+        Err(failure) => Err(failure_error(failure)),
+    }
+}
+
+fn failure_error(failure: RpcFailure) -> ClientError {
+    let revert_data = failure
+        .data
+        .as_ref()
+        .and_then(Value::as_str)
+        .and_then(|data| data.parse::<Bytes>().ok());
+    match revert_data {
+        Some(data) => ClientError::revert(data),
+        None => ClientError::Transport(TransportError::JsonRpc(failure.into()).into()),
+    }
+}
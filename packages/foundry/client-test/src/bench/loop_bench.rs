@@ -0,0 +1,291 @@
+use ethers::core::rand::thread_rng;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::utils::parse_ether;
+use eyre::Result;
+use serde_json::{json, Value};
+use tokio::time::{Duration, Instant};
+
+use crate::bench::{throughput, GasStats, LatencyStats};
+use crate::engine::{ResubmitPolicy, TxEngine, TxOutcome};
+use crate::functions::{
+    claims_in_receipt, create_loop_confirmed, current_period, sign_eligibility,
+};
+use crate::t;
+use crate::utils::{set_balance, warp};
+use crate::Env;
+
+/// Parámetros del benchmark: se crea un loop nuevo con `users` claimers.
+#[derive(Debug, Clone, Copy)]
+pub struct LoopBenchmark {
+    pub users: usize,
+    pub period_length: U256,
+    pub percent_per_period: U256,
+    /// Tokens (en wei) que se depositan en el loop antes de empezar.
+    pub fund: U256,
+    pub concurrency: usize,
+}
+
+/// Una fase del benchmark (registro o periodo de claims).
+#[derive(Debug, Clone, Default)]
+pub struct PhaseReport {
+    pub name: &'static str,
+    pub mined: usize,
+    pub failed: usize,
+    pub elapsed: Duration,
+    pub latency: LatencyStats,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BenchReport {
+    pub loop_address: Address,
+    pub users: usize,
+    /// `claimAndRegister` sin claim (primer registro).
+    pub register_only: GasStats,
+    /// `claimAndRegister` del primer claimer del periodo: escribe `maxPayout`.
+    pub first_claim_and_register: GasStats,
+    pub claim_and_register: GasStats,
+    pub claim: GasStats,
+    pub phases: Vec<PhaseReport>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TxKind {
+    Register,
+    FirstClaimAndRegister,
+    ClaimAndRegister,
+    Claim,
+}
+
+impl LoopBenchmark {
+    /// Crea y financia el loop, registra a todos los usuarios y recorre un
+    /// periodo completo de claims. Necesita anvil.
+    pub async fn run(&self, env: &Env) -> Result<BenchReport> {
+        let organization = env.deployment_address("organization")?;
+        let (_, loop_address) = create_loop_confirmed(
+            env,
+            organization,
+            self.period_length,
+            self.percent_per_period,
+        )
+        .await?;
+        let token = env.deployment_address("test_token_address")?;
+        let transfer = env
+            .contract_at(token, "TestToken")?
+            .connect(env.signer()?)
+            .method::<(Address, U256), bool>("transfer", (loop_address, self.fund))?;
+        let funding = transfer.send().await?;
+        let tx_hash = funding.tx_hash();
+        // Sin fondos los claims revierten y los números de gas no valen
+        match funding.await? {
+            Some(receipt) if receipt.status == Some(U64::one()) => {}
+            _ => {
+                return Err(eyre::eyre!(t!(
+                    "❌ La TX {tx} que financia el loop {address} no se minó con éxito",
+                    tx = ?tx_hash,
+                    address = ?loop_address
+                )))
+            }
+        }
+
+        let trusted: LocalWallet = env.trusted_signer_pk.parse()?;
+        let loop_facet = env.contract_at(loop_address, "LoopFacet")?;
+        let mut engine = TxEngine::new(env, self.concurrency, ResubmitPolicy::default())?;
+        let mut users = Vec::with_capacity(self.users);
+        for _ in 0..self.users.max(1) {
+            let user = engine.add_wallet(LocalWallet::new(&mut thread_rng()));
+            set_balance(engine.provider(), user, parse_ether(10)?).await?;
+            users.push(user);
+        }
+
+        let mut report = BenchReport {
+            loop_address,
+            users: users.len(),
+            ..Default::default()
+        };
+
+        let claim_and_register = |user: Address, next_period: U256| {
+            let trusted = trusted.clone();
+            let loop_facet = loop_facet.clone();
+            async move {
+                let signature = sign_eligibility(&trusted, user, next_period, loop_address).await?;
+                let call = loop_facet.method::<Bytes, ()>("claimAndRegister", signature)?;
+                Ok::<TypedTransaction, eyre::Report>(call.tx)
+            }
+        };
+
+        // Fase 1: todos se registran para el periodo siguiente
+        let period = current_period(env, loop_address).await?;
+        let mut txs = Vec::with_capacity(users.len());
+        for user in &users {
+            let tx = claim_and_register(*user, period + 1).await?;
+            txs.push((*user, tx, TxKind::Register));
+        }
+        let phase = run_phase(&engine, "register", txs, &mut report).await;
+        report.phases.push(phase);
+
+        // Fase 2: el periodo en el que están registrados
+        warp(engine.provider(), self.period_length.as_u64()).await?;
+        let period = current_period(env, loop_address).await?;
+
+        // El primer claimer va solo para que sea él quien escribe maxPayout
+        let first = claim_and_register(users[0], period + 1).await?;
+        let phase = run_phase(
+            &engine,
+            "first claim",
+            vec![(users[0], first, TxKind::FirstClaimAndRegister)],
+            &mut report,
+        )
+        .await;
+        report.phases.push(phase);
+
+        // El resto alterna claimAndRegister y claim
+        let mut txs = Vec::with_capacity(users.len());
+        for (i, user) in users.iter().enumerate().skip(1) {
+            if i % 2 == 1 {
+                let tx = claim_and_register(*user, period + 1).await?;
+                txs.push((*user, tx, TxKind::ClaimAndRegister));
+            } else {
+                let call = loop_facet.method::<(), ()>("claim", ())?;
+                txs.push((*user, call.tx, TxKind::Claim));
+            }
+        }
+        let phase = run_phase(&engine, "claims", txs, &mut report).await;
+        report.phases.push(phase);
+
+        Ok(report)
+    }
+}
+
+async fn run_phase(
+    engine: &TxEngine,
+    name: &'static str,
+    txs: Vec<(Address, TypedTransaction, TxKind)>,
+    report: &mut BenchReport,
+) -> PhaseReport {
+    let kinds: Vec<TxKind> = txs.iter().map(|(_, _, kind)| *kind).collect();
+    let started = Instant::now();
+    let outcomes = engine
+        .submit_all(txs.into_iter().map(|(from, tx, _)| (from, tx)).collect())
+        .await;
+    let mut phase = PhaseReport {
+        name,
+        elapsed: started.elapsed(),
+        ..Default::default()
+    };
+    for (kind, outcome) in kinds.into_iter().zip(outcomes) {
+        match outcome {
+            Ok(outcome) if outcome.succeeded() => {
+                phase.mined += 1;
+                phase.latency.push(outcome.latency);
+                record_gas(report, kind, &outcome);
+            }
+            _ => phase.failed += 1,
+        }
+    }
+    phase
+}
+
+fn record_gas(report: &mut BenchReport, kind: TxKind, outcome: &TxOutcome) {
+    let gas = outcome.receipt.gas_used.unwrap_or_default().as_u64();
+    let claimed = !claims_in_receipt(&outcome.receipt).is_empty();
+    match kind {
+        TxKind::Register => report.register_only.push(gas),
+        TxKind::FirstClaimAndRegister if claimed => report.first_claim_and_register.push(gas),
+        TxKind::ClaimAndRegister if claimed => report.claim_and_register.push(gas),
+        TxKind::Claim => report.claim.push(gas),
+        // Un claimAndRegister que no llegó a cobrar solo registró
+        _ => report.register_only.push(gas),
+    }
+}
+
+pub fn bench_report_json(report: &BenchReport) -> Value {
+    let phases: Vec<Value> = report
+        .phases
+        .iter()
+        .map(|phase| {
+            json!({
+                "name": phase.name,
+                "mined": phase.mined,
+                "failed": phase.failed,
+                "elapsed_ms": phase.elapsed.as_millis() as u64,
+                "tx_per_second": throughput(phase.mined, phase.elapsed),
+                "latency": phase.latency.to_json(),
+            })
+        })
+        .collect();
+    json!({
+        "loop": format!("{:?}", report.loop_address),
+        "users": report.users,
+        "gas": {
+            "register_only": report.register_only.to_json(),
+            "claim_and_register_first": report.first_claim_and_register.to_json(),
+            "claim_and_register": report.claim_and_register.to_json(),
+            "claim": report.claim.to_json(),
+        },
+        "phases": phases,
+    })
+}
+
+pub fn print_bench_report(report: &BenchReport) {
+    println!(
+        "{}",
+        t!(
+            "Loop {address} con {users} usuarios",
+            address = ?report.loop_address,
+            users = report.users
+        )
+    );
+    println!();
+    println!(
+        "{:<28} {:>6} {:>10} {:>10} {:>10}",
+        "GAS",
+        "N",
+        "MIN",
+        t!("MEDIA"),
+        "MAX"
+    );
+    for (name, stats) in [
+        (t!("claimAndRegister (registro)"), &report.register_only),
+        (
+            t!("claimAndRegister (1er claim)"),
+            &report.first_claim_and_register,
+        ),
+        ("claimAndRegister".to_string(), &report.claim_and_register),
+        ("claim".to_string(), &report.claim),
+    ] {
+        println!(
+            "{:<28} {:>6} {:>10} {:>10} {:>10}",
+            name,
+            stats.samples.len(),
+            stats.min(),
+            stats.mean(),
+            stats.max()
+        );
+    }
+    println!();
+    println!(
+        "{:<12} {:>6} {:>6} {:>8} {:>8} {:>8} {:>8} {:>8}",
+        t!("FASE"),
+        "OK",
+        "FAIL",
+        "TX/S",
+        "P50ms",
+        "P90ms",
+        "P99ms",
+        "MAXms"
+    );
+    for phase in &report.phases {
+        println!(
+            "{:<12} {:>6} {:>6} {:>8.1} {:>8} {:>8} {:>8} {:>8}",
+            phase.name,
+            phase.mined,
+            phase.failed,
+            throughput(phase.mined, phase.elapsed),
+            phase.latency.percentile(50.0).as_millis(),
+            phase.latency.percentile(90.0).as_millis(),
+            phase.latency.percentile(99.0).as_millis(),
+            phase.latency.percentile(100.0).as_millis()
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod loop_bench;
+pub mod stats;
+pub use loop_bench::*;
+pub use stats::*;
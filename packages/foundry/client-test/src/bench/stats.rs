@@ -0,0 +1,76 @@
+use serde_json::{json, Value};
+use tokio::time::Duration;
+
+/// Gas consumido por un tipo de llamada.
+#[derive(Debug, Clone, Default)]
+pub struct GasStats {
+    pub samples: Vec<u64>,
+}
+
+impl GasStats {
+    pub fn push(&mut self, gas: u64) {
+        self.samples.push(gas);
+    }
+
+    pub fn min(&self) -> u64 {
+        self.samples.iter().copied().min().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> u64 {
+        self.samples.iter().copied().max().unwrap_or_default()
+    }
+
+    pub fn mean(&self) -> u64 {
+        if self.samples.is_empty() {
+            return 0;
+        }
+        self.samples.iter().sum::<u64>() / self.samples.len() as u64
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "count": self.samples.len(),
+            "min": self.min(),
+            "mean": self.mean(),
+            "max": self.max(),
+        })
+    }
+}
+
+/// Latencias (envío -> receipt) de una fase.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    pub samples: Vec<Duration>,
+}
+
+impl LatencyStats {
+    pub fn push(&mut self, latency: Duration) {
+        self.samples.push(latency);
+    }
+
+    /// Percentil por el método nearest-rank; `p` en 0..=100.
+    pub fn percentile(&self, p: f64) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "count": self.samples.len(),
+            "p50_ms": self.percentile(50.0).as_millis() as u64,
+            "p90_ms": self.percentile(90.0).as_millis() as u64,
+            "p99_ms": self.percentile(99.0).as_millis() as u64,
+            "max_ms": self.percentile(100.0).as_millis() as u64,
+        })
+    }
+}
+
+/// Throughput de una fase: TX minadas entre el tiempo de pared.
+pub fn throughput(transactions: usize, elapsed: Duration) -> f64 {
+    transactions as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+}
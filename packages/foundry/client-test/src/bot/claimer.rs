@@ -0,0 +1,260 @@
+use ethers::prelude::*;
+use eyre::Result;
+use futures::future::join_all;
+use std::env as std_env;
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+use tracing::{info, instrument, warn, Span};
+
+use crate::functions::{
+    claim_and_register_as, claim_and_register_call, claimer_status, claims_in_receipt,
+    current_period, loop_details, sign_eligibility, LoopDetails,
+};
+use crate::t;
+use crate::telemetry;
+use crate::utils::{dry_run, DryRun, SelectorBook, SignerClient};
+use crate::Env;
+
+/// De dónde sale la firma de elegibilidad.
+#[derive(Debug, Clone)]
+pub enum SignatureSource {
+    /// Firmamos localmente con la clave del trusted backend signer.
+    Local(LocalWallet),
+    /// Pedimos la firma a un backend: `GET {url}?user=..&period=..&loop=..` -> `{"signature": "0x.."}`.
+    Remote(String),
+}
+
+impl SignatureSource {
+    pub async fn signature(
+        &self,
+        user: Address,
+        next_period: U256,
+        loop_address: Address,
+    ) -> Result<Bytes> {
+        match self {
+            SignatureSource::Local(wallet) => {
+                let signature = sign_eligibility(wallet, user, next_period, loop_address).await;
+                telemetry::signature("local", signature.is_ok());
+                Ok(signature?)
+            }
+            SignatureSource::Remote(url) => {
+                let signature = Self::remote_signature(url, user, next_period, loop_address).await;
+                telemetry::signature("remote", signature.is_ok());
+                signature
+            }
+        }
+    }
+
+    /// `GET {url}?user=..&period=..&loop=..`; un 4xx/5xx cuenta como firma rechazada.
+    async fn remote_signature(
+        url: &str,
+        user: Address,
+        next_period: U256,
+        loop_address: Address,
+    ) -> Result<Bytes> {
+        let response: serde_json::Value = reqwest::Client::new()
+            .get(url)
+            .query(&[
+                ("user", format!("{:?}", user)),
+                ("period", next_period.to_string()),
+                ("loop", format!("{:?}", loop_address)),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let signature = response
+            .get("signature")
+            .and_then(|s| s.as_str())
+            .ok_or_else(|| eyre::eyre!(t!("❌ El backend no devolvió una firma")))?;
+        Ok(signature.parse()?)
+    }
+}
+
+/// Resultado de un intento de claim para una wallet.
+#[derive(Debug, Clone)]
+pub struct ClaimOutcome {
+    pub wallet: Address,
+    pub period: U256,
+    pub tx_hash: Option<H256>,
+    pub payout: Option<U256>,
+    pub expects_payout: bool,
+    pub skipped: Option<String>,
+}
+
+/// Espera tras un fallo de RPC del bot; se duplica hasta `MAX_RETRY_BACKOFF`.
+const MIN_RETRY_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(120);
+
+/// Claimer de larga duración: cada periodo hace `claimAndRegister` con todas las wallets.
+pub struct ClaimBot {
+    pub loop_address: Address,
+    pub wallets: Vec<Arc<SignerClient>>,
+    pub source: SignatureSource,
+}
+
+/// Lee las claves de `CLAIMER_PKS` (separadas por comas).
+pub fn wallets_from_env(env: &Env) -> Result<Vec<Arc<SignerClient>>> {
+    let pks = std_env::var("CLAIMER_PKS")
+        .map_err(|_| eyre::eyre!(t!("❌ {name} no está definida", name = "CLAIMER_PKS")))?;
+    pks.split(',')
+        .map(str::trim)
+        .filter(|pk| !pk.is_empty())
+        .map(|pk| Ok(env.wallet(pk)?))
+        .collect()
+}
+
+impl ClaimBot {
+    /// Intenta claimAndRegister con una wallet en el periodo `period`.
+    #[instrument(skip_all, fields(loop_address = ?self.loop_address, signer = ?client.address(), period = %period, tx_hash))]
+    async fn claim_one(&self, env: &Env, client: Arc<SignerClient>, period: U256) -> ClaimOutcome {
+        let wallet = client.address();
+        let mut outcome = ClaimOutcome {
+            wallet,
+            period,
+            tx_hash: None,
+            payout: None,
+            expects_payout: false,
+            skipped: None,
+        };
+
+        let result: Result<()> = async {
+            let (is_registered, has_claimed) =
+                claimer_status(env, self.loop_address, wallet).await?;
+            // Sólo se cobra si estaba registrado para este periodo y aún no cobró;
+            // el registro para el siguiente se intenta siempre
+            outcome.expects_payout = is_registered && !has_claimed;
+            let signature = self
+                .source
+                .signature(wallet, period + 1, self.loop_address)
+                .await?;
+            let receipt =
+                claim_and_register_as(env, client.clone(), self.loop_address, signature).await?;
+            outcome.tx_hash = Some(receipt.transaction_hash);
+            Span::current().record("tx_hash", tracing::field::debug(receipt.transaction_hash));
+            outcome.payout = claims_in_receipt(&receipt)
+                .into_iter()
+                .find(|c| c.claimer == wallet)
+                .map(|c| c.payout);
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            outcome.skipped = Some(e.to_string());
+        }
+        outcome
+    }
+
+    /// Un pase completo sobre todas las wallets para el periodo en curso.
+    #[instrument(skip_all, fields(loop_address = ?self.loop_address, wallets = self.wallets.len()))]
+    pub async fn run_period(&self, env: &Env) -> Result<Vec<ClaimOutcome>> {
+        let period = current_period(env, self.loop_address).await?;
+        let outcomes = join_all(
+            self.wallets
+                .iter()
+                .map(|client| self.claim_one(env, client.clone(), period)),
+        )
+        .await;
+
+        for outcome in &outcomes {
+            if outcome.expects_payout && outcome.tx_hash.is_some() && outcome.payout.is_none() {
+                warn!(
+                    wallet = ?outcome.wallet,
+                    period = %outcome.period,
+                    "estaba registrado pero no hubo Claim"
+                );
+            }
+            match (&outcome.tx_hash, &outcome.skipped) {
+                (Some(tx_hash), _) => info!(
+                    wallet = ?outcome.wallet,
+                    period = %outcome.period,
+                    payout = %outcome.payout.unwrap_or_default(),
+                    tx_hash = ?tx_hash,
+                    "claimAndRegister confirmado"
+                ),
+                (None, Some(reason)) => warn!(
+                    wallet = ?outcome.wallet,
+                    period = %outcome.period,
+                    reason = %reason,
+                    "wallet omitida"
+                ),
+                (None, None) => {}
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Pase simulado para `--dry-run`: firma y `eth_call` de `claimAndRegister`
+    /// con cada wallet, sin enviar nada.
+    pub async fn dry_run_period(&self, env: &Env) -> Result<Vec<DryRun<()>>> {
+        let period = current_period(env, self.loop_address).await?;
+        let book = SelectorBook::from_artifacts()?;
+        let mut previews = Vec::with_capacity(self.wallets.len());
+        for client in &self.wallets {
+            let signature = self
+                .source
+                .signature(client.address(), period + 1, self.loop_address)
+                .await?;
+            let call = claim_and_register_call(env, client.clone(), self.loop_address, signature)?;
+            previews.push(dry_run(&call, &book).await);
+        }
+        Ok(previews)
+    }
+
+    /// Actualiza el gauge de saldo del loop.
+    async fn record_balance(&self, env: &Env, token: Address) -> Result<()> {
+        let token = env.contract_at(token, "TestToken")?;
+        let balance = token.method::<Address, U256>("balanceOf", self.loop_address)?;
+        let balance = balance.call().await?;
+        let decimals: u8 = token.method::<(), u8>("decimals", ())?.call().await?;
+        telemetry::loop_balance(self.loop_address, balance, decimals);
+        Ok(())
+    }
+
+    /// Un pase y lo que falta hasta el inicio del próximo periodo.
+    async fn tick(&self, env: &Env, details: &LoopDetails, once: bool) -> Result<Duration> {
+        self.run_period(env).await?;
+        if telemetry::metrics_enabled() {
+            self.record_balance(env, details.token).await?;
+        }
+        if once {
+            return Ok(Duration::ZERO);
+        }
+
+        let now = env
+            .provider()?
+            .get_block(BlockNumber::Latest)
+            .await?
+            .map(|b| b.timestamp)
+            .unwrap_or_default();
+        let next_start = details.period_start(details.period_at(now) + 1);
+        Ok(Duration::from_secs((next_start - now).as_u64() + 1))
+    }
+
+    /// Corre indefinidamente (o una vez con `once`), despertando al inicio de cada periodo
+    /// según `firstPeriodStart` y `periodLength`. Un fallo de RPC no detiene el
+    /// servicio: se registra y se reintenta con espera creciente.
+    pub async fn run(&self, env: &Env, once: bool) -> Result<()> {
+        let details = loop_details(env, self.loop_address).await?;
+        let mut backoff = MIN_RETRY_BACKOFF;
+
+        loop {
+            match self.tick(env, &details, once).await {
+                Ok(_) if once => return Ok(()),
+                Ok(wait) => {
+                    backoff = MIN_RETRY_BACKOFF;
+                    info!(wait_secs = wait.as_secs(), "esperando al próximo periodo");
+                    sleep(wait).await;
+                }
+                Err(e) if once => return Err(e),
+                Err(e) => {
+                    warn!(error = %e, retry_secs = backoff.as_secs(), "fallo en el pase del bot, se reintenta");
+                    sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+                }
+            }
+        }
+    }
+}
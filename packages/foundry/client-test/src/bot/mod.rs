@@ -0,0 +1,2 @@
+pub mod claimer;
+pub use claimer::*;
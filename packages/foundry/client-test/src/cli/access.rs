@@ -0,0 +1,85 @@
+use ethers::types::Address;
+use eyre::Result;
+
+use crate::access::{self, parse_role};
+use crate::cli::{submit, AccessCommand, GlobalOpts};
+use crate::utils::{parse_selector, SelectorBook};
+use crate::{ClientResult, Env};
+
+pub async fn access(env: &Env, action: AccessCommand, opts: &GlobalOpts) -> Result<()> {
+    match action {
+        AccessCommand::Roles { target, user } => {
+            let diamond = env.resolve_address(&target)?;
+            let user = env.resolve_address(&user)?;
+            let source = access::role_source(env, diamond).await?;
+            let roles = access::roles_of_user(env, diamond, user, source).await?;
+            println!("{:?}: {}", user, roles);
+        }
+        AccessCommand::FunctionRoles { target, function } => {
+            let diamond = env.resolve_address(&target)?;
+            let selector = parse_selector(&function, &SelectorBook::from_artifacts()?)?;
+            let source = access::role_source(env, diamond).await?;
+            let roles = access::roles_of_function(env, diamond, selector, source).await?;
+            println!("{}: {}", function, roles);
+        }
+        AccessCommand::CanCall {
+            target,
+            user,
+            function,
+        } => {
+            let diamond = env.resolve_address(&target)?;
+            let user = env.resolve_address(&user)?;
+            let selector = parse_selector(&function, &SelectorBook::from_artifacts()?)?;
+            let allowed = match access::role_source(env, diamond).await? {
+                access::RoleSource::Facet => access::can_call(env, diamond, user, selector).await?,
+                source => access::roles_of_user(env, diamond, user, source)
+                    .await?
+                    .can_call(&access::roles_of_function(env, diamond, selector, source).await?),
+            };
+            println!("{:?} -> {}: {}", user, function, allowed);
+        }
+        AccessCommand::SetRole {
+            target,
+            user,
+            role,
+            disable,
+        } => {
+            let diamond = env.resolve_address(&target)?;
+            let user = env.resolve_address(&user)?;
+            let role = parse_role(&role)?;
+            let call = access::set_user_role_call(env, diamond, user, role, !disable)?;
+            submit(env.signer()?, call, "setUserRole", opts).await?;
+        }
+        AccessCommand::SetAccess {
+            target,
+            function,
+            role,
+            disable,
+        } => {
+            let diamond = env.resolve_address(&target)?;
+            let selector = parse_selector(&function, &SelectorBook::from_artifacts()?)?;
+            let role = parse_role(&role)?;
+            let call = access::set_function_access_call(env, diamond, selector, role, !disable)?;
+            submit(env.signer()?, call, "setFunctionAccess", opts).await?;
+        }
+        AccessCommand::Audit { target, from_block } => {
+            let diamond = env.resolve_address(&target)?;
+            let audit = access::audit_permissions(env, diamond, from_block).await?;
+            access::print_permission_audit(&audit, &SelectorBook::from_artifacts()?);
+        }
+        AccessCommand::Report { target, users } => {
+            let diamond = env.resolve_address(&target)?;
+            let mut addresses = users
+                .iter()
+                .map(|u| env.resolve_address(u))
+                .collect::<ClientResult<Vec<Address>>>()?;
+            addresses.extend(env.trusted_signer.iter().map(|s| s.address()));
+            addresses.extend(env.bad_signer.iter().map(|s| s.address()));
+            addresses.sort();
+            addresses.dedup();
+            let report = access::access_report(env, diamond, &addresses).await?;
+            access::print_access_report(&report);
+        }
+    }
+    Ok(())
+}
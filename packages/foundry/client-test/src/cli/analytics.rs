@@ -0,0 +1,50 @@
+use ethers::utils::parse_units;
+use eyre::Result;
+use std::path::Path;
+
+use crate::analytics::{
+    analytics_json, loop_analytics, participation_json, print_loop_analytics,
+    print_user_participation, user_participation, write_analytics,
+};
+use crate::cli::GlobalOpts;
+use crate::functions::loop_details;
+use crate::Env;
+
+pub async fn analytics(
+    env: &Env,
+    loop_target: &str,
+    from_block: u64,
+    threshold: &str,
+    csv: Option<&Path>,
+    json: Option<&Path>,
+    opts: &GlobalOpts,
+) -> Result<()> {
+    let loop_address = env.resolve_address(loop_target)?;
+    let token = loop_details(env, loop_address).await?.token;
+    let decimals: u8 = env
+        .contract_at(token, "TestToken")?
+        .method::<(), u8>("decimals", ())?
+        .call()
+        .await?;
+    let threshold = parse_units(threshold, decimals as u32)?.into();
+
+    let report = loop_analytics(env, loop_address, from_block, threshold).await?;
+    if opts.json() {
+        println!("{}", analytics_json(&report));
+    } else {
+        print_loop_analytics(&report);
+    }
+    write_analytics(&report, csv, json)?;
+    Ok(())
+}
+
+pub async fn history(env: &Env, user: &str, from_block: u64, opts: &GlobalOpts) -> Result<()> {
+    let user = env.resolve_address(user)?;
+    let history = user_participation(env, user, from_block).await?;
+    if opts.json() {
+        println!("{}", participation_json(user, &history));
+    } else {
+        print_user_participation(user, &history);
+    }
+    Ok(())
+}
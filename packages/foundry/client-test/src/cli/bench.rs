@@ -0,0 +1,37 @@
+use ethers::types::U256;
+use eyre::Result;
+
+use crate::bench::{bench_report_json, print_bench_report, LoopBenchmark};
+use crate::functions::token_info;
+use crate::Env;
+
+pub async fn bench(
+    env: &Env,
+    users: usize,
+    period_length: u64,
+    percent: u64,
+    fund: u64,
+    concurrency: usize,
+    json: bool,
+) -> Result<()> {
+    // `fund` va en unidades enteras del token del deployment, con sus decimales
+    let token = env.deployment_address("test_token_address")?;
+    let fund = token_info(env, token).await?.parse(&fund.to_string())?;
+    let benchmark = LoopBenchmark {
+        users,
+        period_length: U256::from(period_length),
+        percent_per_period: U256::from(percent),
+        fund,
+        concurrency,
+    };
+    let report = benchmark.run(env).await?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&bench_report_json(&report))?
+        );
+    } else {
+        print_bench_report(&report);
+    }
+    Ok(())
+}
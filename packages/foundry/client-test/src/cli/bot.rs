@@ -0,0 +1,43 @@
+use ethers::signers::LocalWallet;
+use eyre::Result;
+use tracing::info;
+
+use crate::bot::{wallets_from_env, ClaimBot, SignatureSource};
+use crate::cli::GlobalOpts;
+use crate::utils::{dry_run_json, print_dry_run};
+use crate::Env;
+
+pub async fn claim_bot(
+    env: &Env,
+    loop_target: &str,
+    signer_url: Option<String>,
+    once: bool,
+    opts: &GlobalOpts,
+) -> Result<()> {
+    let source = match signer_url {
+        Some(url) => SignatureSource::Remote(url),
+        None => SignatureSource::Local(env.trusted_signer_pk.parse::<LocalWallet>()?),
+    };
+    let bot = ClaimBot {
+        loop_address: env.resolve_address(loop_target)?,
+        wallets: wallets_from_env(env)?,
+        source,
+    };
+    if opts.dry_run {
+        // Un único pase simulado, aunque no se haya pedido `--once`
+        for preview in bot.dry_run_period(env).await? {
+            if opts.json() {
+                println!("{}", dry_run_json(&preview));
+            } else {
+                print_dry_run(&preview);
+            }
+        }
+        return Ok(());
+    }
+    info!(
+        loop_address = ?bot.loop_address,
+        wallets = bot.wallets.len(),
+        "claim bot iniciado"
+    );
+    bot.run(env, once).await
+}
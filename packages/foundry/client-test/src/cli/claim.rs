@@ -0,0 +1,67 @@
+use ethers::prelude::*;
+use eyre::Result;
+use serde_json::json;
+use std::env as std_env;
+
+use crate::cli::{submit, GlobalOpts};
+use crate::functions::{claim_and_register_call, current_period, sign_eligibility};
+use crate::t;
+use crate::Env;
+
+/// Acepta una clave privada o el nombre de la variable de entorno que la contiene.
+fn private_key(input: &str) -> Result<String> {
+    if input.parse::<LocalWallet>().is_ok() {
+        return Ok(input.to_string());
+    }
+    std_env::var(input).map_err(|_| eyre::eyre!(t!("❌ {name} no está definida", name = input)))
+}
+
+pub async fn claim(
+    env: &Env,
+    loop_target: &str,
+    wallet: &str,
+    signature: Option<&str>,
+    opts: &GlobalOpts,
+) -> Result<()> {
+    let loop_address = env.resolve_address(loop_target)?;
+    let client = env.wallet(&private_key(wallet)?)?;
+    let signature: Bytes = match signature {
+        Some(signature) => signature.parse()?,
+        None => {
+            let next_period = current_period(env, loop_address).await? + 1;
+            let trusted: LocalWallet = env.trusted_signer_pk.parse()?;
+            sign_eligibility(&trusted, client.address(), next_period, loop_address).await?
+        }
+    };
+    let call = claim_and_register_call(env, client.clone(), loop_address, signature)?;
+    submit(client, call, "claimAndRegister", opts).await?;
+    Ok(())
+}
+
+pub async fn sign(
+    env: &Env,
+    user: &str,
+    loop_target: &str,
+    period: Option<u64>,
+    opts: &GlobalOpts,
+) -> Result<()> {
+    let loop_address = env.resolve_address(loop_target)?;
+    let user = env.resolve_address(user)?;
+    let period = match period {
+        Some(period) => U256::from(period),
+        None => current_period(env, loop_address).await? + 1,
+    };
+    let trusted: LocalWallet = env.trusted_signer_pk.parse()?;
+    let signature = sign_eligibility(&trusted, user, period, loop_address).await?;
+    opts.emit(
+        json!({
+            "user": user,
+            "loop": loop_address,
+            "period": period.as_u64(),
+            "signer": trusted.address(),
+            "signature": signature,
+        }),
+        || signature.to_string(),
+    );
+    Ok(())
+}
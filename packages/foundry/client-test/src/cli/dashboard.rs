@@ -0,0 +1,18 @@
+use eyre::Result;
+use std::time::Duration;
+
+use crate::dashboard::run_dashboard;
+use crate::{ClientResult, Env};
+
+pub async fn dashboard(
+    env: &Env,
+    loop_targets: &[String],
+    interval: u64,
+    from_block: Option<u64>,
+) -> Result<()> {
+    let loops = loop_targets
+        .iter()
+        .map(|target| env.resolve_address(target))
+        .collect::<ClientResult<Vec<_>>>()?;
+    run_dashboard(env, &loops, Duration::from_secs(interval), from_block).await
+}
@@ -0,0 +1,110 @@
+use eyre::Result;
+
+use crate::cli::{submit, GlobalOpts, RegistryCommand};
+use crate::diamond::{self, NewFacet};
+use crate::t;
+use crate::utils::format_selector;
+use crate::Env;
+
+pub async fn inspect(env: &Env, target: &str, opts: &GlobalOpts) -> Result<()> {
+    let diamond = env.resolve_address(target)?;
+    let report = diamond::inspect_diamond(env, diamond).await?;
+    if opts.json() {
+        println!("{}", diamond::diamond_report_json(&report));
+    } else {
+        diamond::print_diamond_report(&report);
+    }
+    Ok(())
+}
+
+pub async fn plan_cut(
+    env: &Env,
+    target: &str,
+    facets: &[String],
+    execute: bool,
+    opts: &GlobalOpts,
+) -> Result<()> {
+    let diamond = env.resolve_address(target)?;
+    let new_facets = facets
+        .iter()
+        .map(|spec| NewFacet::parse(spec))
+        .collect::<Result<Vec<_>>>()?;
+    let plan = diamond::build_cut_plan(env, diamond, &new_facets).await?;
+    diamond::print_cut_plan(&plan);
+    if plan.cuts.is_empty() {
+        return Ok(());
+    }
+
+    let admin = diamond::system_admin(env, diamond).await?;
+    diamond::simulate_cut(env, &plan, admin).await?;
+    println!("{}", t!("Simulación OK (from {admin})", admin = ?admin));
+    if execute {
+        // Con --dry-run, además de la simulación como admin, la del signer que lo enviaría
+        submit(
+            env.signer()?,
+            diamond::cut_call(env, &plan)?,
+            "diamondCut",
+            opts,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+pub async fn registry(env: &Env, action: RegistryCommand, opts: &GlobalOpts) -> Result<()> {
+    match action {
+        RegistryCommand::List => {
+            for (facet, selectors) in diamond::registered_facets(env).await? {
+                let selectors: Vec<String> =
+                    selectors.iter().map(|s| format_selector(*s)).collect();
+                println!("{:?} [{}]", facet, selectors.join(", "));
+            }
+        }
+        RegistryCommand::Register { artifact, facet } => {
+            let facet = env.resolve_address(&facet)?;
+            let call = diamond::register_facet_call(env, &artifact, facet)?;
+            submit(env.signer()?, call, "addFacet", opts).await?;
+        }
+        RegistryCommand::Predict { artifact, salt } => {
+            let salt = diamond::parse_salt(&salt);
+            let facet = diamond::compute_facet_address(env, salt, &artifact).await?;
+            println!(
+                "{}",
+                t!(
+                    "{artifact} con salt {salt} -> {address}",
+                    artifact = artifact,
+                    salt = ?salt,
+                    address = ?facet
+                )
+            );
+        }
+        RegistryCommand::Deploy { artifact, salt } => {
+            let salt = diamond::parse_salt(&salt);
+            let call = diamond::deploy_facet_call(env, salt, &artifact)?;
+            let Some(receipt) = submit(env.signer()?, call, "deployFacet", opts).await? else {
+                return Ok(());
+            };
+            let predicted = diamond::compute_facet_address(env, salt, &artifact).await?;
+            let facet = diamond::deployed_facet(&receipt, predicted)?;
+            println!(
+                "{}",
+                t!(
+                    "{artifact} desplegada en {address} (TX {tx})",
+                    artifact = artifact,
+                    address = ?facet,
+                    tx = ?receipt.transaction_hash
+                )
+            );
+        }
+        RegistryCommand::Unregister { facet } => {
+            let facet = env.resolve_address(&facet)?;
+            let call = diamond::unregister_facet_call(env, facet)?;
+            submit(env.signer()?, call, "removeFacet", opts).await?;
+        }
+        RegistryCommand::History { from_block } => {
+            let events = diamond::registry_history(env, from_block).await?;
+            diamond::print_registry_history(&events);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use ethers::prelude::*;
+use eyre::Result;
+use serde_json::json;
+use tokio::time::{sleep, Duration};
+
+use crate::cli::{EventsCommand, GlobalOpts};
+use crate::events::{loop_history_range, LoopEvent};
+use crate::functions::{loop_details, token_info, TokenInfo};
+use crate::t;
+use crate::telemetry;
+use crate::Env;
+
+/// Imprime un evento; las cantidades salen con los decimales de `token`.
+fn print_event(event: &LoopEvent, token: &TokenInfo, opts: &GlobalOpts) {
+    match event {
+        LoopEvent::Register {
+            block,
+            tx_hash,
+            event,
+        } => opts.emit(
+            json!({
+                "event": "Register",
+                "block": block,
+                "tx_hash": tx_hash,
+                "user": event.sender,
+                "period": event.period_number.as_u64(),
+            }),
+            || {
+                format!(
+                    "#{:<8} {}",
+                    block.unwrap_or_default(),
+                    t!(
+                        "Register {user} para el periodo {period}",
+                        user = ?event.sender,
+                        period = event.period_number
+                    )
+                )
+            },
+        ),
+        LoopEvent::Claim {
+            block,
+            tx_hash,
+            event,
+        } => opts.emit(
+            json!({
+                "event": "Claim",
+                "block": block,
+                "tx_hash": tx_hash,
+                "user": event.claimer,
+                "period": event.period_number.as_u64(),
+                "payout": event.payout.to_string(),
+                "payout_units": token.format(event.payout),
+            }),
+            || {
+                format!(
+                    "#{:<8} {}",
+                    block.unwrap_or_default(),
+                    t!(
+                        "Claim    {user} periodo {period} payout {payout}",
+                        user = ?event.claimer,
+                        period = event.period_number,
+                        payout = token.display(event.payout)
+                    )
+                )
+            },
+        ),
+        LoopEvent::Withdraw {
+            block,
+            tx_hash,
+            event,
+        } => opts.emit(
+            json!({
+                "event": "Withdraw",
+                "block": block,
+                "tx_hash": tx_hash,
+                "admin": event.admin,
+                "to": event.to,
+                "amount": event.amount.to_string(),
+                "amount_units": token.format(event.amount),
+            }),
+            || {
+                format!(
+                    "#{:<8} Withdraw {:?} -> {:?} {}",
+                    block.unwrap_or_default(),
+                    event.admin,
+                    event.to,
+                    token.display(event.amount)
+                )
+            },
+        ),
+    }
+}
+
+pub async fn events(env: &Env, action: EventsCommand, opts: &GlobalOpts) -> Result<()> {
+    match action {
+        EventsCommand::Tail {
+            loop_target,
+            from_block,
+            interval,
+        } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            let token = token_info(env, loop_details(env, loop_address).await?.token).await?;
+            let provider = env.provider()?;
+            let mut next_block = match from_block {
+                Some(block) => block,
+                None => provider.get_block_number().await?.as_u64(),
+            };
+            // Consulta por tramos de bloques; no termina nunca (Ctrl+C para salir)
+            loop {
+                let latest = provider.get_block_number().await?.as_u64();
+                telemetry::indexer_lag("events_tail", (latest + 1).saturating_sub(next_block));
+                if latest >= next_block {
+                    let events =
+                        loop_history_range(env, loop_address, next_block, Some(latest)).await?;
+                    for event in &events {
+                        print_event(event, &token, opts);
+                    }
+                    next_block = latest + 1;
+                    telemetry::indexer_lag("events_tail", 0);
+                }
+                sleep(Duration::from_secs(interval)).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use ethers::prelude::*;
+use eyre::Result;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{submit, GlobalOpts, JournalCommand};
+use crate::functions::{create_organization_call, token_info};
+use crate::journal::{EntryStatus, Journal, Onboarding};
+use crate::t;
+use crate::utils::JOURNAL_DIR;
+use crate::Env;
+
+/// Argumentos de `onboard` tal como llegan de la línea de comandos.
+pub struct OnboardRequest {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub admin: Option<String>,
+    pub period_length: u64,
+    pub percent: u64,
+    pub fund: Option<String>,
+}
+
+pub async fn onboard(
+    env: &Env,
+    request: OnboardRequest,
+    journal: Option<PathBuf>,
+    opts: &GlobalOpts,
+) -> Result<()> {
+    let admin = match &request.admin {
+        Some(admin) => env.resolve_address(admin)?,
+        None => env.signer()?.address(),
+    };
+    if opts.dry_run {
+        // Sólo el primer paso se puede simular: los demás dependen de sus direcciones
+        let call = create_organization_call(env, &request.name, admin, &request.description)?;
+        submit(env.signer()?, call, "createOrganization", opts).await?;
+        return Ok(());
+    }
+    let funding = match &request.fund {
+        Some(amount) => token_info(env, env.deployment_address("test_token_address")?)
+            .await?
+            .parse(amount)?,
+        None => U256::zero(),
+    };
+    let path =
+        journal.unwrap_or_else(|| Path::new(JOURNAL_DIR).join(format!("{}.jsonl", request.id)));
+    let mut journal = Journal::open(&path)?;
+
+    let plan = Onboarding {
+        id: request.id,
+        name: request.name,
+        description: request.description,
+        admin,
+        period_length: U256::from(request.period_length),
+        percent_per_period: U256::from(request.percent),
+        funding,
+    };
+    let result = plan.run(env, &mut journal).await?;
+
+    for step in &result.steps {
+        opts.emit(
+            json!({ "step": step.key, "tx_hash": step.tx_hash, "resumed": step.resumed }),
+            || {
+                if step.resumed {
+                    t!("  {step}: retomada (TX {tx})", step = step.key, tx = ?step.tx_hash)
+                } else {
+                    t!("  {step}: enviada (TX {tx})", step = step.key, tx = ?step.tx_hash)
+                }
+            },
+        );
+    }
+    opts.emit(
+        json!({
+            "organization": result.organization,
+            "loop": result.loop_address,
+            "token": result.token,
+            "journal": path.display().to_string(),
+        }),
+        || {
+            t!(
+                "Organización {organization} con el loop {loop_address} (diario {journal})",
+                organization = ?result.organization,
+                loop_address = ?result.loop_address,
+                journal = path.display()
+            )
+        },
+    );
+    Ok(())
+}
+
+pub fn journal(action: JournalCommand, opts: &GlobalOpts) -> Result<()> {
+    match action {
+        JournalCommand::Show { path } => {
+            if !path.exists() {
+                return Err(eyre::eyre!(t!(
+                    "❌ No existe el diario {path}",
+                    path = path.display()
+                )));
+            }
+            let journal = Journal::open(&path)?;
+            for entry in journal.entries() {
+                let status = match entry.status() {
+                    EntryStatus::Intended => "intended",
+                    EntryStatus::Signed => "signed",
+                    EntryStatus::Mined => "mined",
+                    EntryStatus::Reverted => "reverted",
+                    EntryStatus::Failed => "failed",
+                };
+                opts.emit(
+                    json!({
+                        "key": entry.key,
+                        "status": status,
+                        "attempts": entry.attempts,
+                        "from": entry.from,
+                        "to": entry.to,
+                        "nonce": entry.nonce.map(|nonce| nonce.as_u64()),
+                        "tx_hash": entry.tx_hash,
+                        "block": entry.receipt.as_ref().and_then(|r| r.block_number),
+                        "failure": entry.failure,
+                    }),
+                    || {
+                        let tx = entry
+                            .tx_hash
+                            .map(|hash| format!("{:?}", hash))
+                            .unwrap_or_else(|| "-".into());
+                        let mut line =
+                            format!("{:<32} {:<9} {} x{}", entry.key, status, tx, entry.attempts);
+                        if let Some(failure) = &entry.failure {
+                            line.push_str(&format!("  ({})", failure));
+                        }
+                        line
+                    },
+                );
+            }
+        }
+    }
+    Ok(())
+}
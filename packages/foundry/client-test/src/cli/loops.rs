@@ -0,0 +1,212 @@
+use ethers::prelude::*;
+use eyre::Result;
+use serde_json::json;
+use tracing::info;
+
+use crate::batch::{BatchBackend, BatchReader};
+use crate::cli::{submit, GlobalOpts, LoopCommand};
+use crate::functions::{
+    create_loop_call, create_loop_confirmed, current_period, current_period_data,
+    fund_loop_amount_call, loop_address_in_receipt, loop_details, period_individual_payout,
+    set_percent_per_period_call, set_trusted_backend_signer_call, token_balance, token_info,
+    withdraw_deposit_call,
+};
+use crate::journal::Journal;
+use crate::t;
+use crate::Env;
+
+pub async fn loops(env: &Env, action: LoopCommand, opts: &GlobalOpts) -> Result<()> {
+    match action {
+        LoopCommand::Create {
+            organization,
+            period_length,
+            percent,
+            journal,
+        } => {
+            let organization = env.resolve_address(&organization)?;
+            let (period_length, percent) = (U256::from(period_length), U256::from(percent));
+            if opts.dry_run {
+                // El retorno simulado de createNewLoop es la dirección que tendría el loop
+                let call = create_loop_call(env, organization, period_length, percent)?;
+                submit(env.signer()?, call, "createNewLoop", opts).await?;
+                return Ok(());
+            }
+            let (tx_hash, loop_address) = match journal {
+                Some(path) => {
+                    // Misma clave para los mismos parámetros: repetir el comando retoma
+                    let key = format!(
+                        "create_loop/{:?}/{}/{}",
+                        organization, period_length, percent
+                    );
+                    let signer = env.signer()?;
+                    let call = create_loop_call(env, organization, period_length, percent)?;
+                    let sent = Journal::open(&path)?.send(&key, &signer, call).await?;
+                    (
+                        sent.tx_hash,
+                        loop_address_in_receipt(&sent.receipt, organization)?,
+                    )
+                }
+                None => create_loop_confirmed(env, organization, period_length, percent).await?,
+            };
+            opts.emit(json!({ "loop": loop_address, "tx_hash": tx_hash }), || {
+                t!(
+                    "Loop creado en {address} (TX {tx})",
+                    address = ?loop_address,
+                    tx = ?tx_hash
+                )
+            });
+        }
+        LoopCommand::Info { loop_target } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            let details = loop_details(env, loop_address).await?;
+            let period = current_period(env, loop_address).await?;
+            let (registered, max_payout) = current_period_data(env, loop_address).await?;
+            let payout = period_individual_payout(env, loop_address, period).await?;
+            let token = token_info(env, details.token).await?;
+            let balance = token_balance(env, details.token, loop_address).await?;
+            let units = |value: U256| token.format(value);
+            opts.emit(
+                json!({
+                    "loop": loop_address,
+                    "token": details.token,
+                    "symbol": token.symbol,
+                    "period_length": details.period_length.as_u64(),
+                    "percent_per_period": details.percent_per_period.as_u64(),
+                    "first_period_start": details.first_period_start.as_u64(),
+                    "current_period": period.as_u64(),
+                    "current_period_start": details.period_start(period).as_u64(),
+                    "registered_users": registered.as_u64(),
+                    "max_payout": units(max_payout),
+                    "individual_payout": units(payout),
+                    "balance": units(balance),
+                }),
+                || {
+                    let row = |label: String, value: String| format!("  {:<19}{}", label, value);
+                    [
+                        format!("Loop {:?}", loop_address),
+                        row(
+                            "token:".into(),
+                            format!("{:?} ({})", details.token, token.symbol),
+                        ),
+                        row(
+                            "periodLength:".into(),
+                            format!("{}s", details.period_length),
+                        ),
+                        row(
+                            "percentPerPeriod:".into(),
+                            format!("{}%", details.percent_per_period),
+                        ),
+                        row(
+                            t!("periodo en curso:"),
+                            t!(
+                                "{period} (empezó en {start})",
+                                period = period,
+                                start = details.period_start(period)
+                            ),
+                        ),
+                        row(t!("registrados:"), registered.to_string()),
+                        row("maxPayout:".into(), units(max_payout)),
+                        row(t!("pago individual:"), units(payout)),
+                        row("balance:".into(), units(balance)),
+                    ]
+                    .join("\n")
+                },
+            );
+        }
+        LoopCommand::Fund {
+            loop_target,
+            amount,
+        } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            let (_, _, call) = fund_loop_amount_call(env, loop_address, &amount).await?;
+            submit(env.signer()?, call, "transfer", opts).await?;
+        }
+        LoopCommand::Withdraw { loop_target, to } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            let call = withdraw_deposit_call(env, loop_address, env.resolve_address(&to)?)?;
+            submit(env.signer()?, call, "withdrawDeposit", opts).await?;
+        }
+        LoopCommand::SetSigner {
+            loop_target,
+            signer,
+        } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            let call =
+                set_trusted_backend_signer_call(env, loop_address, env.resolve_address(&signer)?)?;
+            submit(env.signer()?, call, "setTrustedBackendSigner", opts).await?;
+        }
+        LoopCommand::SetPercent {
+            loop_target,
+            percent,
+        } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            let call = set_percent_per_period_call(env, loop_address, U256::from(percent))?;
+            submit(env.signer()?, call, "setPercentPerPeriod", opts).await?;
+        }
+        LoopCommand::Claimers {
+            loop_target,
+            mut users,
+            users_file,
+        } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            if let Some(path) = users_file {
+                let contents = std::fs::read_to_string(&path).map_err(|e| {
+                    eyre::eyre!(t!(
+                        "❌ No se pudo leer {path}: {error}",
+                        path = path.display(),
+                        error = e
+                    ))
+                })?;
+                users.extend(
+                    contents
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                        .map(String::from),
+                );
+            }
+            let users = users
+                .iter()
+                .map(|user| env.resolve_address(user))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let reader = BatchReader::connect(env).await?;
+            let statuses = reader.loop_claimer_statuses(loop_address, &users).await?;
+            for (user, status) in users.iter().zip(statuses) {
+                match status {
+                    Ok(status) => opts.emit(
+                        json!({
+                            "loop": loop_address,
+                            "user": user,
+                            "registered": status.is_registered,
+                            "claimed": status.has_claimed,
+                        }),
+                        || {
+                            format!(
+                                "{:?}  {:<14}{}",
+                                user,
+                                t!("registrado: {value}", value = status.is_registered),
+                                t!("cobrado: {value}", value = status.has_claimed)
+                            )
+                        },
+                    ),
+                    Err(e) => opts.emit(
+                        json!({ "loop": loop_address, "user": user, "error": e.to_string() }),
+                        || format!("{:?}  {}", user, e),
+                    ),
+                }
+            }
+            let backend = match reader.backend() {
+                BatchBackend::Multicall3(address) => format!("Multicall3 {:?}", address),
+                BatchBackend::JsonRpc => t!("lotes JSON-RPC"),
+            };
+            info!(
+                users = users.len(),
+                round_trips = reader.round_trips(),
+                %backend,
+                "estado de claimers leído en lote"
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,673 @@
+use clap::{Parser, Subcommand};
+use ethers::signers::LocalWallet;
+use eyre::Result;
+use std::env as std_env;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use crate::console::run_console;
+use crate::i18n::Locale;
+use crate::t;
+use crate::utils::{EnvOverrides, LogFormat, RPC_URL};
+use crate::Env;
+
+pub mod access;
+pub mod analytics;
+pub mod bench;
+pub mod bot;
+pub mod claim;
+pub mod dashboard;
+pub mod diamond;
+pub mod events;
+pub mod journal;
+pub mod loops;
+pub mod org;
+pub mod output;
+pub mod scenarios;
+pub mod storage;
+pub mod token;
+pub use access::*;
+pub use analytics::*;
+pub use bench::*;
+pub use bot::*;
+pub use claim::*;
+pub use dashboard::*;
+pub use diamond::*;
+pub use events::*;
+pub use journal::*;
+pub use loops::*;
+pub use org::*;
+pub use output::*;
+pub use scenarios::*;
+pub use storage::*;
+pub use token::*;
+
+#[derive(Parser)]
+#[command(
+    name = "client-test",
+    about = "Cliente de pruebas para los contratos de Gyralis"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+    /// URL del RPC o nombre de red (`anvil`, o `RPC_URL_<NOMBRE>` del entorno); por defecto RPC_URL
+    #[arg(long, global = true)]
+    pub network: Option<String>,
+    /// Clave privada (o nombre de la variable que la contiene) con la que se envían las TX
+    #[arg(long, global = true)]
+    pub signer: Option<String>,
+    /// Formato de salida
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+    /// Simula las escrituras (eth_call + estimación de gas) en vez de enviarlas
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+    /// Nivel o filtro de logs (`debug`, `client_test=trace,ethers=warn`...); por defecto RUST_LOG o `info`
+    #[arg(long, global = true)]
+    pub log_level: Option<String>,
+    /// Formato de los logs (van a stderr)
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+    /// Dirección en la que servir métricas de Prometheus en `/metrics` (p.ej. 0.0.0.0:9100)
+    #[arg(long, global = true)]
+    pub metrics: Option<SocketAddr>,
+    /// Idioma de la salida; por defecto GYRALIS_LANG, LANG o español
+    #[arg(long, global = true, value_enum)]
+    pub lang: Option<Locale>,
+}
+
+impl Cli {
+    /// Traduce `--network` y `--signer` a lo que espera `Env::setup_with`.
+    pub fn overrides(&self) -> Result<EnvOverrides> {
+        let rpc_url = match self.network.as_deref() {
+            None => None,
+            Some(url) if url.contains("://") => Some(url.to_string()),
+            Some("anvil") | Some("local") => Some(RPC_URL.to_string()),
+            Some(name) => {
+                let var = format!("RPC_URL_{}", name.to_uppercase());
+                Some(
+                    std_env::var(&var)
+                        .map_err(|_| eyre::eyre!(t!("❌ {name} no está definida", name = var)))?,
+                )
+            }
+        };
+        let signer_pk = match self.signer.as_deref() {
+            None => None,
+            Some(pk) if pk.parse::<LocalWallet>().is_ok() => Some(pk.to_string()),
+            Some(var) => Some(
+                std_env::var(var)
+                    .map_err(|_| eyre::eyre!(t!("❌ {name} no está definida", name = var)))?,
+            ),
+        };
+        Ok(EnvOverrides {
+            rpc_url,
+            signer_pk,
+            ..EnvOverrides::default()
+        })
+    }
+
+    pub fn globals(&self) -> GlobalOpts {
+        GlobalOpts {
+            dry_run: self.dry_run,
+            output: self.output,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Organizaciones
+    Org {
+        #[command(subcommand)]
+        action: OrgCommand,
+    },
+    /// Administración y estado de loops
+    Loop {
+        #[command(subcommand)]
+        action: LoopCommand,
+    },
+    /// claimAndRegister desde una wallet, firmando la elegibilidad con TRUSTED_SIGNER_PK
+    Claim {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        /// Clave privada o variable de entorno de la wallet que reclama
+        #[arg(long, default_value = "BAD_ACTOR_PK")]
+        wallet: String,
+        /// Firma de elegibilidad ya emitida; si falta se firma localmente
+        #[arg(long)]
+        signature: Option<String>,
+    },
+    /// Emite una firma de elegibilidad para `user` con TRUSTED_SIGNER_PK
+    Sign {
+        user: String,
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        /// Periodo para el que se registra (por defecto el siguiente)
+        #[arg(long)]
+        period: Option<u64>,
+    },
+    /// Consultas y operaciones sobre el token de los loops (ERC20)
+    Token {
+        #[command(subcommand)]
+        action: TokenCommand,
+    },
+    /// Eventos de los loops
+    Events {
+        #[command(subcommand)]
+        action: EventsCommand,
+    },
+    /// Inspección de contratos
+    Inspect {
+        #[command(subcommand)]
+        action: InspectCommand,
+    },
+    /// Calcula el diamondCut necesario para pasar a nuevas facetas, lo simula y opcionalmente lo ejecuta
+    PlanCut {
+        /// Dirección del diamond o clave del deployment
+        target: String,
+        /// Faceta nueva como NOMBRE=DIRECCION (el nombre es el artifact de forge)
+        #[arg(long = "facet", value_name = "NAME=ADDRESS", required = true)]
+        facets: Vec<String>,
+        /// Envía el corte si la simulación pasa
+        #[arg(long)]
+        execute: bool,
+    },
+    /// Informe de participación, pagos y runway de un loop
+    Analytics {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        #[arg(long, default_value_t = 0)]
+        from_block: u64,
+        /// Balance mínimo (en unidades del token) para la proyección de runway
+        #[arg(long, default_value = "1")]
+        threshold: String,
+        /// Exporta una fila por periodo a este CSV
+        #[arg(long)]
+        csv: Option<PathBuf>,
+        /// Exporta el informe completo a este JSON
+        #[arg(long)]
+        json: Option<PathBuf>,
+    },
+    /// Historial de participación y rachas de un usuario en todos los loops
+    History {
+        user: String,
+        #[arg(long, default_value_t = 0)]
+        from_block: u64,
+    },
+    /// Lee el diamond storage de loops y organizaciones con eth_getStorageAt
+    Storage {
+        #[command(subcommand)]
+        action: StorageCommand,
+    },
+    /// Gestión de la FacetRegistry
+    Registry {
+        #[command(subcommand)]
+        action: RegistryCommand,
+    },
+    /// Roles y permisos de AccessControlFacet
+    Access {
+        #[command(subcommand)]
+        action: AccessCommand,
+    },
+    /// Claimer automático: claimAndRegister cada periodo con las wallets de `CLAIMER_PKS`
+    ClaimBot {
+        /// Dirección del loop o clave del deployment
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        /// Pide las firmas a este backend en vez de firmar con TRUSTED_SIGNER_PK
+        #[arg(long)]
+        signer_url: Option<String>,
+        /// Hace un único pase y termina
+        #[arg(long)]
+        once: bool,
+    },
+    /// Crea un loop con N usuarios y mide gas, throughput y latencias de un periodo de claims
+    Bench {
+        #[arg(long, default_value_t = 100)]
+        users: usize,
+        /// Duración del periodo en segundos
+        #[arg(long, default_value_t = 3600)]
+        period_length: u64,
+        /// Porcentaje del balance que se reparte por periodo (1-100)
+        #[arg(long, default_value_t = 5)]
+        percent: u64,
+        /// Tokens a depositar en el loop, en unidades enteras
+        #[arg(long, default_value_t = 1000)]
+        fund: u64,
+        #[arg(long, default_value_t = 32)]
+        concurrency: usize,
+        /// Imprime el informe en JSON en vez de tabla
+        #[arg(long)]
+        json: bool,
+    },
+    /// Escenarios de ataque contra los loops
+    Scenarios {
+        #[command(subcommand)]
+        action: ScenarioCommand,
+    },
+    /// Consola interactiva con los contratos del deployment y completado de funciones
+    Console,
+    /// Dashboard en la terminal que sigue uno o varios loops en vivo
+    Dashboard {
+        /// Loops a seguir (se puede repetir)
+        #[arg(long = "loop", default_value = "loop")]
+        loop_targets: Vec<String>,
+        /// Segundos entre refrescos
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+        /// Bloque desde el que se cargan eventos (por defecto el actual)
+        #[arg(long)]
+        from_block: Option<u64>,
+    },
+    /// Organización, loop y fondeo de una vez; con el mismo `--id` retoma lo que quedó a medias
+    Onboard {
+        /// Identificador del alta: prefijo de sus entradas en el diario
+        #[arg(long)]
+        id: String,
+        name: String,
+        #[arg(long, default_value = "")]
+        description: String,
+        /// Admin de la organización (por defecto el signer)
+        #[arg(long)]
+        admin: Option<String>,
+        /// Duración del periodo en segundos
+        #[arg(long, default_value_t = 120)]
+        period_length: u64,
+        /// Porcentaje del balance que se reparte por periodo (1-100)
+        #[arg(long, default_value_t = 5)]
+        percent: u64,
+        /// Tokens a depositar en el loop (p.ej. 1500.5); sin él no se fondea
+        #[arg(long)]
+        fund: Option<String>,
+        /// Fichero del diario (por defecto .journal/<id>.jsonl)
+        #[arg(long)]
+        journal: Option<PathBuf>,
+    },
+    /// Diario de transacciones
+    Journal {
+        #[command(subcommand)]
+        action: JournalCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum JournalCommand {
+    /// Entradas del diario con su estado y su TX
+    Show { path: PathBuf },
+}
+
+#[derive(Subcommand)]
+pub enum ScenarioCommand {
+    /// Ataques Sybil con el bad_actor: replays, doble registro, claim sin registro
+    Sybil {
+        /// Dirección del loop atacado o clave del deployment
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        /// Segundo loop para los replays entre loops; si falta se crea uno en `organization`
+        #[arg(long = "other-loop")]
+        other_loop: Option<String>,
+    },
+    /// Cientos de TX desde muchas wallets a través del motor de envío concurrente
+    Load {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        #[arg(long, default_value_t = 50)]
+        wallets: usize,
+        /// Transferencias extra por wallet detrás del claimAndRegister
+        #[arg(long, default_value_t = 3)]
+        transfers: usize,
+        /// TX en vuelo como máximo
+        #[arg(long, default_value_t = 32)]
+        concurrency: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum OrgCommand {
+    /// Crea una organización en el system diamond (createOrganization)
+    Create {
+        name: String,
+        #[arg(long, default_value = "")]
+        description: String,
+        /// Admin de la organización (por defecto el signer)
+        #[arg(long)]
+        admin: Option<String>,
+    },
+    /// Nombre, admin y descripción de una organización
+    Info {
+        #[arg(default_value = "organization")]
+        target: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum EventsCommand {
+    /// Sigue los Register/Claim/Withdraw de un loop según se minan
+    Tail {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        /// Bloque desde el que empezar (por defecto el último)
+        #[arg(long)]
+        from_block: Option<u64>,
+        /// Segundos entre consultas
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum InspectCommand {
+    /// Muestra las facetas y selectores de un diamond y los compara con la FacetRegistry
+    Diamond {
+        /// Dirección del diamond o clave del deployment (`loop`, `organization`, `system_diamond`)
+        target: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LoopCommand {
+    /// Crea un loop desde una organización (createNewLoop)
+    Create {
+        #[arg(long, default_value = "organization")]
+        organization: String,
+        /// Duración del periodo en segundos
+        #[arg(long, default_value_t = 120)]
+        period_length: u64,
+        /// Porcentaje del balance que se reparte por periodo (1-100)
+        #[arg(long, default_value_t = 5)]
+        percent: u64,
+        /// Pasa la TX por este diario: repetir el comando retoma en vez de crear otro loop
+        #[arg(long)]
+        journal: Option<PathBuf>,
+    },
+    /// Parámetros, periodo en curso y balance de un loop
+    Info {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+    },
+    /// Deposita tokens en el loop (cantidad en unidades del token, p.ej. 1500.5)
+    Fund {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        amount: String,
+    },
+    /// Saca todo el balance del loop a `to`
+    Withdraw {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        to: String,
+    },
+    /// Cambia el trusted backend signer
+    SetSigner {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        signer: String,
+    },
+    /// Cambia el porcentaje por periodo
+    SetPercent {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        percent: u64,
+    },
+    /// getClaimerStatus de muchos usuarios en lote (Multicall3 o lotes JSON-RPC)
+    Claimers {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        /// Usuarios a consultar
+        users: Vec<String>,
+        /// Fichero con una dirección por línea (se suma a los usuarios dados)
+        #[arg(long)]
+        users_file: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TokenCommand {
+    /// Nombre, símbolo, decimales y totalSupply
+    Info {
+        #[arg(default_value = "test_token_address")]
+        token: String,
+    },
+    /// Balance de una dirección
+    Balance {
+        holder: String,
+        #[arg(long, default_value = "test_token_address")]
+        token: String,
+    },
+    /// Cuánto puede gastar `spender` de lo de `owner`
+    Allowance {
+        owner: String,
+        spender: String,
+        #[arg(long, default_value = "test_token_address")]
+        token: String,
+    },
+    /// Autoriza a `spender` (cantidad en unidades del token, p.ej. 1500.5)
+    Approve {
+        spender: String,
+        amount: String,
+        #[arg(long, default_value = "test_token_address")]
+        token: String,
+    },
+    /// Transfiere desde el signer (cantidad en unidades del token)
+    Transfer {
+        to: String,
+        amount: String,
+        #[arg(long, default_value = "test_token_address")]
+        token: String,
+    },
+    /// Acuña tokens en anvil escribiendo el storage del ERC20 (sólo redes locales)
+    Mint {
+        to: String,
+        amount: String,
+        #[arg(long, default_value = "test_token_address")]
+        token: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StorageCommand {
+    /// Campos de LoopStorage.Layout
+    Loop {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+    },
+    /// claimers[user]: registeredForPeriod y latestClaimPeriod
+    Claimer {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        user: String,
+    },
+    /// periods[n]: totalRegisteredUsers y maxPayout (n por defecto: periodo en curso)
+    Period {
+        #[arg(long = "loop", default_value = "loop")]
+        loop_target: String,
+        period: Option<u64>,
+    },
+    /// OrganizationStorage.Layout
+    Organization {
+        #[arg(default_value = "organization")]
+        target: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RegistryCommand {
+    /// Lista las facetas registradas y sus selectores
+    List,
+    /// Registra una faceta ya desplegada derivando los selectores de su artifact
+    Register { artifact: String, facet: String },
+    /// Predice la dirección CREATE2 de una faceta
+    Predict {
+        artifact: String,
+        /// bytes32 en hex o texto libre (se hashea con keccak256)
+        #[arg(long)]
+        salt: String,
+    },
+    /// Despliega y registra una faceta de forma determinista
+    Deploy {
+        artifact: String,
+        #[arg(long)]
+        salt: String,
+    },
+    /// Da de baja una faceta
+    Unregister { facet: String },
+    /// Historial de FacetRegistered/FacetUnregistered
+    History {
+        #[arg(long, default_value_t = 0)]
+        from_block: u64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AccessCommand {
+    /// Roles de un usuario
+    Roles { target: String, user: String },
+    /// Roles con acceso a una función (selector, firma o nombre)
+    FunctionRoles { target: String, function: String },
+    /// Comprueba si un usuario puede llamar a una función
+    CanCall {
+        target: String,
+        user: String,
+        function: String,
+    },
+    /// Da o quita un rol a un usuario
+    SetRole {
+        target: String,
+        user: String,
+        role: String,
+        #[arg(long)]
+        disable: bool,
+    },
+    /// Da o quita a un rol el acceso a una función
+    SetAccess {
+        target: String,
+        function: String,
+        role: String,
+        #[arg(long)]
+        disable: bool,
+    },
+    /// Reconstruye el historial de permisos desde eventos y marca estados de riesgo
+    Audit {
+        target: String,
+        #[arg(long, default_value_t = 0)]
+        from_block: u64,
+    },
+    /// Informe de quién puede llamar qué
+    Report {
+        target: String,
+        /// Usuarios a incluir además de los signers del entorno
+        #[arg(long = "user")]
+        users: Vec<String>,
+    },
+}
+
+/// Los escenarios y el benchmark existen para enviar TX: simularlos no mide nada.
+fn reject_dry_run(command: &str, opts: &GlobalOpts) -> Result<()> {
+    if opts.dry_run {
+        return Err(eyre::eyre!(t!(
+            "❌ {command} envía TX reales y no admite --dry-run",
+            command = command
+        )));
+    }
+    Ok(())
+}
+
+/// Ejecuta el subcomando elegido. Con `--dry-run` las escrituras sólo se simulan.
+pub async fn run(env: &Env, command: Command, opts: &GlobalOpts) -> Result<()> {
+    let dry_run = opts.dry_run;
+    match command {
+        Command::Org { action } => org(env, action, opts).await,
+        Command::Loop { action } => loops(env, action, opts).await,
+        Command::Claim {
+            loop_target,
+            wallet,
+            signature,
+        } => claim(env, &loop_target, &wallet, signature.as_deref(), opts).await,
+        Command::Sign {
+            user,
+            loop_target,
+            period,
+        } => sign(env, &user, &loop_target, period, opts).await,
+        Command::Token { action } => token(env, action, opts).await,
+        Command::Events { action } => events(env, action, opts).await,
+        Command::Inspect {
+            action: InspectCommand::Diamond { target },
+        } => inspect(env, &target, opts).await,
+        Command::PlanCut {
+            target,
+            facets,
+            execute,
+        } => plan_cut(env, &target, &facets, execute, opts).await,
+        Command::Analytics {
+            loop_target,
+            from_block,
+            threshold,
+            csv,
+            json,
+        } => {
+            analytics(
+                env,
+                &loop_target,
+                from_block,
+                &threshold,
+                csv.as_deref(),
+                json.as_deref(),
+                opts,
+            )
+            .await
+        }
+        Command::History { user, from_block } => history(env, &user, from_block, opts).await,
+        Command::Storage { action } => storage(env, action).await,
+        Command::Registry { action } => registry(env, action, opts).await,
+        Command::Access { action } => access(env, action, opts).await,
+        Command::ClaimBot {
+            loop_target,
+            signer_url,
+            once,
+        } => claim_bot(env, &loop_target, signer_url, once, opts).await,
+        Command::Bench {
+            users,
+            period_length,
+            percent,
+            fund,
+            concurrency,
+            json,
+        } => {
+            reject_dry_run("bench", opts)?;
+            let json = json || opts.json();
+            bench(env, users, period_length, percent, fund, concurrency, json).await
+        }
+        Command::Scenarios { action } => {
+            reject_dry_run("scenarios", opts)?;
+            scenarios(env, action).await
+        }
+        Command::Console => run_console(env, dry_run).await,
+        Command::Dashboard {
+            loop_targets,
+            interval,
+            from_block,
+        } => dashboard(env, &loop_targets, interval, from_block).await,
+        Command::Onboard {
+            id,
+            name,
+            description,
+            admin,
+            period_length,
+            percent,
+            fund,
+            journal,
+        } => {
+            let request = OnboardRequest {
+                id,
+                name,
+                description,
+                admin,
+                period_length,
+                percent,
+                fund,
+            };
+            onboard(env, request, journal, opts).await
+        }
+        Command::Journal { action } => journal(action, opts),
+    }
+}
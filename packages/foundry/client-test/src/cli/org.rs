@@ -0,0 +1,60 @@
+use eyre::Result;
+use serde_json::json;
+
+use crate::cli::{submit, GlobalOpts, OrgCommand};
+use crate::functions::{create_organization, create_organization_call, organization_info};
+use crate::t;
+use crate::Env;
+
+pub async fn org(env: &Env, action: OrgCommand, opts: &GlobalOpts) -> Result<()> {
+    match action {
+        OrgCommand::Create {
+            name,
+            description,
+            admin,
+        } => {
+            let admin = match admin {
+                Some(admin) => env.resolve_address(&admin)?,
+                None => env.signer()?.address(),
+            };
+            if opts.dry_run {
+                let call = create_organization_call(env, &name, admin, &description)?;
+                submit(env.signer()?, call, "createOrganization", opts).await?;
+                return Ok(());
+            }
+            let (tx_hash, organization) =
+                create_organization(env, &name, admin, &description).await?;
+            opts.emit(
+                json!({ "organization": organization, "admin": admin, "tx_hash": tx_hash }),
+                || {
+                    t!(
+                        "Organización creada en {address} (TX {tx})",
+                        address = ?organization,
+                        tx = ?tx_hash
+                    )
+                },
+            );
+        }
+        OrgCommand::Info { target } => {
+            let info = organization_info(env, env.resolve_address(&target)?).await?;
+            opts.emit(
+                json!({
+                    "organization": info.address,
+                    "name": info.name,
+                    "admin": info.admin,
+                    "description": info.description,
+                }),
+                || {
+                    t!(
+                        "Organización {address}\n  nombre:      {name}\n  admin:       {admin}\n  descripción: {description}",
+                        address = ?info.address,
+                        name = info.name,
+                        admin = ?info.admin,
+                        description = info.description
+                    )
+                },
+            );
+        }
+    }
+    Ok(())
+}
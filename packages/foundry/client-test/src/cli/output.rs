@@ -0,0 +1,148 @@
+use clap::ValueEnum;
+use ethers::abi::Detokenize;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::utils::keccak256;
+use eyre::Result;
+use serde_json::{json, Value};
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::journal::Journal;
+use crate::t;
+use crate::utils::{dry_run, dry_run_json, print_dry_run, SelectorBook, SignerClient, JOURNAL_DIR};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Texto para humanos
+    #[default]
+    Text,
+    /// Un objeto JSON por resultado, para scripts
+    Json,
+}
+
+/// Flags globales que afectan a todos los subcomandos.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlobalOpts {
+    pub dry_run: bool,
+    pub output: OutputFormat,
+}
+
+impl GlobalOpts {
+    pub fn json(&self) -> bool {
+        self.output == OutputFormat::Json
+    }
+
+    /// Imprime `value` en JSON o, en modo texto, lo que devuelva `text`.
+    pub fn emit(&self, value: Value, text: impl FnOnce() -> String) {
+        if self.json() {
+            println!("{}", value);
+        } else {
+            println!("{}", text());
+        }
+    }
+}
+
+/// Envía `call` desde `client` (o la simula con `--dry-run`), espera el receipt
+/// e informa en el formato pedido. La TX pasa por el diario de `action`: si un
+/// comando anterior murió con ella firmada, repetirlo la retoma en vez de
+/// firmar otra. Cada remitente tiene su propio diario, así que comandos a la
+/// vez desde wallets distintas no se bloquean entre sí. Una TX revertida es un error para que el proceso termine con
+/// código distinto de cero.
+pub async fn submit<R>(
+    client: Arc<SignerClient>,
+    call: ContractCall<SignerClient, R>,
+    action: &str,
+    opts: &GlobalOpts,
+) -> Result<Option<TransactionReceipt>>
+where
+    R: Detokenize + Debug,
+{
+    if opts.dry_run {
+        let preview = dry_run(&call, &SelectorBook::from_artifacts()?).await;
+        if opts.json() {
+            println!("{}", dry_run_json(&preview));
+        } else {
+            print_dry_run(&preview);
+        }
+        return Ok(None);
+    }
+    let mut journal = Journal::open(command_journal(action, client.address()))?;
+    let key = call_key(action, &client, &call.tx);
+    match journal.resume_or_send(&key, &client, call).await {
+        Ok(sent) => {
+            if sent.resumed {
+                info!(key = %sent.key, journal = %journal.path().display(), "TX retomada del diario");
+            }
+            report_receipt(action, &sent.receipt, opts)?;
+            Ok(Some(sent.receipt))
+        }
+        // Revertida al minarse: el receipt queda en el diario y se informa igual
+        Err(error) => match journal.entry(&key).and_then(|entry| entry.receipt.clone()) {
+            Some(receipt) => report_receipt(action, &receipt, opts).map(|_| None),
+            None => Err(error.into()),
+        },
+    }
+}
+
+/// Diario por defecto de los comandos sueltos: `.journal/<action>-<from>.jsonl`.
+pub fn command_journal(action: &str, from: Address) -> PathBuf {
+    Path::new(JOURNAL_DIR).join(format!("{}-{:?}.jsonl", action, from))
+}
+
+/// Clave de diario de una llamada: la misma TX (chain, remitente, destino,
+/// datos y valor) cae siempre en la misma entrada.
+pub fn call_key(action: &str, client: &SignerClient, tx: &TypedTransaction) -> String {
+    let mut preimage = client.signer().chain_id().to_be_bytes().to_vec();
+    preimage.extend(client.address().as_bytes());
+    preimage.extend(tx.to_addr().copied().unwrap_or_default().as_bytes());
+    preimage.extend(tx.data().map(|data| data.to_vec()).unwrap_or_default());
+    let mut value = [0u8; 32];
+    tx.value()
+        .copied()
+        .unwrap_or_default()
+        .to_big_endian(&mut value);
+    preimage.extend(value);
+    format!("{}/{:?}", action, H256::from(keccak256(preimage)))
+}
+
+/// Informa del resultado de una TX minada con su `status` y bloque.
+pub fn report_receipt(action: &str, receipt: &TransactionReceipt, opts: &GlobalOpts) -> Result<()> {
+    let success = receipt.status == Some(U64::one());
+    let tx_hash = receipt.transaction_hash;
+    let block = receipt.block_number.unwrap_or_default();
+    opts.emit(
+        json!({
+            "action": action,
+            "tx_hash": tx_hash,
+            "status": receipt.status.map(|status| status.as_u64()),
+            "block": block.as_u64(),
+        }),
+        || {
+            if success {
+                t!(
+                    "✅ {action} minado en el bloque {block} (TX {tx})",
+                    action = action,
+                    block = block,
+                    tx = ?tx_hash
+                )
+            } else {
+                t!(
+                    "❌ {action} revertido en el bloque {block} (TX {tx})",
+                    action = action,
+                    block = block,
+                    tx = ?tx_hash
+                )
+            }
+        },
+    );
+    if !success {
+        return Err(eyre::eyre!(t!(
+            "❌ La TX {tx} revirtió",
+            tx = ?tx_hash
+        )));
+    }
+    Ok(())
+}
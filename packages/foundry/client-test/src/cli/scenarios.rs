@@ -0,0 +1,43 @@
+use eyre::Result;
+
+use crate::cli::ScenarioCommand;
+use crate::scenarios::{print_load_report, print_scenario_report, LoadTest, SybilSuite};
+use crate::t;
+use crate::Env;
+
+pub async fn scenarios(env: &Env, action: ScenarioCommand) -> Result<()> {
+    match action {
+        ScenarioCommand::Sybil {
+            loop_target,
+            other_loop,
+        } => {
+            let loop_a = env.resolve_address(&loop_target)?;
+            let loop_b = other_loop
+                .map(|target| env.resolve_address(&target))
+                .transpose()?;
+            let results = SybilSuite::new(env, loop_a, loop_b)?.run().await?;
+            print_scenario_report(&results);
+            if results.iter().any(|r| !r.passed) {
+                return Err(eyre::eyre!(t!(
+                    "❌ Algún escenario no se comportó como se esperaba"
+                )));
+            }
+            Ok(())
+        }
+        ScenarioCommand::Load {
+            loop_target,
+            wallets,
+            transfers,
+            concurrency,
+        } => {
+            let test = LoadTest {
+                wallets,
+                transfers_per_wallet: transfers,
+                concurrency,
+            };
+            let report = test.run(env, env.resolve_address(&loop_target)?).await?;
+            print_load_report(&report);
+            Ok(())
+        }
+    }
+}
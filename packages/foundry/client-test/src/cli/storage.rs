@@ -0,0 +1,71 @@
+use ethers::types::U256;
+use eyre::Result;
+
+use crate::cli::StorageCommand;
+use crate::functions::current_period;
+use crate::storage::{
+    claimer_slot, period_slot, read_claimer, read_loop_layout, read_organization_layout,
+    read_period,
+};
+use crate::t;
+use crate::Env;
+
+pub async fn storage(env: &Env, action: StorageCommand) -> Result<()> {
+    match action {
+        StorageCommand::Loop { loop_target } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            let layout = read_loop_layout(env, loop_address).await?;
+            println!(
+                "{}",
+                t!("{layout} de {address}", layout = "LoopStorage", address = ?loop_address)
+            );
+            println!("  loopAdmin:            {:?}", layout.loop_admin);
+            println!("  token:                {:?}", layout.token);
+            println!("  periodLength:         {}", layout.period_length);
+            println!("  percentPerPeriod:     {}", layout.percent_per_period);
+            println!("  firstPeriodStart:     {}", layout.first_period_start);
+            println!(
+                "  trustedBackendSigner: {:?}",
+                layout.trusted_backend_signer
+            );
+        }
+        StorageCommand::Claimer { loop_target, user } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            let user = env.resolve_address(&user)?;
+            let claimer = read_claimer(env, loop_address, user).await?;
+            println!("claimers[{:?}] (slot {:#x})", user, claimer_slot(user));
+            println!("  registeredForPeriod: {}", claimer.registered_for_period);
+            println!("  latestClaimPeriod:   {}", claimer.latest_claim_period);
+        }
+        StorageCommand::Period {
+            loop_target,
+            period,
+        } => {
+            let loop_address = env.resolve_address(&loop_target)?;
+            let period = match period {
+                Some(period) => U256::from(period),
+                None => current_period(env, loop_address).await?,
+            };
+            let data = read_period(env, loop_address, period).await?;
+            println!("periods[{}] (slot {:#x})", period, period_slot(period));
+            println!("  totalRegisteredUsers: {}", data.total_registered_users);
+            println!("  maxPayout:            {}", data.max_payout);
+        }
+        StorageCommand::Organization { target } => {
+            let organization = env.resolve_address(&target)?;
+            let layout = read_organization_layout(env, organization).await?;
+            println!(
+                "{}",
+                t!(
+                    "{layout} de {address}",
+                    layout = "OrganizationStorage",
+                    address = ?organization
+                )
+            );
+            println!("  name:        {}", layout.name);
+            println!("  admin:       {:?}", layout.admin);
+            println!("  description: {}", layout.description);
+        }
+    }
+    Ok(())
+}
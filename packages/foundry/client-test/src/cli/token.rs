@@ -0,0 +1,161 @@
+use ethers::prelude::*;
+use eyre::Result;
+use serde_json::json;
+
+use crate::cli::{submit, GlobalOpts, TokenCommand};
+use crate::functions::{
+    approve_call, mint_local, mint_plan, token_allowance, token_balance, token_info, transfer_call,
+    StorageWrite, TokenInfo,
+};
+use crate::t;
+use crate::Env;
+
+pub async fn token(env: &Env, action: TokenCommand, opts: &GlobalOpts) -> Result<()> {
+    match action {
+        TokenCommand::Info { token } => {
+            let info = token_info(env, env.resolve_address(&token)?).await?;
+            opts.emit(
+                json!({
+                    "token": info.address,
+                    "name": info.name,
+                    "symbol": info.symbol,
+                    "decimals": info.decimals,
+                    "total_supply": info.total_supply.to_string(),
+                    "total_supply_units": info.format(info.total_supply),
+                }),
+                || {
+                    t!(
+                        "Token {address}\n  nombre:       {name}\n  símbolo:      {symbol}\n  decimales:    {decimals}\n  totalSupply:  {supply}",
+                        address = ?info.address,
+                        name = info.name,
+                        symbol = info.symbol,
+                        decimals = info.decimals,
+                        supply = info.display(info.total_supply)
+                    )
+                },
+            );
+        }
+        TokenCommand::Balance { holder, token } => {
+            let info = token_info(env, env.resolve_address(&token)?).await?;
+            let holder = env.resolve_address(&holder)?;
+            let balance = token_balance(env, info.address, holder).await?;
+            opts.emit(
+                json!({
+                    "token": info.address,
+                    "holder": holder,
+                    "balance": balance.to_string(),
+                    "balance_units": info.format(balance),
+                }),
+                || info.display(balance),
+            );
+        }
+        TokenCommand::Allowance {
+            owner,
+            spender,
+            token,
+        } => {
+            let info = token_info(env, env.resolve_address(&token)?).await?;
+            let (owner, spender) = (env.resolve_address(&owner)?, env.resolve_address(&spender)?);
+            let allowance = token_allowance(env, info.address, owner, spender).await?;
+            opts.emit(
+                json!({
+                    "token": info.address,
+                    "owner": owner,
+                    "spender": spender,
+                    "allowance": allowance.to_string(),
+                    "allowance_units": info.format(allowance),
+                }),
+                || info.display(allowance),
+            );
+        }
+        TokenCommand::Approve {
+            spender,
+            amount,
+            token,
+        } => {
+            let info = token_info(env, env.resolve_address(&token)?).await?;
+            let call = approve_call(
+                env,
+                info.address,
+                env.resolve_address(&spender)?,
+                info.parse(&amount)?,
+            )?;
+            submit(env.signer()?, call, "approve", opts).await?;
+        }
+        TokenCommand::Transfer { to, amount, token } => {
+            let info = token_info(env, env.resolve_address(&token)?).await?;
+            let call = transfer_call(
+                env,
+                info.address,
+                env.resolve_address(&to)?,
+                info.parse(&amount)?,
+            )?;
+            submit(env.signer()?, call, "transfer", opts).await?;
+        }
+        TokenCommand::Mint { to, amount, token } => {
+            let info = token_info(env, env.resolve_address(&token)?).await?;
+            let to = env.resolve_address(&to)?;
+            let amount = info.parse(&amount)?;
+            if opts.dry_run {
+                let writes = mint_plan(&env.provider()?, info.address, to, amount).await?;
+                print_mint_plan(&info, to, amount, &writes, opts);
+                return Ok(());
+            }
+            mint_local(env, info.address, to, amount).await?;
+            opts.emit(
+                json!({ "token": info.address, "to": to, "amount": amount.to_string() }),
+                || {
+                    t!(
+                        "Acuñados {amount} para {to}",
+                        amount = info.display(amount),
+                        to = ?to
+                    )
+                },
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `token mint --dry-run`: los slots que se escribirían y con qué valores.
+fn print_mint_plan(
+    info: &TokenInfo,
+    to: Address,
+    amount: U256,
+    writes: &[StorageWrite],
+    opts: &GlobalOpts,
+) {
+    opts.emit(
+        json!({
+            "dry_run": true,
+            "token": info.address,
+            "to": to,
+            "amount": amount.to_string(),
+            "writes": writes
+                .iter()
+                .map(|write| json!({
+                    "slot": H256::from_uint(&write.slot),
+                    "before": write.before.to_string(),
+                    "after": write.after.to_string(),
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        || {
+            let mut text = t!(
+                "🧪 Dry run: acuñar {amount} para {to} escribiría en {token}:",
+                amount = info.display(amount),
+                to = ?to,
+                token = ?info.address
+            );
+            for write in writes {
+                text.push_str(&format!(
+                    "\n  {:?}: {} → {}",
+                    H256::from_uint(&write.slot),
+                    info.display(write.before),
+                    info.display(write.after)
+                ));
+            }
+            text
+        },
+    );
+}
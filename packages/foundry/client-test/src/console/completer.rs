@@ -0,0 +1,84 @@
+use rustyline::completion::Completer;
+use rustyline::{Context, Helper, Highlighter, Hinter, Validator};
+use std::collections::BTreeMap;
+
+use crate::console::{Session, COMMANDS};
+
+/// Completado con tabulador: comandos, nombres de la sesión y, tras `nombre.`,
+/// las funciones de la ABI de ese contrato.
+#[derive(Default, Helper, Hinter, Highlighter, Validator)]
+pub struct ConsoleHelper {
+    targets: BTreeMap<String, Vec<String>>,
+    signers: Vec<String>,
+}
+
+impl ConsoleHelper {
+    /// Se llama tras cada línea para reflejar `set` y `signer`.
+    pub fn refresh(&mut self, session: &Session) {
+        self.targets = session.completions();
+        self.signers = session.signers.keys().cloned().collect();
+    }
+
+    fn candidates(&self, line: &str, word: &str) -> (usize, Vec<String>) {
+        let first_word = !line.trim_start().contains(char::is_whitespace);
+        if first_word {
+            let commands = COMMANDS
+                .iter()
+                .map(|(c, _)| c.to_string())
+                .filter(|c| c.starts_with(word))
+                .collect();
+            return (0, commands);
+        }
+
+        if let Some((name, method)) = word.split_once('.') {
+            let methods = self
+                .targets
+                .get(name)
+                .map(|methods| {
+                    methods
+                        .iter()
+                        .filter(|m| m.starts_with(method))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default();
+            return (name.len() + 1, methods);
+        }
+
+        let command = line.split_whitespace().next().unwrap_or_default();
+        let names: Vec<String> = match command {
+            "signer" => self.signers.clone(),
+            // El primer argumento es `contrato.función`
+            "call" | "send" | "dry" => self.targets.keys().map(|t| format!("{}.", t)).collect(),
+            _ => self
+                .targets
+                .keys()
+                .chain(self.signers.iter())
+                .cloned()
+                .collect(),
+        };
+        (
+            0,
+            names.into_iter().filter(|n| n.starts_with(word)).collect(),
+        )
+    }
+}
+
+impl Completer for ConsoleHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line
+            .rfind(char::is_whitespace)
+            .map(|i| i + 1)
+            .unwrap_or_default();
+        let (offset, candidates) = self.candidates(line, &line[start..]);
+        Ok((start + offset, candidates))
+    }
+}
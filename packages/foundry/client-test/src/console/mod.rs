@@ -0,0 +1,6 @@
+pub mod completer;
+pub use completer::*;
+pub mod session;
+pub use session::*;
+pub mod repl;
+pub use repl::*;
@@ -0,0 +1,57 @@
+use eyre::Result;
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::Editor;
+
+use crate::console::{ConsoleHelper, Session};
+use crate::t;
+use crate::utils::CONSOLE_HISTORY;
+use crate::Env;
+
+/// Bucle interactivo sobre una `Session`. Los errores de cada comando se
+/// muestran sin cerrar la consola. Con `dry_run`, `send` sólo simula.
+pub async fn run_console(env: &Env, dry_run: bool) -> Result<()> {
+    let mut session = Session::new(env).await?;
+    session.dry_run = dry_run;
+    let mut editor: Editor<ConsoleHelper, DefaultHistory> = Editor::new()?;
+    let mut helper = ConsoleHelper::default();
+    helper.refresh(&session);
+    editor.set_helper(Some(helper));
+    // Sin historial previo se empieza de cero
+    let _ = editor.load_history(CONSOLE_HISTORY);
+
+    println!(
+        "{}",
+        t!(
+            "Consola de Gyralis en {rpc} (chain {chain}). `help` para ver los comandos.",
+            rpc = env.rpc_url,
+            chain = env.chain_id
+        )
+    );
+    loop {
+        let prompt = format!("{}> ", session.signer);
+        // readline bloquea: se cede el hilo del runtime mientras se espera la línea
+        let line = match tokio::task::block_in_place(|| editor.readline(&prompt)) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line.as_str())?;
+
+        match session.execute(&line).await {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => println!("{}", e),
+        }
+        if let Some(helper) = editor.helper_mut() {
+            helper.refresh(&session);
+        }
+    }
+
+    let _ = editor.save_history(CONSOLE_HISTORY);
+    Ok(())
+}
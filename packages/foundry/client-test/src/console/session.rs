@@ -0,0 +1,690 @@
+use ethers::abi::token::{LenientTokenizer, Tokenizer};
+use ethers::abi::{Abi, Function, ParamType, RawLog, StateMutability, Token};
+use ethers::prelude::*;
+use eyre::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env as std_env;
+use std::sync::Arc;
+
+use crate::diamond::load_diamond_state;
+use crate::i18n::tr;
+use crate::t;
+use crate::utils::{
+    dry_run, format_selector, function_signature, load_abi, print_dry_run, SelectorBook,
+    SignerClient,
+};
+use crate::Env;
+
+/// Diamonds del deployment que se cargan al abrir la consola. Su ABI se arma
+/// con lo que enruta el loupe, así que sólo ofrece funciones que existen.
+pub const DIAMOND_TARGETS: &[&str] = &["loop", "organization", "system_diamond", "factory_diamond"];
+
+/// Contratos sin loupe: clave del deployment y artifacts cuyas ABIs se combinan.
+pub const DEFAULT_TARGETS: &[(&str, &[&str])] = &[
+    ("facet_registry", &["FacetRegistry"]),
+    ("test_token_address", &["TestToken"]),
+    ("DiamondCutFacet", &["DiamondCutFacet"]),
+    ("DiamondLoupeFacet", &["DiamondLoupeFacet"]),
+    ("AccessControlFacet", &["AccessControlFacet"]),
+    ("OrganizationFactoryFacet", &["OrganizationFactoryFacet"]),
+    ("OrganizationFacet", &["OrganizationFacet"]),
+    ("LoopFactoryFacet", &["LoopFactoryFacet"]),
+    ("LoopFacet", &["LoopFacet"]),
+];
+
+/// Comandos de la consola, en el orden en que los muestra `help`.
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("help", "esta ayuda"),
+    ("ls", "contratos y signers de la sesión"),
+    (
+        "set",
+        "set <nombre> <dirección> [artifact...]: nombra un contrato",
+    ),
+    (
+        "signer",
+        "signer [nombre] [clave|VARIABLE]: lista, elige o añade un signer",
+    ),
+    ("abi", "abi <contrato>: funciones y eventos de un contrato"),
+    ("call", "call <contrato>.<función> [args...]: eth_call"),
+    (
+        "send",
+        "send <contrato>.<función> [args...]: envía la TX con el signer actual",
+    ),
+    (
+        "dry",
+        "dry <contrato>.<función> [args...]: simula la TX y estima el gas",
+    ),
+    (
+        "logs",
+        "logs <contrato> [desde_bloque]: eventos decodificados",
+    ),
+    (
+        "decode",
+        "decode <hex>: decodifica calldata o datos de revert",
+    ),
+    ("exit", "sale de la consola"),
+];
+
+/// Contrato con nombre dentro de la sesión.
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub address: Address,
+    pub artifacts: Vec<String>,
+    pub abi: Abi,
+}
+
+/// Estado de la consola: el `Env` se carga una vez y los contratos y signers
+/// se referencian por nombre.
+pub struct Session<'a> {
+    env: &'a Env,
+    book: SelectorBook,
+    pub targets: BTreeMap<String, Target>,
+    pub signers: BTreeMap<String, Arc<SignerClient>>,
+    pub signer: String,
+    /// `--dry-run`: `send` se comporta como `dry`.
+    pub dry_run: bool,
+}
+
+/// Combina varias ABIs en una, como las ve un diamond con esas facetas.
+pub fn merge_abis(artifacts: &[String]) -> Result<Abi> {
+    merge_abis_with(artifacts, |_| true)
+}
+
+/// Como `merge_abis`, pero sólo con las funciones que cumplen `keep`.
+fn merge_abis_with(artifacts: &[String], keep: impl Fn(&Function) -> bool) -> Result<Abi> {
+    let mut merged = Abi::default();
+    for artifact in artifacts {
+        let abi = load_abi(artifact)?;
+        for (name, functions) in abi.functions {
+            for function in functions.into_iter().filter(|f| keep(f)) {
+                let entry = merged.functions.entry(name.clone()).or_default();
+                if !entry.iter().any(|f| f.signature() == function.signature()) {
+                    entry.push(function);
+                }
+            }
+        }
+        for (name, events) in abi.events {
+            let entry = merged.events.entry(name).or_default();
+            for event in events {
+                if !entry.iter().any(|e| e.signature() == event.signature()) {
+                    entry.push(event);
+                }
+            }
+        }
+        for (name, errors) in abi.errors {
+            merged.errors.entry(name).or_insert(errors);
+        }
+    }
+    Ok(merged)
+}
+
+/// ABI de un diamond según su loupe: por cada selector enrutado, el artifact
+/// local que lo declara (una faceta si la hay). Devuelve también esos artifacts.
+pub async fn diamond_abi(
+    env: &Env,
+    diamond: Address,
+    book: &SelectorBook,
+) -> Result<(Vec<String>, Abi)> {
+    let state = load_diamond_state(env, diamond).await?;
+    let routed: BTreeSet<[u8; 4]> = state.routes.keys().copied().collect();
+    let mut artifacts = BTreeSet::new();
+    for selector in &routed {
+        let owners = book.contracts(*selector);
+        if let Some(owner) = owners
+            .iter()
+            .find(|name| name.ends_with("Facet"))
+            .or(owners.first())
+        {
+            artifacts.insert(owner.to_string());
+        }
+    }
+    let artifacts: Vec<String> = artifacts.into_iter().collect();
+    let abi = merge_abis_with(&artifacts, |f| routed.contains(&f.short_signature()))?;
+    Ok((artifacts, abi))
+}
+
+/// Separa una línea en palabras respetando comillas simples y dobles.
+pub fn split_line(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_word = false;
+    for c in line.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err(eyre::eyre!(t!("❌ Comillas sin cerrar")));
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+fn mutability(function: &Function) -> &'static str {
+    match function.state_mutability {
+        StateMutability::Pure => "pure",
+        StateMutability::View => "view",
+        StateMutability::NonPayable => "nonpayable",
+        StateMutability::Payable => "payable",
+    }
+}
+
+impl<'a> Session<'a> {
+    /// Carga los contratos del deployment y los signers del entorno. Los que
+    /// faltan (clave ausente, loupe inaccesible o artifact sin compilar) se omiten.
+    pub async fn new(env: &'a Env) -> Result<Self> {
+        let mut session = Self {
+            env,
+            book: SelectorBook::from_artifacts()?,
+            targets: BTreeMap::new(),
+            signers: BTreeMap::new(),
+            signer: "trusted".to_string(),
+            dry_run: false,
+        };
+
+        for key in DIAMOND_TARGETS {
+            let Ok(address) = env.deployment_address(key) else {
+                continue;
+            };
+            if let Ok((artifacts, abi)) = diamond_abi(env, address, &session.book).await {
+                session.targets.insert(
+                    key.to_string(),
+                    Target {
+                        address,
+                        artifacts,
+                        abi,
+                    },
+                );
+            }
+        }
+        for (key, artifacts) in DEFAULT_TARGETS {
+            let Ok(address) = env.deployment_address(key) else {
+                continue;
+            };
+            let artifacts: Vec<String> = artifacts.iter().map(|a| a.to_string()).collect();
+            if let Ok(abi) = merge_abis(&artifacts) {
+                session.targets.insert(
+                    key.to_string(),
+                    Target {
+                        address,
+                        artifacts,
+                        abi,
+                    },
+                );
+            }
+        }
+
+        session.signers.insert("trusted".to_string(), env.signer()?);
+        if let Some(bad_signer) = env.bad_signer.clone() {
+            session.signers.insert("bad_actor".to_string(), bad_signer);
+        }
+        Ok(session)
+    }
+
+    pub fn current_signer(&self) -> Result<Arc<SignerClient>> {
+        self.signers
+            .get(&self.signer)
+            .cloned()
+            .ok_or_else(|| eyre::eyre!(t!("❌ Signer desconocido: {name}", name = self.signer)))
+    }
+
+    /// Nombres de contratos con los nombres de sus funciones, para el completado.
+    pub fn completions(&self) -> BTreeMap<String, Vec<String>> {
+        self.targets
+            .iter()
+            .map(|(name, target)| (name.clone(), target.abi.functions.keys().cloned().collect()))
+            .collect()
+    }
+
+    /// Acepta una dirección, un contrato o signer de la sesión o una clave del deployment.
+    pub fn resolve(&self, name: &str) -> Result<Address> {
+        if let Some(target) = self.targets.get(name) {
+            return Ok(target.address);
+        }
+        if let Some(signer) = self.signers.get(name) {
+            return Ok(signer.address());
+        }
+        Ok(self.env.resolve_address(name)?)
+    }
+
+    fn target(&self, name: &str) -> Result<&Target> {
+        self.targets.get(name).ok_or_else(|| {
+            eyre::eyre!(t!(
+                "❌ Contrato desconocido: {name} (usa `set` para nombrarlo)",
+                name = name
+            ))
+        })
+    }
+
+    /// Resuelve `contrato.función` y elige la sobrecarga según el número de argumentos.
+    /// También acepta la firma completa: `loop.claimAndRegister(bytes)`.
+    fn function(&self, spec: &str, args: usize) -> Result<(&Target, Function)> {
+        let (name, method) = spec.split_once('.').ok_or_else(|| {
+            eyre::eyre!(t!(
+                "❌ Formato esperado contrato.función: {spec}",
+                spec = spec
+            ))
+        })?;
+        let target = self.target(name)?;
+
+        let candidates: Vec<&Function> = if method.contains('(') {
+            let signature = method.replace(' ', "");
+            target
+                .abi
+                .functions()
+                .filter(|f| function_signature(f) == signature)
+                .collect()
+        } else {
+            target
+                .abi
+                .functions_by_name(method)
+                .map_err(|_| {
+                    eyre::eyre!(t!(
+                        "❌ {name} no tiene la función {method}",
+                        name = name,
+                        method = method
+                    ))
+                })?
+                .iter()
+                .filter(|f| f.inputs.len() == args)
+                .collect()
+        };
+
+        match candidates.as_slice() {
+            [function] => Ok((target, (*function).clone())),
+            [] => Err(eyre::eyre!(t!(
+                "❌ {name}.{method} no recibe {args} argumentos",
+                name = name,
+                method = method,
+                args = args
+            ))),
+            _ => Err(eyre::eyre!(t!(
+                "❌ {name}.{method} es ambigua, indica la firma completa",
+                name = name,
+                method = method
+            ))),
+        }
+    }
+
+    /// Tokeniza los argumentos según la ABI; las direcciones admiten nombres de la sesión.
+    fn tokenize(&self, function: &Function, args: &[String]) -> Result<Vec<Token>> {
+        function
+            .inputs
+            .iter()
+            .zip(args)
+            .map(|(param, arg)| match param.kind {
+                ParamType::Address => Ok(Token::Address(self.resolve(arg)?)),
+                ref kind => LenientTokenizer::tokenize(kind, arg).map_err(|e| {
+                    eyre::eyre!(t!(
+                        "❌ Argumento {arg} inválido para {kind}: {error}",
+                        arg = arg,
+                        kind = param.kind,
+                        error = e
+                    ))
+                }),
+            })
+            .collect()
+    }
+
+    fn contract_call<M: Middleware>(
+        &self,
+        spec: &str,
+        args: &[String],
+        client: Arc<M>,
+    ) -> Result<(Function, ContractCall<M, Token>)> {
+        let (target, function) = self.function(spec, args.len())?;
+        let tokens = self.tokenize(&function, args)?;
+        let contract = Contract::new(target.address, target.abi.clone(), client);
+        let call = contract
+            .method_hash::<Token, Token>(function.short_signature(), Token::Tuple(tokens))?;
+        Ok((function, call))
+    }
+
+    /// Decodifica un log con las ABIs de la sesión.
+    pub fn decode_log(&self, log: &Log) -> String {
+        let topic = match log.topics.first() {
+            Some(topic) => *topic,
+            None => return t!("{address} log anónimo", address = ?log.address),
+        };
+        for target in self.targets.values() {
+            for event in target.abi.events() {
+                if event.signature() != topic {
+                    continue;
+                }
+                let raw = RawLog {
+                    topics: log.topics.clone(),
+                    data: log.data.to_vec(),
+                };
+                if let Ok(parsed) = event.parse_log(raw) {
+                    let params: Vec<String> = parsed
+                        .params
+                        .iter()
+                        .map(|p| format!("{}={}", p.name, p.value))
+                        .collect();
+                    return format!("{}({})", event.name, params.join(", "));
+                }
+            }
+        }
+        t!(
+            "{address} evento desconocido {topic}",
+            address = ?log.address,
+            topic = ?topic
+        )
+    }
+
+    /// Decodifica calldata con las ABIs de la sesión o, si no, como datos de revert.
+    pub fn decode(&self, data: &[u8]) -> String {
+        if data.len() >= 4 {
+            let selector = [data[0], data[1], data[2], data[3]];
+            for target in self.targets.values() {
+                for function in target.abi.functions() {
+                    if function.short_signature() != selector {
+                        continue;
+                    }
+                    if let Ok(tokens) = function.decode_input(&data[4..]) {
+                        let args: Vec<String> = tokens.iter().map(ToString::to_string).collect();
+                        return format!("{}({})", function.name, args.join(", "));
+                    }
+                }
+            }
+        }
+        self.book.decode_revert(data)
+    }
+
+    /// Ejecuta una línea. Devuelve `false` cuando hay que salir.
+    pub async fn execute(&mut self, line: &str) -> Result<bool> {
+        let words = split_line(line)?;
+        let Some((command, args)) = words.split_first() else {
+            return Ok(true);
+        };
+        match command.as_str() {
+            "help" => self.help(),
+            "ls" => self.ls(),
+            "set" => self.set(args).await?,
+            "signer" => self.select_signer(args)?,
+            "abi" => self.abi(args)?,
+            "call" => self.call(args).await?,
+            "send" => self.send(args).await?,
+            "dry" => self.dry(args).await?,
+            "logs" => self.logs(args).await?,
+            "decode" => self.decode_command(args)?,
+            "exit" | "quit" => return Ok(false),
+            other => {
+                return Err(eyre::eyre!(t!(
+                    "❌ Comando desconocido: {command} (prueba `help`)",
+                    command = other
+                )))
+            }
+        }
+        Ok(true)
+    }
+
+    fn help(&self) {
+        for (command, description) in COMMANDS {
+            println!("  {:<8} {}", command, tr(description));
+        }
+        println!(
+            "  {}",
+            t!("Las direcciones admiten nombres de la sesión (loop, trusted, ...)")
+        );
+    }
+
+    fn ls(&self) {
+        println!("{}", t!("Contratos:"));
+        for (name, target) in &self.targets {
+            println!(
+                "  {:<26} {:?} [{}]",
+                name,
+                target.address,
+                target.artifacts.join(", ")
+            );
+        }
+        println!("{}", t!("Signers:"));
+        for (name, signer) in &self.signers {
+            let marker = if *name == self.signer { "*" } else { " " };
+            println!("{} {:<26} {:?}", marker, name, signer.address());
+        }
+    }
+
+    /// Sin artifacts, la ABI sale del loupe de la dirección; si no es un
+    /// diamond se reutilizan los artifacts que ya tuviera `nombre`.
+    async fn set(&mut self, args: &[String]) -> Result<()> {
+        let [name, address, artifacts @ ..] = args else {
+            return Err(eyre::eyre!(t!(
+                "❌ Uso: set <nombre> <dirección> [artifact...]"
+            )));
+        };
+        let address = self.resolve(address)?;
+        let (artifacts, abi) = if !artifacts.is_empty() {
+            (artifacts.to_vec(), merge_abis(artifacts)?)
+        } else if let Ok(loaded) = diamond_abi(self.env, address, &self.book).await {
+            loaded
+        } else {
+            let artifacts = self
+                .targets
+                .get(name)
+                .map(|t| t.artifacts.clone())
+                .ok_or_else(|| {
+                    eyre::eyre!(t!(
+                        "❌ Indica al menos un artifact para {name}",
+                        name = name
+                    ))
+                })?;
+            let abi = merge_abis(&artifacts)?;
+            (artifacts, abi)
+        };
+        println!(
+            "{}",
+            t!(
+                "✅ {name} = {address} ({count} funciones)",
+                name = name,
+                address = ?address,
+                count = abi.functions().count()
+            )
+        );
+        self.targets.insert(
+            name.clone(),
+            Target {
+                address,
+                artifacts,
+                abi,
+            },
+        );
+        Ok(())
+    }
+
+    fn select_signer(&mut self, args: &[String]) -> Result<()> {
+        match args {
+            [] => {
+                let signer = self.current_signer()?;
+                println!("{} {:?}", self.signer, signer.address());
+            }
+            [name] => {
+                if !self.signers.contains_key(name) {
+                    return Err(eyre::eyre!(t!(
+                        "❌ Signer desconocido: {name}",
+                        name = name
+                    )));
+                }
+                self.signer = name.clone();
+            }
+            [name, pk] => {
+                // La clave puede venir directamente o en una variable de entorno
+                let pk = match pk.parse::<LocalWallet>() {
+                    Ok(_) => pk.clone(),
+                    Err(_) => std_env::var(pk)
+                        .map_err(|_| eyre::eyre!(t!("❌ {name} no está definida", name = pk)))?,
+                };
+                let signer = self.env.wallet(&pk)?;
+                println!("✅ {} = {:?}", name, signer.address());
+                self.signers.insert(name.clone(), signer);
+                self.signer = name.clone();
+            }
+            _ => return Err(eyre::eyre!(t!("❌ Uso: signer [nombre] [clave|VARIABLE]"))),
+        }
+        Ok(())
+    }
+
+    fn abi(&self, args: &[String]) -> Result<()> {
+        let [name] = args else {
+            return Err(eyre::eyre!(t!("❌ Uso: abi <contrato>")));
+        };
+        let target = self.target(name)?;
+        for function in target.abi.functions() {
+            let outputs: Vec<String> = function
+                .outputs
+                .iter()
+                .map(|p| p.kind.to_string())
+                .collect();
+            println!(
+                "  {} {:<50} {:<10} -> ({})",
+                format_selector(function.short_signature()),
+                function_signature(function),
+                mutability(function),
+                outputs.join(",")
+            );
+        }
+        for event in target.abi.events() {
+            let inputs: Vec<String> = event.inputs.iter().map(|p| p.kind.to_string()).collect();
+            println!("  event {}({})", event.name, inputs.join(","));
+        }
+        Ok(())
+    }
+
+    async fn call(&self, args: &[String]) -> Result<()> {
+        let (spec, args) = args
+            .split_first()
+            .ok_or_else(|| eyre::eyre!(t!("❌ Uso: call <contrato>.<función> [args...]")))?;
+        let client = Arc::new(self.env.provider()?);
+        let (_, call) = self.contract_call(spec, args, client)?;
+        let call = call.from(self.current_signer()?.address());
+        match call.call().await {
+            Ok(Token::Tuple(tokens)) if tokens.is_empty() => println!("()"),
+            Ok(value) => println!("{}", value),
+            Err(e) => match e.as_revert() {
+                Some(data) => println!(
+                    "{}",
+                    t!(
+                        "❌ revert: {reason}",
+                        reason = self.book.decode_revert(data)
+                    )
+                ),
+                None => return Err(e.into()),
+            },
+        }
+        Ok(())
+    }
+
+    async fn send(&self, args: &[String]) -> Result<()> {
+        if self.dry_run {
+            return self.dry(args).await;
+        }
+        let (spec, args) = args
+            .split_first()
+            .ok_or_else(|| eyre::eyre!(t!("❌ Uso: send <contrato>.<función> [args...]")))?;
+        let (function, call) = self.contract_call(spec, args, self.current_signer()?)?;
+        let pending = match call.send().await {
+            Ok(pending) => pending,
+            Err(e) => {
+                return match e.as_revert() {
+                    Some(data) => {
+                        println!(
+                            "{}",
+                            t!(
+                                "❌ revert: {reason}",
+                                reason = self.book.decode_revert(data)
+                            )
+                        );
+                        Ok(())
+                    }
+                    None => Err(e.into()),
+                }
+            }
+        };
+        println!(
+            "{}",
+            t!(
+                "⏳ {function} enviada (TX {tx})",
+                function = function.name,
+                tx = ?pending.tx_hash()
+            )
+        );
+        let receipt = pending
+            .await?
+            .ok_or_else(|| eyre::eyre!(t!("❌ La transacción no se confirmó")))?;
+        let status = if receipt.status == Some(U64::one()) {
+            "✅".to_string()
+        } else {
+            t!("❌ revertida")
+        };
+        println!(
+            "{}",
+            t!(
+                "{status} bloque {block} gas {gas}",
+                status = status,
+                block = receipt.block_number.unwrap_or_default(),
+                gas = receipt.gas_used.unwrap_or_default()
+            )
+        );
+        for log in &receipt.logs {
+            println!("  {}", self.decode_log(log));
+        }
+        Ok(())
+    }
+
+    async fn dry(&self, args: &[String]) -> Result<()> {
+        let (spec, args) = args
+            .split_first()
+            .ok_or_else(|| eyre::eyre!(t!("❌ Uso: dry <contrato>.<función> [args...]")))?;
+        let (_, call) = self.contract_call(spec, args, self.current_signer()?)?;
+        print_dry_run(&dry_run(&call, &self.book).await);
+        Ok(())
+    }
+
+    async fn logs(&self, args: &[String]) -> Result<()> {
+        let (name, from_block) = match args {
+            [name] => (name, 0u64),
+            [name, from_block] => (name, from_block.parse()?),
+            _ => return Err(eyre::eyre!(t!("❌ Uso: logs <contrato> [desde_bloque]"))),
+        };
+        let address = self.resolve(name)?;
+        let filter = Filter::new().address(address).from_block(from_block);
+        let logs = self.env.provider()?.get_logs(&filter).await?;
+        for log in &logs {
+            println!(
+                "  #{} {}",
+                log.block_number.unwrap_or_default(),
+                self.decode_log(log)
+            );
+        }
+        println!("{}", t!("{count} eventos", count = logs.len()));
+        Ok(())
+    }
+
+    fn decode_command(&self, args: &[String]) -> Result<()> {
+        let [hex] = args else {
+            return Err(eyre::eyre!(t!("❌ Uso: decode <hex>")));
+        };
+        let data = ethers::utils::hex::decode(hex.trim_start_matches("0x"))?;
+        println!("{}", self.decode(&data));
+        Ok(())
+    }
+}
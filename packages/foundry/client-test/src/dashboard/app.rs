@@ -0,0 +1,70 @@
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ethers::prelude::*;
+use eyre::Result;
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::io::{stdout, Stdout};
+use std::time::{Duration, Instant};
+
+use crate::dashboard::{draw, DashboardState};
+use crate::Env;
+
+type Term = Terminal<CrosstermBackend<Stdout>>;
+
+/// Abre el dashboard a pantalla completa hasta que se pulse `q`, Esc o Ctrl+C.
+/// Los fallos del refresco se muestran en pantalla; la terminal se restaura
+/// también si falla el dibujado.
+pub async fn run_dashboard(
+    env: &Env,
+    loops: &[Address],
+    interval: Duration,
+    from_block: Option<u64>,
+) -> Result<()> {
+    let mut state = DashboardState::new(env, loops, from_block).await?;
+    state.refresh(env).await;
+
+    enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let result = event_loop(env, &mut terminal, &mut state, interval).await;
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+async fn event_loop(
+    env: &Env,
+    terminal: &mut Term,
+    state: &mut DashboardState,
+    interval: Duration,
+) -> Result<()> {
+    let mut last_refresh = Instant::now();
+    loop {
+        terminal.draw(|frame| draw(frame, state))?;
+
+        // Espera teclas hasta el próximo refresco sin bloquear el runtime
+        let timeout = interval.saturating_sub(last_refresh.elapsed());
+        if tokio::task::block_in_place(|| event::poll(timeout))? {
+            if let Event::Key(key) = event::read()? {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.kind == KeyEventKind::Press
+                    && (ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc))
+                {
+                    return Ok(());
+                }
+            }
+        }
+
+        if last_refresh.elapsed() >= interval {
+            state.refresh(env).await;
+            last_refresh = Instant::now();
+        }
+    }
+}
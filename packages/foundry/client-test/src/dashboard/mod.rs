@@ -0,0 +1,6 @@
+pub mod state;
+pub use state::*;
+pub mod render;
+pub use render::*;
+pub mod app;
+pub use app::*;
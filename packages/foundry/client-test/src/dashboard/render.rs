@@ -0,0 +1,117 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph};
+use ratatui::Frame;
+
+use crate::dashboard::{DashboardState, LoopPanel};
+use crate::t;
+
+/// Alto de cada panel de loop: bordes, cuatro líneas de datos y la barra del periodo.
+const PANEL_HEIGHT: u16 = 7;
+
+pub fn draw(frame: &mut Frame, state: &DashboardState) {
+    let mut constraints = vec![Constraint::Length(1), Constraint::Length(1)];
+    constraints.extend(
+        state
+            .panels
+            .iter()
+            .map(|_| Constraint::Length(PANEL_HEIGHT)),
+    );
+    constraints.push(Constraint::Min(3));
+    let areas = Layout::vertical(constraints).split(frame.size());
+
+    frame.render_widget(
+        Paragraph::new(t!(
+            " Gyralis · bloque {block} · {count} loops · q para salir",
+            block = state.block,
+            count = state.panels.len()
+        ))
+        .bold(),
+        areas[0],
+    );
+    let status = match &state.status {
+        Some(error) => Line::styled(
+            t!(" ❌ Refresco fallido, se reintenta: {error}", error = error),
+            Style::default().fg(Color::Red),
+        ),
+        None => Line::from(""),
+    };
+    frame.render_widget(Paragraph::new(status), areas[1]);
+    for (panel, area) in state.panels.iter().zip(areas.iter().skip(2)) {
+        draw_panel(frame, panel, *area);
+    }
+
+    let feed = areas[areas.len() - 1];
+    // Sólo cabe la cola del feed: los eventos más recientes abajo
+    let visible = feed.height.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = state
+        .feed
+        .iter()
+        .skip(state.feed.len().saturating_sub(visible))
+        .map(|line| ListItem::new(line.as_str()))
+        .collect();
+    frame.render_widget(
+        List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t!(" Eventos ")),
+        ),
+        feed,
+    );
+}
+
+fn draw_panel(frame: &mut Frame, panel: &LoopPanel, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Loop {:?} ", panel.address));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [info, progress] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(inner)[..]
+    else {
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(t!(
+            "Periodo {period}  ·  siguiente en {countdown}",
+            period = panel.period,
+            countdown = countdown(panel.next_period_in)
+        )),
+        Line::from(t!(
+            "Registrados {registered}  ·  maxPayout {max_payout}",
+            registered = panel.registered,
+            max_payout = panel.amount(panel.max_payout)
+        )),
+        Line::from(t!(
+            "Saldo {balance}  ·  {percent}% por periodo",
+            balance = panel.amount(panel.balance),
+            percent = panel.details.percent_per_period
+        )),
+    ];
+    if let Some(error) = &panel.error {
+        lines.push(Line::styled(
+            format!("❌ {}", error),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    frame.render_widget(Paragraph::new(lines), info);
+    frame.render_widget(
+        Gauge::default()
+            .gauge_style(Style::default().fg(Color::Cyan))
+            .ratio(panel.period_progress())
+            .label(countdown(panel.next_period_in)),
+        progress,
+    );
+}
+
+/// `1h 02m 05s`, omitiendo las unidades que valen cero por delante.
+fn countdown(seconds: u64) -> String {
+    let (h, m, s) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    match (h, m) {
+        (0, 0) => format!("{}s", s),
+        (0, _) => format!("{}m {:02}s", m, s),
+        _ => format!("{}h {:02}m {:02}s", h, m, s),
+    }
+}
@@ -0,0 +1,223 @@
+use ethers::prelude::*;
+use eyre::Result;
+use std::collections::VecDeque;
+
+use crate::batch::{BatchReader, PeriodData};
+use crate::error::ClientResult;
+use crate::events::{loop_history_range, LoopEvent};
+use crate::functions::{loop_details, token_info, LoopDetails, TokenInfo};
+use crate::t;
+use crate::telemetry;
+use crate::Env;
+
+/// Líneas que se guardan en el feed de eventos.
+pub const FEED_CAPACITY: usize = 200;
+
+/// Estado de un loop en el dashboard.
+#[derive(Debug, Clone)]
+pub struct LoopPanel {
+    pub address: Address,
+    pub details: LoopDetails,
+    pub token: TokenInfo,
+    pub period: U256,
+    /// Segundos que faltan para el siguiente periodo, según el último bloque.
+    pub next_period_in: u64,
+    pub registered: U256,
+    pub max_payout: U256,
+    pub balance: U256,
+    /// Último error al refrescar; el resto de valores son los de la última lectura buena.
+    pub error: Option<String>,
+}
+
+impl LoopPanel {
+    async fn load(env: &Env, address: Address) -> Result<Self> {
+        let details = loop_details(env, address).await?;
+        let token = token_info(env, details.token).await?;
+        Ok(Self {
+            address,
+            details,
+            token,
+            period: U256::zero(),
+            next_period_in: 0,
+            registered: U256::zero(),
+            max_payout: U256::zero(),
+            balance: U256::zero(),
+            error: None,
+        })
+    }
+
+    /// Aplica lo leído en el lote del refresco.
+    fn update(
+        &mut self,
+        now: U256,
+        period_data: ClientResult<PeriodData>,
+        balance: ClientResult<U256>,
+    ) -> Result<()> {
+        let period_data = period_data?;
+        let balance = balance?;
+        self.period = self.details.period_at(now);
+        self.next_period_in = self
+            .details
+            .period_start(self.period + 1)
+            .saturating_sub(now)
+            .as_u64();
+        self.registered = period_data.total_registered_users;
+        self.max_payout = period_data.max_payout;
+        self.balance = balance;
+        Ok(())
+    }
+
+    /// Fracción transcurrida del periodo en curso, para la barra de progreso.
+    pub fn period_progress(&self) -> f64 {
+        let length = self.details.period_length.as_u64();
+        if length == 0 {
+            return 0.0;
+        }
+        1.0 - (self.next_period_in.min(length) as f64 / length as f64)
+    }
+
+    /// Cantidad del token del loop con sus decimales.
+    pub fn amount(&self, value: U256) -> String {
+        self.token.display(value)
+    }
+}
+
+/// Todo lo que muestra el dashboard. Se refresca por sondeo.
+pub struct DashboardState {
+    pub panels: Vec<LoopPanel>,
+    pub feed: VecDeque<String>,
+    pub block: u64,
+    /// Último error del refresco (RPC caído, lote fallido...). Se muestra en
+    /// la cabecera y se borra en cuanto un refresco sale bien.
+    pub status: Option<String>,
+    next_block: u64,
+    /// Lee el periodo y el balance de todos los loops en un par de viajes.
+    reader: BatchReader,
+}
+
+impl DashboardState {
+    /// Carga los loops. Sin `from_block` el feed empieza en el bloque actual.
+    pub async fn new(env: &Env, loops: &[Address], from_block: Option<u64>) -> Result<Self> {
+        let mut panels = Vec::with_capacity(loops.len());
+        for address in loops {
+            panels.push(LoopPanel::load(env, *address).await?);
+        }
+        let block = env.provider()?.get_block_number().await?.as_u64();
+        Ok(Self {
+            panels,
+            feed: VecDeque::with_capacity(FEED_CAPACITY),
+            block,
+            status: None,
+            next_block: from_block.unwrap_or(block),
+            reader: BatchReader::connect(env).await?,
+        })
+    }
+
+    /// Relee los loops y añade al feed los eventos de los bloques nuevos. Los
+    /// fallos de un loop se muestran en su panel y los del refresco entero en
+    /// `status`, sin parar el dashboard: el siguiente sondeo lo reintenta.
+    pub async fn refresh(&mut self, env: &Env) {
+        self.status = self.try_refresh(env).await.err().map(|e| e.to_string());
+    }
+
+    async fn try_refresh(&mut self, env: &Env) -> Result<()> {
+        let provider = env.provider()?;
+        let latest = provider
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| eyre::eyre!(t!("❌ No se pudo leer el último bloque")))?;
+        let now = latest.timestamp;
+        self.block = latest.number.unwrap_or_default().as_u64();
+
+        let loops: Vec<Address> = self.panels.iter().map(|panel| panel.address).collect();
+        let holdings: Vec<(Address, Address)> = self
+            .panels
+            .iter()
+            .map(|panel| (panel.details.token, panel.address))
+            .collect();
+        let period_data = self.reader.current_period_data(&loops).await?;
+        let balances = self.reader.token_balances(&holdings).await?;
+        for ((panel, period_data), balance) in self.panels.iter_mut().zip(period_data).zip(balances)
+        {
+            panel.error = panel
+                .update(now, period_data, balance)
+                .err()
+                .map(|e| e.to_string());
+        }
+
+        telemetry::indexer_lag(
+            "dashboard",
+            (self.block + 1).saturating_sub(self.next_block),
+        );
+        if self.block < self.next_block {
+            return Ok(());
+        }
+        // Si falla algún loop no se añade nada: el rango se vuelve a pedir entero
+        let mut events = Vec::new();
+        for panel in &self.panels {
+            for event in
+                loop_history_range(env, panel.address, self.next_block, Some(self.block)).await?
+            {
+                events.push((panel, event));
+            }
+        }
+        events.sort_by_key(|(_, event)| event_block(event));
+        let lines: Vec<String> = events
+            .iter()
+            .map(|(panel, event)| format_event(panel, event))
+            .collect();
+        for line in lines {
+            if self.feed.len() == FEED_CAPACITY {
+                self.feed.pop_front();
+            }
+            self.feed.push_back(line);
+        }
+        self.next_block = self.block + 1;
+        telemetry::indexer_lag("dashboard", 0);
+        Ok(())
+    }
+}
+
+fn event_block(event: &LoopEvent) -> u64 {
+    let block = match event {
+        LoopEvent::Register { block, .. }
+        | LoopEvent::Claim { block, .. }
+        | LoopEvent::Withdraw { block, .. } => block,
+    };
+    block.unwrap_or_default().as_u64()
+}
+
+fn format_event(panel: &LoopPanel, event: &LoopEvent) -> String {
+    let block = event_block(event);
+    match event {
+        LoopEvent::Register { event, .. } => format!(
+            "#{:<8} {} {}",
+            block,
+            panel.address,
+            t!(
+                "Register {user} para el periodo {period}",
+                user = ?event.sender,
+                period = event.period_number
+            )
+        ),
+        LoopEvent::Claim { event, .. } => format!(
+            "#{:<8} {} {}",
+            block,
+            panel.address,
+            t!(
+                "Claim    {user} periodo {period} payout {payout}",
+                user = ?event.claimer,
+                period = event.period_number,
+                payout = panel.amount(event.payout)
+            )
+        ),
+        LoopEvent::Withdraw { event, .. } => format!(
+            "#{:<8} {} Withdraw {:?} -> {:?} {}",
+            block,
+            panel.address,
+            event.admin,
+            event.to,
+            panel.amount(event.amount)
+        ),
+    }
+}
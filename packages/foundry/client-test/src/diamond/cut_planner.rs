@@ -0,0 +1,394 @@
+use ethers::abi::{AbiType, ParamType};
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use eyre::Result;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::storage::{namespace_slot, read_address};
+use crate::t;
+use crate::utils::{
+    facet_selectors, format_selector, load_abi, load_deployed_bytecode, SignerClient, CONTRACTS_DIR,
+};
+use crate::Env;
+
+/// Espejo de `IDiamond.FacetCutAction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EthAbiType)]
+pub enum FacetCutAction {
+    Add,
+    Replace,
+    Remove,
+}
+
+// Los enums de Solidity viajan como `uint8`
+impl AbiType for FacetCutAction {
+    fn param_type() -> ParamType {
+        ParamType::Uint(8)
+    }
+}
+
+/// Espejo de `IDiamond.FacetCut`, listo para pasar a `diamondCut`.
+#[derive(Debug, Clone, PartialEq, Eq, EthAbiType)]
+pub struct FacetCut {
+    pub facet: Address,
+    pub action: FacetCutAction,
+    pub selectors: Vec<[u8; 4]>,
+}
+
+/// Faceta nueva a partir de un artifact de forge y la dirección donde está desplegada.
+#[derive(Debug, Clone)]
+pub struct NewFacet {
+    pub name: String,
+    pub address: Address,
+    pub selectors: Vec<[u8; 4]>,
+}
+
+impl NewFacet {
+    pub fn from_artifact(name: &str, address: Address) -> Result<Self> {
+        let abi = load_abi(name)?;
+        Ok(Self {
+            name: name.to_string(),
+            address,
+            selectors: facet_selectors(&abi),
+        })
+    }
+
+    /// Parsea `NOMBRE=0xdireccion`, el formato de `--facet` en la CLI.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (name, address) = spec.split_once('=').ok_or_else(|| {
+            eyre::eyre!(t!(
+                "❌ Formato esperado NOMBRE=DIRECCION: {spec}",
+                spec = spec
+            ))
+        })?;
+        Self::from_artifact(name.trim(), address.trim().parse()?)
+    }
+}
+
+/// Estado actual del diamond según el loupe: a qué faceta va cada selector.
+/// Los selectores enrutados al propio `diamond` son inmutables.
+#[derive(Debug, Clone, Default)]
+pub struct DiamondState {
+    pub diamond: Address,
+    pub routes: HashMap<[u8; 4], Address>,
+}
+
+impl DiamondState {
+    pub fn facets(&self) -> BTreeSet<Address> {
+        self.routes.values().copied().collect()
+    }
+
+    pub fn selectors_of(&self, facet: Address) -> Vec<[u8; 4]> {
+        let mut selectors: Vec<[u8; 4]> = self
+            .routes
+            .iter()
+            .filter(|(_, f)| **f == facet)
+            .map(|(s, _)| *s)
+            .collect();
+        selectors.sort();
+        selectors
+    }
+}
+
+/// Cambio en los namespaces de storage (`keccak256("diamond.loop.storage")`, ...)
+/// que usa una faceta respecto de la que reemplaza.
+#[derive(Debug, Clone)]
+pub struct NamespaceChange {
+    pub facet: String,
+    pub old_facet: Address,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CutPlan {
+    pub diamond: Address,
+    pub cuts: Vec<FacetCut>,
+    pub namespace_changes: Vec<NamespaceChange>,
+}
+
+/// Lee el estado actual del diamond vía DiamondLoupe.
+pub async fn load_diamond_state(env: &Env, diamond: Address) -> Result<DiamondState> {
+    loupe_state(&env.contract_at(diamond, "DiamondLoupeFacet")?).await
+}
+
+/// Igual que [`load_diamond_state`] sobre un contrato con la ABI del loupe.
+pub async fn loupe_state<M: Middleware + 'static>(loupe: &Contract<M>) -> Result<DiamondState> {
+    let facets: Vec<Address> = loupe
+        .method::<(), Vec<Address>>("facetAddresses", ())?
+        .call()
+        .await?;
+
+    let mut state = DiamondState {
+        diamond: loupe.address(),
+        ..Default::default()
+    };
+    for facet in facets {
+        let selectors: Vec<[u8; 4]> = loupe
+            .method::<Address, Vec<[u8; 4]>>("facetFunctionSelectors", facet)?
+            .call()
+            .await?;
+        for selector in selectors {
+            state.routes.insert(selector, facet);
+        }
+    }
+    Ok(state)
+}
+
+/// Calcula los cortes Add/Replace/Remove necesarios para llevar el diamond a las
+/// facetas nuevas. Los selectores de una faceta reemplazada que ya no existen en
+/// ninguna faceta nueva se eliminan. Falla si hay que tocar un selector inmutable,
+/// igual que `diamondCut` con `DiamondCut_ImmutableFacet`.
+pub fn plan_cuts(state: &DiamondState, new_facets: &[NewFacet]) -> Result<Vec<FacetCut>> {
+    let mut cuts = Vec::new();
+    let mut provided: BTreeSet<[u8; 4]> = BTreeSet::new();
+    let mut replaced_facets: BTreeSet<Address> = BTreeSet::new();
+
+    for facet in new_facets {
+        let mut add = Vec::new();
+        let mut replace = Vec::new();
+        for selector in &facet.selectors {
+            if !provided.insert(*selector) {
+                // Ya lo aporta otra faceta nueva de la lista
+                continue;
+            }
+            match state.routes.get(selector) {
+                None => add.push(*selector),
+                Some(current) if *current == state.diamond => {
+                    return Err(eyre::eyre!(t!(
+                        "❌ {selector} es inmutable en el diamond {diamond}: no se puede reemplazar",
+                        selector = format_selector(*selector),
+                        diamond = ?state.diamond
+                    )));
+                }
+                Some(current) if *current != facet.address => {
+                    replaced_facets.insert(*current);
+                    replace.push(*selector);
+                }
+                Some(_) => {}
+            }
+        }
+        if !add.is_empty() {
+            cuts.push(FacetCut {
+                facet: facet.address,
+                action: FacetCutAction::Add,
+                selectors: add,
+            });
+        }
+        if !replace.is_empty() {
+            cuts.push(FacetCut {
+                facet: facet.address,
+                action: FacetCutAction::Replace,
+                selectors: replace,
+            });
+        }
+    }
+
+    for old in replaced_facets {
+        let remove: Vec<[u8; 4]> = state
+            .selectors_of(old)
+            .into_iter()
+            .filter(|s| !provided.contains(s))
+            .collect();
+        if !remove.is_empty() {
+            cuts.push(FacetCut {
+                facet: old,
+                action: FacetCutAction::Remove,
+                selectors: remove,
+            });
+        }
+    }
+
+    Ok(cuts)
+}
+
+/// Recorre las fuentes Solidity buscando `keccak256("...")` y devuelve `hash -> namespace`.
+pub fn storage_namespaces() -> Result<BTreeMap<[u8; 32], String>> {
+    let mut namespaces = BTreeMap::new();
+    collect_namespaces(Path::new(CONTRACTS_DIR), &mut namespaces)?;
+    Ok(namespaces)
+}
+
+fn collect_namespaces(dir: &Path, out: &mut BTreeMap<[u8; 32], String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_namespaces(&path, out)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some("sol") {
+            let source = fs::read_to_string(&path)?;
+            for chunk in source.split("keccak256(\"").skip(1) {
+                if let Some(end) = chunk.find("\")") {
+                    let name = &chunk[..end];
+                    out.insert(keccak256(name), name.to_string());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Namespaces cuyo slot aparece como constante dentro del bytecode.
+pub fn namespaces_in_code(
+    code: &[u8],
+    namespaces: &BTreeMap<[u8; 32], String>,
+) -> BTreeSet<String> {
+    namespaces
+        .iter()
+        .filter(|(hash, _)| code.windows(32).any(|w| w == hash.as_slice()))
+        .map(|(_, name)| name.clone())
+        .collect()
+}
+
+/// Compara los namespaces de storage de cada faceta nueva con los de las facetas
+/// a las que reemplaza selectores.
+pub async fn detect_namespace_changes(
+    env: &Env,
+    state: &DiamondState,
+    new_facets: &[NewFacet],
+) -> Result<Vec<NamespaceChange>> {
+    let mut with_code = Vec::with_capacity(new_facets.len());
+    for facet in new_facets {
+        with_code.push((facet.clone(), load_deployed_bytecode(&facet.name)?));
+    }
+    namespace_changes(&env.provider()?, state, &with_code, &storage_namespaces()?).await
+}
+
+/// Igual que [`detect_namespace_changes`] con el bytecode de cada faceta nueva
+/// ya cargado; el de las facetas actuales se pide al nodo.
+pub async fn namespace_changes<M: Middleware>(
+    provider: &M,
+    state: &DiamondState,
+    new_facets: &[(NewFacet, Vec<u8>)],
+    namespaces: &BTreeMap<[u8; 32], String>,
+) -> Result<Vec<NamespaceChange>>
+where
+    M::Error: 'static,
+{
+    let mut changes = Vec::new();
+
+    for (facet, code) in new_facets {
+        let new_ns = namespaces_in_code(code, namespaces);
+        let predecessors: BTreeSet<Address> = facet
+            .selectors
+            .iter()
+            .filter_map(|s| state.routes.get(s))
+            .filter(|old| **old != facet.address)
+            .copied()
+            .collect();
+
+        for old_facet in predecessors {
+            let old_code = provider.get_code(old_facet, None).await?;
+            let old_ns = namespaces_in_code(&old_code, namespaces);
+            let added: Vec<String> = new_ns.difference(&old_ns).cloned().collect();
+            let removed: Vec<String> = old_ns.difference(&new_ns).cloned().collect();
+            if !added.is_empty() || !removed.is_empty() {
+                changes.push(NamespaceChange {
+                    facet: facet.name.clone(),
+                    old_facet,
+                    added,
+                    removed,
+                });
+            }
+        }
+    }
+    Ok(changes)
+}
+
+pub async fn build_cut_plan(
+    env: &Env,
+    diamond: Address,
+    new_facets: &[NewFacet],
+) -> Result<CutPlan> {
+    let state = load_diamond_state(env, diamond).await?;
+    let cuts = plan_cuts(&state, new_facets)?;
+    let namespace_changes = detect_namespace_changes(env, &state, new_facets).await?;
+    Ok(CutPlan {
+        diamond,
+        cuts,
+        namespace_changes,
+    })
+}
+
+/// `systemAdmin` del diamond, leído directamente de `DiamondCutStorage`
+/// (slot base + 4: `facets` ocupa 2 slots, luego los dos mappings).
+pub async fn system_admin(env: &Env, diamond: Address) -> Result<Address> {
+    let slot = namespace_slot("diamond.cut.storage") + 4;
+    read_address(&env.provider()?, diamond, slot).await
+}
+
+/// Simula el `diamondCut` con `eth_call` desde `from`. Sirve igual contra un fork de anvil.
+pub async fn simulate_cut(env: &Env, plan: &CutPlan, from: Address) -> Result<()> {
+    let cut_facet = env.contract_at(plan.diamond, "DiamondCutFacet")?;
+    cut_facet
+        .method::<(Vec<FacetCut>, Address, Bytes), ()>(
+            "diamondCut",
+            (plan.cuts.clone(), Address::zero(), Bytes::new()),
+        )?
+        .from(from)
+        .call()
+        .await
+        .map_err(|e| {
+            eyre::eyre!(t!(
+                "❌ La simulación del diamondCut falló: {error}",
+                error = e
+            ))
+        })?;
+    Ok(())
+}
+
+/// `diamondCut` del plan firmado por el trusted signer.
+pub fn cut_call(env: &Env, plan: &CutPlan) -> Result<ContractCall<SignerClient, ()>> {
+    let signer = env.signer()?;
+    let cut_facet = env
+        .contract_at(plan.diamond, "DiamondCutFacet")?
+        .connect(signer);
+    Ok(cut_facet.method::<(Vec<FacetCut>, Address, Bytes), ()>(
+        "diamondCut",
+        (plan.cuts.clone(), Address::zero(), Bytes::new()),
+    )?)
+}
+
+/// Envía el `diamondCut` con el trusted signer y espera el receipt.
+pub async fn execute_cut(env: &Env, plan: &CutPlan) -> Result<H256> {
+    let call = cut_call(env, plan)?;
+    let pending = call.send().await?;
+    let tx_hash = pending.tx_hash();
+    pending
+        .await?
+        .ok_or_else(|| eyre::eyre!(t!("❌ La transacción no se confirmó")))?;
+    Ok(tx_hash)
+}
+
+pub fn print_cut_plan(plan: &CutPlan) {
+    println!("Diamond {:?}", plan.diamond);
+    if plan.cuts.is_empty() {
+        println!(
+            "{}",
+            t!("Sin cambios: el diamond ya coincide con las facetas indicadas")
+        );
+    }
+    for cut in &plan.cuts {
+        println!(
+            "{:?} {:?} {}",
+            cut.action,
+            cut.facet,
+            t!("({count} selectores)", count = cut.selectors.len())
+        );
+        for selector in &cut.selectors {
+            println!("    {}", format_selector(*selector));
+        }
+    }
+    for change in &plan.namespace_changes {
+        println!(
+            "{}",
+            t!(
+                "⚠️  {facet} cambia el storage respecto de {old_facet}: +{added} -{removed}",
+                facet = change.facet,
+                old_facet = ?change.old_facet,
+                added = ?change.added,
+                removed = ?change.removed
+            )
+        );
+    }
+}
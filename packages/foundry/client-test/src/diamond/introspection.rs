@@ -0,0 +1,253 @@
+use ethers::prelude::*;
+use eyre::Result;
+use std::collections::HashMap;
+
+use crate::t;
+use crate::utils::{format_selector, SelectorBook};
+use crate::Env;
+
+/// Problemas detectados para un selector del diamond.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectorFlag {
+    /// El selector no aparece en ninguna ABI local.
+    Unknown,
+    /// La faceta que lo sirve no está registrada en la FacetRegistry.
+    MissingFacet,
+    /// La FacetRegistry asigna el selector a otra faceta (o a ninguna).
+    RegistryMismatch(Option<Address>),
+    /// Varias facetas/firmas reclaman el mismo selector.
+    Collision,
+}
+
+impl std::fmt::Display for SelectorFlag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectorFlag::Unknown => write!(f, "UNKNOWN"),
+            SelectorFlag::MissingFacet => write!(f, "MISSING_FACET"),
+            SelectorFlag::RegistryMismatch(Some(other)) => write!(f, "REGISTRY->{:?}", other),
+            SelectorFlag::RegistryMismatch(None) => write!(f, "NOT_IN_REGISTRY"),
+            SelectorFlag::Collision => write!(f, "COLLISION"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectorRow {
+    pub facet: Address,
+    pub selector: [u8; 4],
+    pub signature: Option<String>,
+    pub flags: Vec<SelectorFlag>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FacetRow {
+    pub address: Address,
+    /// Nombre según el JSON de deployment, si existe.
+    pub name: Option<String>,
+    pub selector_count: usize,
+    pub registered: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiamondReport {
+    pub diamond: Address,
+    pub facets: Vec<FacetRow>,
+    pub selectors: Vec<SelectorRow>,
+}
+
+impl DiamondReport {
+    pub fn flagged(&self) -> impl Iterator<Item = &SelectorRow> {
+        self.selectors.iter().filter(|row| !row.flags.is_empty())
+    }
+}
+
+/// Consulta el DiamondLoupe de `diamond`, resuelve sus selectores con las ABIs
+/// locales y lo compara con la FacetRegistry del deployment.
+pub async fn inspect_diamond(env: &Env, diamond: Address) -> Result<DiamondReport> {
+    let loupe = env.contract_at(diamond, "DiamondLoupeFacet")?;
+    let registry = env.contract_at(env.deployment_address("facet_registry")?, "FacetRegistry")?;
+    let book = SelectorBook::from_artifacts()?;
+
+    let facet_addresses: Vec<Address> = loupe
+        .method::<(), Vec<Address>>("facetAddresses", ())?
+        .call()
+        .await?;
+    let registered: Vec<Address> = registry
+        .method::<(), Vec<Address>>("facetAddresses", ())?
+        .call()
+        .await?;
+
+    let mut facets = Vec::new();
+    let mut selectors = Vec::new();
+    let mut seen: HashMap<[u8; 4], usize> = HashMap::new();
+
+    for facet in facet_addresses {
+        let facet_selectors: Vec<[u8; 4]> = loupe
+            .method::<Address, Vec<[u8; 4]>>("facetFunctionSelectors", facet)?
+            .call()
+            .await?;
+        let is_registered = registered.contains(&facet);
+
+        facets.push(FacetRow {
+            address: facet,
+            name: deployment_name(env, facet),
+            selector_count: facet_selectors.len(),
+            registered: is_registered,
+        });
+
+        for selector in facet_selectors {
+            *seen.entry(selector).or_default() += 1;
+
+            let mut flags = Vec::new();
+            let signature = book.resolve(selector).map(str::to_string);
+            if book.signatures(selector).is_empty() {
+                flags.push(SelectorFlag::Unknown);
+            }
+            if book.is_collision(selector) {
+                flags.push(SelectorFlag::Collision);
+            }
+            if !is_registered {
+                flags.push(SelectorFlag::MissingFacet);
+            }
+
+            // El loupe debe enrutar el selector a la faceta que lo declara
+            let routed: Address = loupe
+                .method::<[u8; 4], Address>("facetAddress", selector)?
+                .call()
+                .await?;
+            if routed != facet && !flags.contains(&SelectorFlag::Collision) {
+                flags.push(SelectorFlag::Collision);
+            }
+
+            // `getFacetBySelector` revierte si no lo encuentra
+            let in_registry = registry
+                .method::<[u8; 4], Address>("getFacetBySelector", selector)?
+                .call()
+                .await
+                .ok();
+            if is_registered && in_registry != Some(facet) {
+                flags.push(SelectorFlag::RegistryMismatch(in_registry));
+            }
+
+            selectors.push(SelectorRow {
+                facet,
+                selector,
+                signature,
+                flags,
+            });
+        }
+    }
+
+    // Un mismo selector expuesto por dos facetas también es una colisión
+    for row in selectors.iter_mut() {
+        if seen.get(&row.selector).copied().unwrap_or_default() > 1
+            && !row.flags.contains(&SelectorFlag::Collision)
+        {
+            row.flags.push(SelectorFlag::Collision);
+        }
+    }
+
+    Ok(DiamondReport {
+        diamond,
+        facets,
+        selectors,
+    })
+}
+
+/// Busca el nombre de una faceta entre las claves del JSON de deployment.
+fn deployment_name(env: &Env, address: Address) -> Option<String> {
+    env.deployemt_data
+        .as_object()?
+        .iter()
+        .find_map(|(key, value)| {
+            let parsed: Address = value.as_str()?.parse().ok()?;
+            (parsed == address).then(|| key.clone())
+        })
+}
+
+/// Imprime el informe en forma de tabla.
+pub fn print_diamond_report(report: &DiamondReport) {
+    println!("Diamond {:?}", report.diamond);
+    println!();
+    println!(
+        "{:<44} {:<26} {:>10} {:>10}",
+        t!("FACETA"),
+        t!("NOMBRE"),
+        t!("SELECTORES"),
+        t!("REGISTRADA")
+    );
+    for facet in &report.facets {
+        println!(
+            "{:<44} {:<26} {:>10} {:>10}",
+            format!("{:?}", facet.address),
+            facet.name.as_deref().unwrap_or("-"),
+            facet.selector_count,
+            if facet.registered {
+                t!("sí")
+            } else {
+                t!("NO")
+            }
+        );
+    }
+    println!();
+    println!(
+        "{:<44} {:<10} {:<48} {}",
+        t!("FACETA"),
+        "SELECTOR",
+        t!("FIRMA"),
+        t!("AVISOS")
+    );
+    for row in &report.selectors {
+        let flags: Vec<String> = row.flags.iter().map(ToString::to_string).collect();
+        println!(
+            "{:<44} {:<10} {:<48} {}",
+            format!("{:?}", row.facet),
+            format_selector(row.selector),
+            row.signature.as_deref().unwrap_or("?"),
+            flags.join(",")
+        );
+    }
+    println!();
+    println!(
+        "{}",
+        t!(
+            "{facets} facetas, {selectors} selectores, {flagged} con advertencias",
+            facets = report.facets.len(),
+            selectors = report.selectors.len(),
+            flagged = report.flagged().count()
+        )
+    );
+}
+
+pub fn diamond_report_json(report: &DiamondReport) -> serde_json::Value {
+    let facets: Vec<serde_json::Value> = report
+        .facets
+        .iter()
+        .map(|facet| {
+            serde_json::json!({
+                "address": facet.address,
+                "name": facet.name,
+                "selectors": facet.selector_count,
+                "registered": facet.registered,
+            })
+        })
+        .collect();
+    let selectors: Vec<serde_json::Value> = report
+        .selectors
+        .iter()
+        .map(|row| {
+            let flags: Vec<String> = row.flags.iter().map(ToString::to_string).collect();
+            serde_json::json!({
+                "facet": row.facet,
+                "selector": format_selector(row.selector),
+                "signature": row.signature,
+                "flags": flags,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "diamond": report.diamond,
+        "facets": facets,
+        "selectors": selectors,
+    })
+}
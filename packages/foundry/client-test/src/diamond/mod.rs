@@ -0,0 +1,8 @@
+pub mod introspection;
+pub use introspection::*;
+
+pub mod cut_planner;
+pub use cut_planner::*;
+
+pub mod registry;
+pub use registry::*;
@@ -0,0 +1,241 @@
+use ethers::abi::Detokenize;
+use ethers::contract::parse_log;
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use eyre::Result;
+
+use crate::error::ClientError;
+use crate::t;
+use crate::utils::{
+    facet_selectors, format_selector, load_abi, load_creation_code, RpcProvider, SignerClient,
+};
+use crate::Env;
+
+/// `FacetRegistered(address indexed facet, bytes4[] selectors)`
+#[derive(Debug, Clone, EthEvent)]
+#[ethevent(name = "FacetRegistered", abi = "FacetRegistered(address,bytes4[])")]
+pub struct FacetRegisteredFilter {
+    #[ethevent(indexed)]
+    pub facet: Address,
+    pub selectors: Vec<[u8; 4]>,
+}
+
+/// `FacetUnregistered(address indexed facet)`
+#[derive(Debug, Clone, EthEvent)]
+#[ethevent(name = "FacetUnregistered", abi = "FacetUnregistered(address)")]
+pub struct FacetUnregisteredFilter {
+    #[ethevent(indexed)]
+    pub facet: Address,
+}
+
+/// Evento histórico de la FacetRegistry.
+#[derive(Debug, Clone)]
+pub enum RegistryEvent {
+    Registered {
+        block: Option<U64>,
+        tx_hash: Option<H256>,
+        facet: Address,
+        selectors: Vec<[u8; 4]>,
+    },
+    Unregistered {
+        block: Option<U64>,
+        tx_hash: Option<H256>,
+        facet: Address,
+    },
+}
+
+/// Interpreta `--salt`: un `bytes32` en hex o, si no lo es, `keccak256(texto)`.
+pub fn parse_salt(salt: &str) -> H256 {
+    match salt.parse::<H256>() {
+        Ok(hash) => hash,
+        Err(_) => H256::from(keccak256(salt)),
+    }
+}
+
+fn registry(env: &Env) -> Result<Contract<RpcProvider>> {
+    Ok(env.contract_at(env.deployment_address("facet_registry")?, "FacetRegistry")?)
+}
+
+/// Facetas registradas junto con sus selectores.
+pub async fn registered_facets(env: &Env) -> Result<Vec<(Address, Vec<[u8; 4]>)>> {
+    let registry = registry(env)?;
+    let facets: Vec<Address> = registry
+        .method::<(), Vec<Address>>("facetAddresses", ())?
+        .call()
+        .await?;
+
+    let mut out = Vec::with_capacity(facets.len());
+    for facet in facets {
+        let selectors: Vec<[u8; 4]> = registry
+            .method::<Address, Vec<[u8; 4]>>("facetSelectors", facet)?
+            .call()
+            .await?;
+        out.push((facet, selectors));
+    }
+    Ok(out)
+}
+
+pub fn register_facet_call(
+    env: &Env,
+    artifact: &str,
+    facet: Address,
+) -> Result<ContractCall<SignerClient, ()>> {
+    let selectors = facet_selectors(&load_abi(artifact)?);
+    let registry = registry(env)?.connect(env.signer()?);
+    Ok(registry.method::<(Address, Vec<[u8; 4]>), ()>("addFacet", (facet, selectors))?)
+}
+
+/// Registra una faceta ya desplegada con los selectores derivados de su artifact.
+pub async fn register_facet(env: &Env, artifact: &str, facet: Address) -> Result<H256> {
+    let receipt = send_confirmed(register_facet_call(env, artifact, facet)?).await?;
+    Ok(receipt.transaction_hash)
+}
+
+pub fn unregister_facet_call(env: &Env, facet: Address) -> Result<ContractCall<SignerClient, ()>> {
+    let registry = registry(env)?.connect(env.signer()?);
+    Ok(registry.method::<Address, ()>("removeFacet", facet)?)
+}
+
+/// Da de baja una faceta de la registry.
+pub async fn unregister_facet(env: &Env, facet: Address) -> Result<H256> {
+    let receipt = send_confirmed(unregister_facet_call(env, facet)?).await?;
+    Ok(receipt.transaction_hash)
+}
+
+/// Envía `call` y espera su receipt: `NotConfirmed` si el nodo la descarta y
+/// `Revert` si se mina con `status` 0.
+async fn send_confirmed<R: Detokenize>(
+    call: ContractCall<SignerClient, R>,
+) -> Result<TransactionReceipt> {
+    let pending = call.send().await.map_err(ClientError::from)?;
+    let tx_hash = pending.tx_hash();
+    let receipt = pending
+        .await
+        .map_err(ClientError::from)?
+        .ok_or(ClientError::NotConfirmed(tx_hash))?;
+    if receipt.status != Some(U64::one()) {
+        return Err(ClientError::Revert {
+            reason: t!("la TX {tx} revirtió al minarse", tx = ?tx_hash),
+            data: Bytes::default(),
+            custom: None,
+        }
+        .into());
+    }
+    Ok(receipt)
+}
+
+/// Dirección CREATE2 que tendría la faceta desplegada con `salt`.
+pub async fn compute_facet_address(env: &Env, salt: H256, artifact: &str) -> Result<Address> {
+    let creation_code = Bytes::from(load_creation_code(artifact)?);
+    let facet: Address = registry(env)?
+        .method::<(H256, Bytes), Address>("computeFacetAddress", (salt, creation_code))?
+        .call()
+        .await?;
+    Ok(facet)
+}
+
+/// `deployFacet`; el valor de retorno es la dirección de la faceta.
+pub fn deploy_facet_call(
+    env: &Env,
+    salt: H256,
+    artifact: &str,
+) -> Result<ContractCall<SignerClient, Address>> {
+    let creation_code = Bytes::from(load_creation_code(artifact)?);
+    let selectors = facet_selectors(&load_abi(artifact)?);
+    let registry = registry(env)?.connect(env.signer()?);
+    Ok(registry.method::<(H256, Bytes, Vec<[u8; 4]>), Address>(
+        "deployFacet",
+        (salt, creation_code, selectors),
+    )?)
+}
+
+/// Despliega la faceta vía `deployFacet` (CREATE2 + registro) y comprueba que
+/// la dirección resultante coincide con la predicha.
+pub async fn deploy_facet(env: &Env, salt: H256, artifact: &str) -> Result<(H256, Address)> {
+    let predicted = compute_facet_address(env, salt, artifact).await?;
+    let receipt = send_confirmed(deploy_facet_call(env, salt, artifact)?).await?;
+    Ok((
+        receipt.transaction_hash,
+        deployed_facet(&receipt, predicted)?,
+    ))
+}
+
+/// Faceta registrada en el receipt de `deployFacet`; error si no es la predicha.
+pub fn deployed_facet(receipt: &TransactionReceipt, predicted: Address) -> Result<Address> {
+    let deployed = receipt
+        .logs
+        .iter()
+        .find_map(|log| parse_log::<FacetRegisteredFilter>(log.clone()).ok())
+        .map(|event| event.facet)
+        .ok_or_else(|| {
+            eyre::eyre!(t!(
+                "❌ No se encontró {event} en el receipt",
+                event = "FacetRegistered"
+            ))
+        })?;
+    if deployed != predicted {
+        return Err(eyre::eyre!(t!(
+            "❌ Faceta desplegada en {deployed}, se esperaba {predicted}",
+            deployed = ?deployed,
+            predicted = ?predicted
+        )));
+    }
+    Ok(deployed)
+}
+
+/// Historial de altas y bajas de la registry desde `from_block`.
+pub async fn registry_history(env: &Env, from_block: u64) -> Result<Vec<RegistryEvent>> {
+    let filter = Filter::new()
+        .address(env.deployment_address("facet_registry")?)
+        .from_block(from_block)
+        .topic0(vec![
+            FacetRegisteredFilter::signature(),
+            FacetUnregisteredFilter::signature(),
+        ]);
+    let logs = env.provider()?.get_logs(&filter).await?;
+
+    let mut events = Vec::with_capacity(logs.len());
+    for log in logs {
+        let (block, tx_hash) = (log.block_number, log.transaction_hash);
+        if let Ok(event) = parse_log::<FacetRegisteredFilter>(log.clone()) {
+            events.push(RegistryEvent::Registered {
+                block,
+                tx_hash,
+                facet: event.facet,
+                selectors: event.selectors,
+            });
+        } else if let Ok(event) = parse_log::<FacetUnregisteredFilter>(log) {
+            events.push(RegistryEvent::Unregistered {
+                block,
+                tx_hash,
+                facet: event.facet,
+            });
+        }
+    }
+    Ok(events)
+}
+
+pub fn print_registry_history(events: &[RegistryEvent]) {
+    for event in events {
+        match event {
+            RegistryEvent::Registered {
+                block,
+                facet,
+                selectors,
+                ..
+            } => {
+                let selectors: Vec<String> =
+                    selectors.iter().map(|s| format_selector(*s)).collect();
+                println!(
+                    "#{:<8} REGISTERED   {:?} [{}]",
+                    block.unwrap_or_default(),
+                    facet,
+                    selectors.join(", ")
+                );
+            }
+            RegistryEvent::Unregistered { block, facet, .. } => {
+                println!("#{:<8} UNREGISTERED {:?}", block.unwrap_or_default(), facet);
+            }
+        }
+    }
+}
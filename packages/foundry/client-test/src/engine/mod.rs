@@ -0,0 +1,4 @@
+pub mod nonce;
+pub mod sender;
+pub use nonce::*;
+pub use sender::*;
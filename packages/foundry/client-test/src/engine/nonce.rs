@@ -0,0 +1,78 @@
+use ethers::prelude::*;
+use eyre::Result;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Default)]
+struct SignerNonces {
+    next: U256,
+    /// Nonces reservados que no llegaron a la mempool; se reutilizan primero
+    /// para no dejar huecos que bloqueen las TX siguientes.
+    released: BTreeSet<U256>,
+}
+
+/// Nonces llevados en local por signer, para no pedir `eth_getTransactionCount`
+/// en cada envío (que es lo que hace `SignerMiddleware` y rompe al paralelizar).
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    signers: Mutex<HashMap<Address, SignerNonces>>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserva el siguiente nonce de `address`. La primera vez se inicializa
+    /// con el contador `pending` del nodo.
+    pub async fn next<M: Middleware>(&self, provider: &M, address: Address) -> Result<U256>
+    where
+        M::Error: 'static,
+    {
+        let mut signers = self.signers.lock().await;
+        let nonces = match signers.entry(address) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(SignerNonces {
+                next: pending_nonce(provider, address).await?,
+                ..Default::default()
+            }),
+        };
+        if let Some(nonce) = nonces.released.pop_first() {
+            return Ok(nonce);
+        }
+        let nonce = nonces.next;
+        nonces.next += U256::one();
+        Ok(nonce)
+    }
+
+    /// Devuelve un nonce que no se llegó a usar.
+    pub async fn release(&self, address: Address, nonce: U256) {
+        if let Some(nonces) = self.signers.lock().await.get_mut(&address) {
+            nonces.released.insert(nonce);
+        }
+    }
+
+    /// Alinea el contador con el nodo cuando éste dice "nonce too low" (alguien
+    /// más ha enviado desde la misma cuenta).
+    pub async fn resync<M: Middleware>(&self, provider: &M, address: Address) -> Result<()>
+    where
+        M::Error: 'static,
+    {
+        let pending = pending_nonce(provider, address).await?;
+        let mut signers = self.signers.lock().await;
+        let nonces = signers.entry(address).or_default();
+        nonces.next = nonces.next.max(pending);
+        nonces.released.retain(|nonce| *nonce >= pending);
+        Ok(())
+    }
+}
+
+async fn pending_nonce<M: Middleware>(provider: &M, address: Address) -> Result<U256>
+where
+    M::Error: 'static,
+{
+    Ok(provider
+        .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+        .await?)
+}
@@ -0,0 +1,262 @@
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use eyre::Result;
+use futures::future::join_all;
+use std::collections::HashMap;
+use tokio::sync::Semaphore;
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{debug, instrument, warn, Span};
+
+use crate::engine::NonceManager;
+use crate::t;
+use crate::telemetry;
+use crate::transport::MeteredTransport;
+use crate::Env;
+
+/// Cuándo y cuánto subir el gas de una TX atascada, y cuántas veces reintentar.
+#[derive(Debug, Clone, Copy)]
+pub struct ResubmitPolicy {
+    /// Tiempo sin receipt tras el que la TX se considera atascada.
+    pub stuck_after: Duration,
+    /// Subida del gas price en cada reenvío, en porcentaje (los nodos piden >= 10).
+    pub bump_percent: u64,
+    /// Máximo de reenvíos con gas subido para el mismo nonce.
+    pub max_bumps: u32,
+    /// Máximo de reintentos cuando el nodo rechaza el envío (nonce o precio).
+    pub max_retries: u32,
+    /// Intervalo de consulta del receipt.
+    pub poll_interval: Duration,
+}
+
+impl Default for ResubmitPolicy {
+    fn default() -> Self {
+        Self {
+            stuck_after: Duration::from_secs(15),
+            bump_percent: 15,
+            max_bumps: 3,
+            max_retries: 3,
+            poll_interval: Duration::from_millis(250),
+        }
+    }
+}
+
+/// Resultado de una TX enviada por el motor.
+#[derive(Debug, Clone)]
+pub struct TxOutcome {
+    pub from: Address,
+    pub nonce: U256,
+    pub tx_hash: H256,
+    pub receipt: TransactionReceipt,
+    pub gas_price: U256,
+    pub bumps: u32,
+    pub retries: u32,
+    pub latency: Duration,
+}
+
+impl TxOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.receipt.status == Some(U64::one())
+    }
+}
+
+/// Motor de envío concurrente: nonces locales por signer, cola acotada por un
+/// semáforo y reenvío con más gas de las TX que no se minan.
+pub struct TxEngine<P = MeteredTransport<Http>> {
+    provider: Provider<P>,
+    chain_id: u64,
+    wallets: HashMap<Address, LocalWallet>,
+    nonces: NonceManager,
+    queue: Semaphore,
+    policy: ResubmitPolicy,
+}
+
+impl TxEngine {
+    pub fn new(env: &Env, concurrency: usize, policy: ResubmitPolicy) -> Result<Self> {
+        Ok(Self::with_provider(
+            env.provider()?,
+            env.chain_id,
+            concurrency,
+            policy,
+        ))
+    }
+}
+
+impl<P: JsonRpcClient> TxEngine<P> {
+    /// Motor sobre un provider cualquiera (p.ej. uno que reproduce fixtures).
+    pub fn with_provider(
+        provider: Provider<P>,
+        chain_id: u64,
+        concurrency: usize,
+        policy: ResubmitPolicy,
+    ) -> Self {
+        Self {
+            provider,
+            chain_id,
+            wallets: HashMap::new(),
+            nonces: NonceManager::new(),
+            queue: Semaphore::new(concurrency.max(1)),
+            policy,
+        }
+    }
+
+    /// Añade un signer al motor.
+    pub fn add_wallet(&mut self, wallet: LocalWallet) -> Address {
+        let wallet = wallet.with_chain_id(self.chain_id);
+        let address = wallet.address();
+        self.wallets.insert(address, wallet);
+        address
+    }
+
+    pub fn wallets(&self) -> impl Iterator<Item = &Address> {
+        self.wallets.keys()
+    }
+
+    pub fn provider(&self) -> &Provider<P> {
+        &self.provider
+    }
+
+    /// Firma y envía `tx` desde `from`, espera el receipt y reenvía con más gas
+    /// si se atasca. Ocupa un hueco de la cola mientras dura.
+    #[instrument(skip(self, tx), fields(nonce, tx_hash))]
+    pub async fn submit(&self, from: Address, mut tx: TypedTransaction) -> Result<TxOutcome> {
+        let wallet = self
+            .wallets
+            .get(&from)
+            .ok_or_else(|| eyre::eyre!(t!("❌ {from} no es un signer del motor", from = ?from)))?;
+        let _permit = self.queue.acquire().await?;
+        let started = Instant::now();
+
+        tx.set_from(from);
+        tx.set_chain_id(self.chain_id);
+        if tx.gas().is_none() {
+            let gas = self.provider.estimate_gas(&tx, None).await?;
+            tx.set_gas(gas);
+        }
+        let mut gas_price = self.provider.get_gas_price().await?;
+        let mut nonce = self.nonces.next(&self.provider, from).await?;
+        Span::current().record("nonce", tracing::field::display(nonce));
+        let mut retries = 0;
+        let mut bumps = 0;
+        // Hashes enviados para este nonce: puede minarse cualquiera de ellos
+        let mut sent: Vec<H256> = Vec::new();
+
+        loop {
+            tx.set_nonce(nonce);
+            tx.set_gas_price(gas_price);
+            let signature = match wallet.sign_transaction(&tx).await {
+                Ok(signature) => signature,
+                Err(e) => {
+                    self.release_unsent(from, nonce, &sent).await;
+                    return Err(e.into());
+                }
+            };
+            let raw = tx.rlp_signed(&signature);
+
+            match self.provider.send_raw_transaction(raw).await {
+                Ok(pending) => {
+                    debug!(tx_hash = ?pending.tx_hash(), %gas_price, "TX enviada");
+                    telemetry::tx_sent("engine");
+                    sent.push(pending.tx_hash())
+                }
+                Err(e) => {
+                    let message = e.to_string().to_lowercase();
+                    let retryable =
+                        message.contains("nonce too low") || message.contains("underpriced");
+                    if !retryable || retries >= self.policy.max_retries {
+                        self.release_unsent(from, nonce, &sent).await;
+                        telemetry::tx_rejected("engine", &message);
+                        return Err(eyre::eyre!(t!(
+                            "❌ Envío rechazado (nonce {nonce}, {retries} reintentos): {error}",
+                            nonce = nonce,
+                            retries = retries,
+                            error = e
+                        )));
+                    }
+                    retries += 1;
+                    warn!(error = %e, retries, "envío rechazado, se reintenta");
+                    if message.contains("underpriced") {
+                        gas_price = self.bump(gas_price);
+                        continue;
+                    }
+                    // "nonce too low": si ya habíamos enviado, es que se minó una
+                    // de nuestras versiones; si no, otro usó el nonce y pedimos otro
+                    if sent.is_empty() {
+                        self.nonces.resync(&self.provider, from).await?;
+                        nonce = self.nonces.next(&self.provider, from).await?;
+                        Span::current().record("nonce", tracing::field::display(nonce));
+                        continue;
+                    }
+                }
+            }
+
+            match self.wait(&sent).await? {
+                Some(receipt) => {
+                    Span::current()
+                        .record("tx_hash", tracing::field::debug(receipt.transaction_hash));
+                    debug!(status = ?receipt.status, bumps, retries, "TX minada");
+                    telemetry::tx_mined("engine", receipt.status == Some(U64::one()));
+                    return Ok(TxOutcome {
+                        from,
+                        nonce,
+                        tx_hash: receipt.transaction_hash,
+                        receipt,
+                        gas_price,
+                        bumps,
+                        retries,
+                        latency: started.elapsed(),
+                    });
+                }
+                None if bumps < self.policy.max_bumps => {
+                    bumps += 1;
+                    warn!(bumps, "TX atascada, se reenvía con más gas");
+                    gas_price = self.bump(gas_price);
+                }
+                None => {
+                    telemetry::tx_rejected("engine", "stuck");
+                    return Err(eyre::eyre!(t!(
+                        "❌ TX con nonce {nonce} de {from} sin minar tras {bumps} subidas de gas",
+                        nonce = nonce,
+                        from = ?from,
+                        bumps = bumps
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Envía todas las TX a la vez; la concurrencia real la limita la cola.
+    pub async fn submit_all(
+        &self,
+        txs: Vec<(Address, TypedTransaction)>,
+    ) -> Vec<Result<TxOutcome>> {
+        join_all(txs.into_iter().map(|(from, tx)| self.submit(from, tx))).await
+    }
+
+    /// Devuelve `nonce` al gestor si no llegó a enviarse ninguna TX con él;
+    /// si no, quedaría un hueco que atasca las siguientes TX de `from`.
+    async fn release_unsent(&self, from: Address, nonce: U256, sent: &[H256]) {
+        if sent.is_empty() {
+            self.nonces.release(from, nonce).await;
+        }
+    }
+
+    fn bump(&self, gas_price: U256) -> U256 {
+        let bumped = gas_price * (100 + self.policy.bump_percent) / 100;
+        bumped.max(gas_price + 1)
+    }
+
+    /// Espera el receipt de cualquiera de `sent` hasta `stuck_after`; `None`
+    /// si ninguno se ha minado.
+    async fn wait(&self, sent: &[H256]) -> Result<Option<TransactionReceipt>> {
+        let deadline = Instant::now() + self.policy.stuck_after;
+        while Instant::now() < deadline {
+            for tx_hash in sent {
+                if let Some(receipt) = self.provider.get_transaction_receipt(*tx_hash).await? {
+                    return Ok(Some(receipt));
+                }
+            }
+            sleep(self.policy.poll_interval).await;
+        }
+        Ok(None)
+    }
+}
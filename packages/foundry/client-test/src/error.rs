@@ -0,0 +1,167 @@
+use ethers::abi::EncodePackedError;
+use ethers::middleware::signer::SignerMiddlewareError;
+use ethers::prelude::*;
+use std::fmt;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+use crate::t;
+use crate::utils::{DecodedError, SelectorBook};
+
+/// Errores de la capa de cliente (`utils`, `functions`, `events`). La CLI los
+/// recibe convertidos a `eyre::Report`; quien use la librería puede distinguirlos.
+/// El `Display` sale en el idioma de `i18n::locale()`.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// Variables de entorno, deployment, artifacts o direcciones inválidas.
+    Config(String),
+    /// Fallo hablando con el nodo.
+    Transport(#[from] ProviderError),
+    /// Clave inválida o fallo del signer.
+    Signing(String),
+    /// La llamada revirtió. `reason` viene decodificado con las ABIs locales:
+    /// `Error(string)`, `Panic(uint256)` o un custom error como `AlreadyRegistered()`.
+    /// `custom` trae el custom error decodificado cuando las ABIs lo conocen.
+    Revert {
+        reason: String,
+        data: Bytes,
+        custom: Option<DecodedError>,
+    },
+    /// La TX se envió pero el nodo la descartó sin receipt.
+    NotConfirmed(H256),
+    /// Datos con un formato inesperado: retornos, logs o eventos que faltan.
+    Decode(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::Config(detail) => t!("❌ Configuración: {detail}", detail = detail),
+            Self::Transport(error) => t!("❌ Transporte: {detail}", detail = error),
+            Self::Signing(detail) => t!("❌ Firma: {detail}", detail = detail),
+            Self::Revert { reason, .. } => t!("❌ Revert: {reason}", reason = reason),
+            Self::NotConfirmed(tx_hash) => {
+                t!("❌ La transacción {tx} no se confirmó", tx = ?tx_hash)
+            }
+            Self::Decode(detail) => t!("❌ Decodificación: {detail}", detail = detail),
+        };
+        f.write_str(&message)
+    }
+}
+
+pub type ClientResult<T> = std::result::Result<T, ClientError>;
+
+impl ClientError {
+    pub fn config(message: impl Into<String>) -> Self {
+        Self::Config(message.into())
+    }
+
+    pub fn decode(message: impl Into<String>) -> Self {
+        Self::Decode(message.into())
+    }
+
+    /// Construye un `Revert` decodificando los datos con las ABIs locales.
+    pub fn revert(data: Bytes) -> Self {
+        Self::revert_with(selector_book(), data)
+    }
+
+    /// Como [`ClientError::revert`] pero con un índice de errores dado.
+    pub fn revert_with(book: &SelectorBook, data: Bytes) -> Self {
+        Self::Revert {
+            reason: book.decode_revert(&data),
+            custom: book.decode_custom_error(&data),
+            data,
+        }
+    }
+
+    /// Nombre del custom error si el revert es uno de los declarados en las ABIs.
+    pub fn custom_error(&self) -> Option<&str> {
+        match self {
+            Self::Revert {
+                custom: Some(error),
+                ..
+            } => Some(&error.name),
+            _ => None,
+        }
+    }
+
+    /// `true` si es un revert con el custom error `name`.
+    pub fn is_custom_error(&self, name: &str) -> bool {
+        self.custom_error() == Some(name)
+    }
+
+    /// Errores comunes de `ContractError`; el del middleware lo traduce `middleware`.
+    fn from_contract<M: Middleware>(
+        error: ContractError<M>,
+        middleware: impl FnOnce(M::Error) -> Self,
+    ) -> Self {
+        if let Some(data) = error.as_revert() {
+            return Self::revert(data.clone());
+        }
+        match error {
+            ContractError::MiddlewareError { e } => middleware(e),
+            ContractError::ProviderError { e } => Self::Transport(e),
+            ContractError::ContractNotDeployed => {
+                Self::config(t!("el contrato no está desplegado"))
+            }
+            other => Self::decode(other.to_string()),
+        }
+    }
+}
+
+/// Índice de errores de las ABIs locales, cargado una vez. Sin artifacts los
+/// reverts se muestran en hex.
+fn selector_book() -> &'static SelectorBook {
+    static BOOK: OnceLock<SelectorBook> = OnceLock::new();
+    BOOK.get_or_init(|| SelectorBook::from_artifacts().unwrap_or_default())
+}
+
+impl<P: JsonRpcClient + 'static> From<ContractError<Provider<P>>> for ClientError {
+    fn from(error: ContractError<Provider<P>>) -> Self {
+        Self::from_contract(error, Self::Transport)
+    }
+}
+
+impl<P: JsonRpcClient + 'static> From<ContractError<SignerMiddleware<Provider<P>, LocalWallet>>>
+    for ClientError
+{
+    fn from(error: ContractError<SignerMiddleware<Provider<P>, LocalWallet>>) -> Self {
+        Self::from_contract(error, Self::from)
+    }
+}
+
+impl<P: JsonRpcClient + 'static> From<SignerMiddlewareError<Provider<P>, LocalWallet>>
+    for ClientError
+{
+    fn from(error: SignerMiddlewareError<Provider<P>, LocalWallet>) -> Self {
+        match error {
+            SignerMiddlewareError::MiddlewareError(e) => Self::Transport(e),
+            SignerMiddlewareError::SignerError(e) => Self::Signing(e.to_string()),
+            other => Self::Signing(other.to_string()),
+        }
+    }
+}
+
+impl From<WalletError> for ClientError {
+    fn from(error: WalletError) -> Self {
+        Self::Signing(error.to_string())
+    }
+}
+
+impl From<ethers::abi::Error> for ClientError {
+    fn from(error: ethers::abi::Error) -> Self {
+        Self::decode(error.to_string())
+    }
+}
+
+impl From<EncodePackedError> for ClientError {
+    fn from(error: EncodePackedError) -> Self {
+        Self::decode(error.to_string())
+    }
+}
+
+impl From<AbiError> for ClientError {
+    fn from(error: AbiError) -> Self {
+        Self::decode(error.to_string())
+    }
+}
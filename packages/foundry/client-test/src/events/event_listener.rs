@@ -1,57 +1,58 @@
+use crate::error::{ClientError, ClientResult};
 use crate::get_provider;
+use crate::t;
 use crate::utils::RPC_URL;
-use ethers::providers::{Middleware, Provider, StreamExt, Ws};
-use ethers::types::{Address, BlockNumber, Filter, Log, H256};
-use eyre::Result;
+use ethers::providers::{Middleware, StreamExt};
+use ethers::types::{Address, Filter, Log, H256};
+use tracing::{debug, info, instrument};
 
 /// Escucha eventos en un contrato. Si `lookback` es `true`, busca en retrospectiva.
-pub async fn event_listener(add: Address, event_signature: H256, lookback: bool) -> Result<Log> {
+#[instrument(skip_all, fields(contract = ?add, event = ?event_signature, lookback))]
+pub async fn event_listener(
+    add: Address,
+    event_signature: H256,
+    lookback: bool,
+) -> ClientResult<Log> {
     let provider = get_provider(RPC_URL).await?;
     let filter = Filter::new().address(add);
 
     if lookback {
-        println!(" Buscando eventos pasados en el contrato {:?}...", add);
-
-        // Filtrar eventos desde los últimos 1000 bloques
-        // let filter = filter.from_block(BlockNumber::Latest.as_number - 1000);
-        println!("Block::number {}", BlockNumber::Latest);
+        // Se busca en el último 10% de la cadena
         let l_block = provider.get_block_number().await?;
-        println!("Block::get_block {}", l_block);
-        println!("Block::get_block -10% {}", l_block / 10);
-        let b_from = l_block.clone() - l_block / 10;
+        let b_from = l_block - l_block / 10;
+        info!(from_block = %b_from, to_block = %l_block, "buscando eventos pasados");
         let filter = filter.from_block(b_from);
         let logs = provider.get_logs(&filter).await?;
 
         for log in logs {
-            if let Some(topic) = log.topics.get(0) {
+            if let Some(topic) = log.topics.first() {
                 if *topic == event_signature {
-                    println!(" Evento encontrado en el pasado: {:?}", log);
+                    debug!(block = ?log.block_number, tx_hash = ?log.transaction_hash, "evento encontrado en el pasado");
                     return Ok(log);
                 }
             }
         }
 
-        return Err(eyre::eyre!("❌ No se encontró el evento en retrospectiva"));
+        Err(ClientError::decode(t!(
+            "no se encontró el evento en retrospectiva"
+        )))
     } else {
-        println!(
-            "Escuchando eventos en tiempo real para el contrato {:?}...",
-            add
-        );
+        info!("escuchando eventos en tiempo real");
         let mut stream = provider.subscribe_logs(&filter).await?;
 
         while let Some(log) = stream.next().await {
-            println!("Nuevo evento recibido: {:?}", log);
+            debug!(block = ?log.block_number, tx_hash = ?log.transaction_hash, "nuevo evento recibido");
 
-            if let Some(topic) = log.topics.get(0) {
+            if let Some(topic) = log.topics.first() {
                 if *topic == event_signature {
-                    println!("Evento detectado en tiempo real: {:?}", log);
+                    info!(block = ?log.block_number, tx_hash = ?log.transaction_hash, "evento detectado en tiempo real");
                     return Ok(log);
                 }
             }
         }
 
-        return Err(eyre::eyre!(
-            "❌ No se encontró el evento esperado en tiempo real"
-        ));
+        Err(ClientError::decode(t!(
+            "no se encontró el evento esperado en tiempo real"
+        )))
     }
 }
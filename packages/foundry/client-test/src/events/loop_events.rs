@@ -0,0 +1,120 @@
+use crate::error::ClientResult;
+use ethers::contract::parse_log;
+use ethers::prelude::*;
+
+use crate::Env;
+
+/// `Claim(address indexed claimer, uint256 periodNumber, uint256 payout)`
+#[derive(Debug, Clone, PartialEq, Eq, EthEvent)]
+#[ethevent(name = "Claim", abi = "Claim(address,uint256,uint256)")]
+pub struct ClaimFilter {
+    #[ethevent(indexed)]
+    pub claimer: Address,
+    pub period_number: U256,
+    pub payout: U256,
+}
+
+/// `Register(address indexed sender, uint256 indexed periodNumber)`
+#[derive(Debug, Clone, PartialEq, Eq, EthEvent)]
+#[ethevent(name = "Register", abi = "Register(address,uint256)")]
+pub struct RegisterFilter {
+    #[ethevent(indexed)]
+    pub sender: Address,
+    #[ethevent(indexed)]
+    pub period_number: U256,
+}
+
+/// `Withdraw(address indexed admin, address indexed to, uint256 amount)`
+#[derive(Debug, Clone, PartialEq, Eq, EthEvent)]
+#[ethevent(name = "Withdraw", abi = "Withdraw(address,address,uint256)")]
+pub struct WithdrawFilter {
+    #[ethevent(indexed)]
+    pub admin: Address,
+    #[ethevent(indexed)]
+    pub to: Address,
+    pub amount: U256,
+}
+
+/// `TrustedBackendSignerUpdated(address indexed newSigner)`
+#[derive(Debug, Clone, PartialEq, Eq, EthEvent)]
+#[ethevent(
+    name = "TrustedBackendSignerUpdated",
+    abi = "TrustedBackendSignerUpdated(address)"
+)]
+pub struct TrustedBackendSignerUpdatedFilter {
+    #[ethevent(indexed)]
+    pub new_signer: Address,
+}
+
+/// Evento histórico de un loop, con su posición en la cadena.
+#[derive(Debug, Clone)]
+pub enum LoopEvent {
+    Register {
+        block: Option<U64>,
+        tx_hash: Option<H256>,
+        event: RegisterFilter,
+    },
+    Claim {
+        block: Option<U64>,
+        tx_hash: Option<H256>,
+        event: ClaimFilter,
+    },
+    Withdraw {
+        block: Option<U64>,
+        tx_hash: Option<H256>,
+        event: WithdrawFilter,
+    },
+}
+
+/// Register/Claim/Withdraw de `loop_address` desde `from_block`, en orden de cadena.
+pub async fn loop_history(
+    env: &Env,
+    loop_address: Address,
+    from_block: u64,
+) -> ClientResult<Vec<LoopEvent>> {
+    loop_history_range(env, loop_address, from_block, None).await
+}
+
+/// Igual que [`loop_history`] pero hasta `to_block` (incluido) si se indica.
+pub async fn loop_history_range(
+    env: &Env,
+    loop_address: Address,
+    from_block: u64,
+    to_block: Option<u64>,
+) -> ClientResult<Vec<LoopEvent>> {
+    let mut filter = Filter::new().address(loop_address).from_block(from_block);
+    if let Some(to_block) = to_block {
+        filter = filter.to_block(to_block);
+    }
+    let filter = filter.topic0(vec![
+        RegisterFilter::signature(),
+        ClaimFilter::signature(),
+        WithdrawFilter::signature(),
+    ]);
+    let logs = env.provider()?.get_logs(&filter).await?;
+
+    let mut events = Vec::with_capacity(logs.len());
+    for log in logs {
+        let (block, tx_hash) = (log.block_number, log.transaction_hash);
+        if let Ok(event) = parse_log::<RegisterFilter>(log.clone()) {
+            events.push(LoopEvent::Register {
+                block,
+                tx_hash,
+                event,
+            });
+        } else if let Ok(event) = parse_log::<ClaimFilter>(log.clone()) {
+            events.push(LoopEvent::Claim {
+                block,
+                tx_hash,
+                event,
+            });
+        } else if let Ok(event) = parse_log::<WithdrawFilter>(log) {
+            events.push(LoopEvent::Withdraw {
+                block,
+                tx_hash,
+                event,
+            });
+        }
+    }
+    Ok(events)
+}
@@ -4,3 +4,6 @@ pub use recover_loop::*;
 pub mod event_listener;
 
 pub use event_listener::*;
+
+pub mod loop_events;
+pub use loop_events::*;
@@ -0,0 +1,172 @@
+use crate::error::{ClientError, ClientResult};
+use ethers::abi::{encode_packed, Token};
+use ethers::contract::parse_log;
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use std::sync::Arc;
+use tracing::{debug, info, instrument, Span};
+
+use crate::events::ClaimFilter;
+use crate::t;
+use crate::telemetry;
+use crate::utils::{RpcProvider, SignerClient};
+use crate::Env;
+
+/// Resultado de `getLoopDetails`.
+#[derive(Debug, Clone, Copy)]
+pub struct LoopDetails {
+    pub token: Address,
+    pub period_length: U256,
+    pub percent_per_period: U256,
+    pub first_period_start: U256,
+}
+
+impl LoopDetails {
+    /// Timestamp en el que empieza `period`.
+    pub fn period_start(&self, period: U256) -> U256 {
+        self.first_period_start + period * self.period_length
+    }
+
+    /// Periodo en curso para un timestamp dado (misma fórmula que `getCurrentPeriod`).
+    pub fn period_at(&self, timestamp: U256) -> U256 {
+        timestamp.saturating_sub(self.first_period_start) / self.period_length
+    }
+}
+
+fn loop_facet(env: &Env, loop_address: Address) -> ClientResult<Contract<RpcProvider>> {
+    env.contract_at(loop_address, "LoopFacet")
+}
+
+pub async fn loop_details(env: &Env, loop_address: Address) -> ClientResult<LoopDetails> {
+    let (token, period_length, percent_per_period, first_period_start) =
+        loop_facet(env, loop_address)?
+            .method::<(), (Address, U256, U256, U256)>("getLoopDetails", ())?
+            .call()
+            .await?;
+    Ok(LoopDetails {
+        token,
+        period_length,
+        percent_per_period,
+        first_period_start,
+    })
+}
+
+pub async fn current_period(env: &Env, loop_address: Address) -> ClientResult<U256> {
+    Ok(loop_facet(env, loop_address)?
+        .method::<(), U256>("getCurrentPeriod", ())?
+        .call()
+        .await?)
+}
+
+/// `(totalRegisteredUsers, maxPayout)` del periodo en curso.
+pub async fn current_period_data(env: &Env, loop_address: Address) -> ClientResult<(U256, U256)> {
+    Ok(loop_facet(env, loop_address)?
+        .method::<(), (U256, U256)>("getCurrentPeriodData", ())?
+        .call()
+        .await?)
+}
+
+/// Pago individual de `period` (0 para periodos futuros o sin registros).
+pub async fn period_individual_payout(
+    env: &Env,
+    loop_address: Address,
+    period: U256,
+) -> ClientResult<U256> {
+    Ok(loop_facet(env, loop_address)?
+        .method::<U256, U256>("getPeriodIndividualPayout", period)?
+        .call()
+        .await?)
+}
+
+/// `(isRegistered, hasClaimed)` para el periodo en curso.
+pub async fn claimer_status(
+    env: &Env,
+    loop_address: Address,
+    user: Address,
+) -> ClientResult<(bool, bool)> {
+    Ok(loop_facet(env, loop_address)?
+        .method::<Address, (bool, bool)>("getClaimerStatus", user)?
+        .call()
+        .await?)
+}
+
+/// Hash que firma el backend: `keccak256(abi.encodePacked(user, nextPeriod, loop))`.
+pub fn eligibility_hash(
+    user: Address,
+    next_period: U256,
+    loop_address: Address,
+) -> ClientResult<[u8; 32]> {
+    let packed = encode_packed(&[
+        Token::Address(user),
+        Token::Uint(next_period),
+        Token::Address(loop_address),
+    ])?;
+    Ok(keccak256(packed))
+}
+
+/// Firma de elegibilidad tal como la verifica `_verifyEligibility` (EIP-191 sobre el hash).
+#[instrument(skip(wallet), fields(signer = ?wallet.address()))]
+pub async fn sign_eligibility(
+    wallet: &LocalWallet,
+    user: Address,
+    next_period: U256,
+    loop_address: Address,
+) -> ClientResult<Bytes> {
+    let hash = eligibility_hash(user, next_period, loop_address)?;
+    let signature = wallet.sign_message(hash).await?;
+    debug!("firma de elegibilidad emitida");
+    Ok(Bytes::from(signature.to_vec()))
+}
+
+pub fn claim_and_register_call(
+    env: &Env,
+    client: Arc<SignerClient>,
+    loop_address: Address,
+    signature: Bytes,
+) -> ClientResult<ContractCall<SignerClient, ()>> {
+    let contract = loop_facet(env, loop_address)?.connect(client);
+    Ok(contract.method::<Bytes, ()>("claimAndRegister", signature)?)
+}
+
+/// Envía `claimAndRegister` desde `client` y espera el receipt. Un receipt con
+/// `status == 0` es un `ClientError::Revert`.
+#[instrument(skip(env, client, signature), fields(signer = ?client.address(), tx_hash))]
+pub async fn claim_and_register_as(
+    env: &Env,
+    client: Arc<SignerClient>,
+    loop_address: Address,
+    signature: Bytes,
+) -> ClientResult<TransactionReceipt> {
+    let call = claim_and_register_call(env, client, loop_address, signature)?;
+    let pending = match call.send().await {
+        Ok(pending) => pending,
+        Err(e) => {
+            telemetry::tx_rejected("claim", &e.to_string());
+            return Err(e.into());
+        }
+    };
+    telemetry::tx_sent("claim");
+    let tx_hash = pending.tx_hash();
+    Span::current().record("tx_hash", tracing::field::debug(tx_hash));
+    let receipt = pending.await?.ok_or(ClientError::NotConfirmed(tx_hash))?;
+    info!(status = ?receipt.status, block = ?receipt.block_number, "claimAndRegister minado");
+    let success = receipt.status == Some(U64::one());
+    telemetry::tx_mined("claim", success);
+    if !success {
+        return Err(ClientError::Revert {
+            reason: t!("la TX {tx} revirtió al minarse", tx = ?tx_hash),
+            data: Bytes::default(),
+            custom: None,
+        });
+    }
+    Ok(receipt)
+}
+
+/// Eventos `Claim` contenidos en un receipt.
+pub fn claims_in_receipt(receipt: &TransactionReceipt) -> Vec<ClaimFilter> {
+    receipt
+        .logs
+        .iter()
+        .filter_map(|log| parse_log::<ClaimFilter>(log.clone()).ok())
+        .collect()
+}
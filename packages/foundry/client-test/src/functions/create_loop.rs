@@ -1,55 +1,64 @@
-use ethers::providers::{Http, Provider};
-use std::sync::Arc;
-// use ethers::abi::{ParamType, Token};
+use crate::error::{ClientError, ClientResult};
+use crate::t;
 use ethers::prelude::*;
-use eyre::Result;
-use serde_json::Value;
+use tracing::{info, instrument, Span};
 
-use crate::events::recover_loop::{find_loop_created_event, LoopCreatedEvent};
+use crate::events::recover_loop::loop_created_in_receipt;
 
+use crate::utils::SignerClient;
 use crate::Env;
 
-pub async fn create_loop(
+/// Crea un loop con el trusted signer, espera el receipt y devuelve la dirección
+/// del loop leída del `LoopCreated(address indexed loopAddress, ...)` de la organización.
+#[instrument(skip(env), fields(tx_hash, loop_address))]
+pub async fn create_loop_confirmed(
     env: &Env,
-    contract: Contract<Provider<Http>>,
-    time: U256,
-) -> Result<(H256, Option<LoopCreatedEvent>)> {
-    let system_diamond: Address = env
-        .deployemt_data
-        .get("system_diamond")
-        .and_then(Value::as_str)
-        .ok_or_else(|| eyre::eyre!("❌ system_diamond no encontrado o inválido"))?
-        .parse()?;
+    organization: Address,
+    period_length: U256,
+    percent_per_period: U256,
+) -> ClientResult<(H256, Address)> {
+    let call = create_loop_call(env, organization, period_length, percent_per_period)?;
+    let pending = call.send().await?;
+    let tx_hash = pending.tx_hash();
+    Span::current().record("tx_hash", tracing::field::debug(tx_hash));
+    let receipt = pending.await?.ok_or(ClientError::NotConfirmed(tx_hash))?;
 
-    let token: Address = env
-        .deployemt_data
-        .get("test_token_address")
-        .and_then(Value::as_str)
-        .ok_or_else(|| eyre::eyre!("❌ test_token_address no encontrado o inválido"))?
-        .parse()?;
+    let loop_address = loop_address_in_receipt(&receipt, organization)?;
+    Span::current().record("loop_address", tracing::field::debug(loop_address));
+    info!(block = ?receipt.block_number, "loop creado");
+    Ok((tx_hash, loop_address))
+}
 
-    let percent_per_period: U256 = U256::from(5);
+/// Dirección del loop del `LoopCreated` que emite `organization`; error si no está.
+pub fn loop_address_in_receipt(
+    receipt: &TransactionReceipt,
+    organization: Address,
+) -> ClientResult<Address> {
+    loop_created_in_receipt(receipt, organization)?
+        .map(|event| event.loop_address)
+        .ok_or_else(|| {
+            ClientError::decode(t!(
+                "no se encontró {event} en el receipt",
+                event = "LoopCreated"
+            ))
+        })
+}
 
-    // match contract {
-    // Some(c) => {
-    let signer = env
-        .trusted_signer
-        .clone()
-        .ok_or_else(|| eyre::eyre!("❌ No hay signer disponible"))?;
-    let c_with_user = contract.clone().connect(signer);
-    println!("contract(organization) : {:?}", c_with_user.address());
-    // 1. Enviar la transacción y obtener el `tx_hash`
-    let tx_hash_pending = c_with_user
-        .method::<(Address, Address, U256, U256), Address>(
-            "createNewLoop",
-            (system_diamond, token, time, percent_per_period),
-        )?
-        .send()
-        .await?
-        .tx_hash();
-    let tx_hash = tx_hash_pending.clone();
+/// `createNewLoop` con el trusted signer; el valor de retorno es la dirección del loop.
+pub fn create_loop_call(
+    env: &Env,
+    organization: Address,
+    period_length: U256,
+    percent_per_period: U256,
+) -> ClientResult<ContractCall<SignerClient, Address>> {
+    let system_diamond = env.deployment_address("system_diamond")?;
+    let token = env.deployment_address("test_token_address")?;
 
-    println!(" Loop creado en TX [pending]: {:?}", tx_hash);
-    let loop_event = find_loop_created_event(system_diamond, tx_hash).await?;
-    Ok((tx_hash, loop_event))
+    let org = env
+        .contract_at(organization, "OrganizationFacet")?
+        .connect(env.signer()?);
+    Ok(org.method::<(Address, Address, U256, U256), Address>(
+        "createNewLoop",
+        (system_diamond, token, period_length, percent_per_period),
+    )?)
 }
@@ -0,0 +1,75 @@
+use crate::error::ClientResult;
+use ethers::prelude::*;
+
+use crate::functions::transfer_call;
+use crate::utils::SignerClient;
+use crate::Env;
+
+/// LoopFacet conectado al trusted signer, que es el admin de los loops que crea.
+fn loop_as_admin(env: &Env, loop_address: Address) -> ClientResult<Contract<SignerClient>> {
+    Ok(env
+        .contract_at(loop_address, "LoopFacet")?
+        .connect(env.signer()?))
+}
+
+/// `withdrawDeposit(to)`: saca todo el balance del token del loop.
+pub fn withdraw_deposit_call(
+    env: &Env,
+    loop_address: Address,
+    to: Address,
+) -> ClientResult<ContractCall<SignerClient, ()>> {
+    Ok(loop_as_admin(env, loop_address)?.method::<Address, ()>("withdrawDeposit", to)?)
+}
+
+pub async fn withdraw_deposit(env: &Env, loop_address: Address, to: Address) -> ClientResult<H256> {
+    let call = withdraw_deposit_call(env, loop_address, to)?;
+    let pending = call.send().await?;
+    Ok(pending.tx_hash())
+}
+
+pub fn set_trusted_backend_signer_call(
+    env: &Env,
+    loop_address: Address,
+    signer: Address,
+) -> ClientResult<ContractCall<SignerClient, ()>> {
+    Ok(loop_as_admin(env, loop_address)?
+        .method::<Address, ()>("setTrustedBackendSigner", signer)?)
+}
+
+pub async fn set_trusted_backend_signer(
+    env: &Env,
+    loop_address: Address,
+    signer: Address,
+) -> ClientResult<H256> {
+    let call = set_trusted_backend_signer_call(env, loop_address, signer)?;
+    let pending = call.send().await?;
+    Ok(pending.tx_hash())
+}
+
+pub fn set_percent_per_period_call(
+    env: &Env,
+    loop_address: Address,
+    percent: U256,
+) -> ClientResult<ContractCall<SignerClient, ()>> {
+    Ok(loop_as_admin(env, loop_address)?.method::<U256, ()>("setPercentPerPeriod", percent)?)
+}
+
+pub async fn set_percent_per_period(
+    env: &Env,
+    loop_address: Address,
+    percent: U256,
+) -> ClientResult<H256> {
+    let call = set_percent_per_period_call(env, loop_address, percent)?;
+    let pending = call.send().await?;
+    Ok(pending.tx_hash())
+}
+
+/// `transfer(loop, amount)` del token del loop desde el trusted signer.
+pub fn fund_loop_call(
+    env: &Env,
+    token: Address,
+    loop_address: Address,
+    amount: U256,
+) -> ClientResult<ContractCall<SignerClient, bool>> {
+    transfer_call(env, token, loop_address, amount)
+}
@@ -1,2 +1,14 @@
 pub mod create_loop;
 pub use create_loop::*;
+
+pub mod claim;
+pub use claim::*;
+
+pub mod loop_admin;
+pub use loop_admin::*;
+
+pub mod organization;
+pub use organization::*;
+
+pub mod token;
+pub use token::*;
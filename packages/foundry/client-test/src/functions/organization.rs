@@ -0,0 +1,88 @@
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+use ethers::prelude::*;
+
+use crate::utils::SignerClient;
+use crate::Env;
+
+/// Datos de una organización leídos con sus getters.
+#[derive(Debug, Clone)]
+pub struct OrganizationInfo {
+    pub address: Address,
+    pub name: String,
+    pub admin: Address,
+    pub description: String,
+}
+
+/// `createOrganization` en el system diamond; el retorno es la dirección de la organización.
+pub fn create_organization_call(
+    env: &Env,
+    name: &str,
+    admin: Address,
+    description: &str,
+) -> ClientResult<ContractCall<SignerClient, Address>> {
+    let factory = env
+        .contract_at(
+            env.deployment_address("system_diamond")?,
+            "OrganizationFactoryFacet",
+        )?
+        .connect(env.signer()?);
+    Ok(factory.method::<(String, Address, String), Address>(
+        "createOrganization",
+        (name.to_string(), admin, description.to_string()),
+    )?)
+}
+
+/// Crea la organización, espera el receipt y devuelve la dirección leída de
+/// `OrganizationCreated(uint256 indexed id, address indexed organizationAddress, ...)`.
+pub async fn create_organization(
+    env: &Env,
+    name: &str,
+    admin: Address,
+    description: &str,
+) -> ClientResult<(H256, Address)> {
+    let call = create_organization_call(env, name, admin, description)?;
+    let pending = call.send().await?;
+    let tx_hash = pending.tx_hash();
+    let receipt = pending.await?.ok_or(ClientError::NotConfirmed(tx_hash))?;
+    Ok((tx_hash, organization_created_in_receipt(&receipt)?))
+}
+
+/// Dirección de la organización del `OrganizationCreated(uint256 indexed id,
+/// address indexed organizationAddress, ...)` de un receipt.
+pub fn organization_created_in_receipt(receipt: &TransactionReceipt) -> ClientResult<Address> {
+    let event_signature = H256::from(ethers::utils::keccak256(
+        "OrganizationCreated(uint256,address,string,address,string)",
+    ));
+    receipt
+        .logs
+        .iter()
+        .find(|log| log.topics.first() == Some(&event_signature))
+        .and_then(|log| log.topics.get(2))
+        .map(|topic| Address::from(*topic))
+        .ok_or_else(|| {
+            ClientError::decode(t!(
+                "no se encontró {event} en el receipt",
+                event = "OrganizationCreated"
+            ))
+        })
+}
+
+pub async fn organization_info(env: &Env, organization: Address) -> ClientResult<OrganizationInfo> {
+    let contract = env.contract_at(organization, "OrganizationFacet")?;
+    Ok(OrganizationInfo {
+        address: organization,
+        name: contract
+            .method::<(), String>("getOrganizationName", ())?
+            .call()
+            .await?,
+        admin: contract
+            .method::<(), Address>("getOrganizationAdmin", ())?
+            .call()
+            .await?,
+        description: contract
+            .method::<(), String>("getOrganizationDescription", ())?
+            .call()
+            .await?,
+    })
+}
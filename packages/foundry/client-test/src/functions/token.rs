@@ -0,0 +1,240 @@
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+use ethers::prelude::*;
+use ethers::utils::{format_units, parse_units};
+use tracing::{info, instrument};
+
+use crate::functions::loop_details;
+use crate::storage::{address_key, mapping_slot};
+use crate::utils::{RpcProvider, SignerClient};
+use crate::Env;
+
+/// Slots de `ERC20` de OpenZeppelin (`_balances`, `_allowances`, `_totalSupply`),
+/// que `TestToken` hereda sin variables propias.
+const BALANCES_SLOT: u64 = 0;
+const TOTAL_SUPPLY_SLOT: u64 = 2;
+
+/// Metadatos de un ERC20.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenInfo {
+    pub address: Address,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub total_supply: U256,
+}
+
+impl TokenInfo {
+    /// Cantidad legible (`"1500.5"`) a unidades mínimas del token.
+    pub fn parse(&self, amount: &str) -> ClientResult<U256> {
+        parse_token_amount(amount, self.decimals)
+    }
+
+    /// Unidades mínimas a texto con decimales, sin ceros sobrantes.
+    pub fn format(&self, amount: U256) -> String {
+        format_token_amount(amount, self.decimals)
+    }
+
+    /// Como `format` pero con el símbolo: `"1500.5 HNY"`.
+    pub fn display(&self, amount: U256) -> String {
+        format!("{} {}", self.format(amount), self.symbol)
+    }
+}
+
+fn erc20(env: &Env, token: Address) -> ClientResult<Contract<RpcProvider>> {
+    env.contract_at(token, "TestToken")
+}
+
+fn erc20_as_signer(env: &Env, token: Address) -> ClientResult<Contract<SignerClient>> {
+    Ok(erc20(env, token)?.connect(env.signer()?))
+}
+
+pub async fn token_info(env: &Env, token: Address) -> ClientResult<TokenInfo> {
+    let contract = erc20(env, token)?;
+    Ok(TokenInfo {
+        address: token,
+        name: contract.method::<(), String>("name", ())?.call().await?,
+        symbol: contract.method::<(), String>("symbol", ())?.call().await?,
+        decimals: contract.method::<(), u8>("decimals", ())?.call().await?,
+        total_supply: contract
+            .method::<(), U256>("totalSupply", ())?
+            .call()
+            .await?,
+    })
+}
+
+pub async fn token_balance(env: &Env, token: Address, holder: Address) -> ClientResult<U256> {
+    Ok(erc20(env, token)?
+        .method::<Address, U256>("balanceOf", holder)?
+        .call()
+        .await?)
+}
+
+pub async fn token_allowance(
+    env: &Env,
+    token: Address,
+    owner: Address,
+    spender: Address,
+) -> ClientResult<U256> {
+    Ok(erc20(env, token)?
+        .method::<(Address, Address), U256>("allowance", (owner, spender))?
+        .call()
+        .await?)
+}
+
+/// `approve(spender, amount)` desde el trusted signer.
+pub fn approve_call(
+    env: &Env,
+    token: Address,
+    spender: Address,
+    amount: U256,
+) -> ClientResult<ContractCall<SignerClient, bool>> {
+    Ok(erc20_as_signer(env, token)?
+        .method::<(Address, U256), bool>("approve", (spender, amount))?)
+}
+
+/// `transfer(to, amount)` desde el trusted signer.
+pub fn transfer_call(
+    env: &Env,
+    token: Address,
+    to: Address,
+    amount: U256,
+) -> ClientResult<ContractCall<SignerClient, bool>> {
+    Ok(erc20_as_signer(env, token)?.method::<(Address, U256), bool>("transfer", (to, amount))?)
+}
+
+/// Fondeo de un loop con una cantidad legible (`"1500.5"`) de su token.
+pub async fn fund_loop_amount_call(
+    env: &Env,
+    loop_address: Address,
+    amount: &str,
+) -> ClientResult<(TokenInfo, U256, ContractCall<SignerClient, bool>)> {
+    let info = token_info(env, loop_details(env, loop_address).await?.token).await?;
+    let amount = info.parse(amount)?;
+    let call = transfer_call(env, info.address, loop_address, amount)?;
+    Ok((info, amount, call))
+}
+
+/// Escritura de un slot con `anvil_setStorageAt`: el valor que había y el nuevo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageWrite {
+    pub slot: U256,
+    pub before: U256,
+    pub after: U256,
+}
+
+/// Slot de `_balances[holder]` en un ERC20 de OpenZeppelin.
+pub fn erc20_balance_slot(holder: Address) -> U256 {
+    mapping_slot(address_key(holder), U256::from(BALANCES_SLOT))
+}
+
+/// Lo que escribe un mint de `amount` para `to`: su balance y el `totalSupply`,
+/// leídos del storage del token. No escribe nada; `--dry-run` lo muestra tal cual.
+pub async fn mint_plan<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    token: Address,
+    to: Address,
+    amount: U256,
+) -> ClientResult<Vec<StorageWrite>> {
+    let overflow = || ClientError::config(t!("el mint desborda el totalSupply"));
+    let mut writes = Vec::with_capacity(2);
+    for slot in [erc20_balance_slot(to), U256::from(TOTAL_SUPPLY_SLOT)] {
+        let word = provider
+            .get_storage_at(token, H256::from_uint(&slot), None)
+            .await?;
+        let before = U256::from_big_endian(word.as_bytes());
+        let after = before.checked_add(amount).ok_or_else(overflow)?;
+        writes.push(StorageWrite {
+            slot,
+            before,
+            after,
+        });
+    }
+    Ok(writes)
+}
+
+/// Aplica las escrituras de `mint_plan` con `anvil_setStorageAt`.
+pub async fn apply_storage_writes<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    token: Address,
+    writes: &[StorageWrite],
+) -> ClientResult<()> {
+    for write in writes {
+        provider
+            .request::<_, serde_json::Value>(
+                "anvil_setStorageAt",
+                (
+                    token,
+                    H256::from_uint(&write.slot),
+                    H256::from_uint(&write.after),
+                ),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Acuña `amount` para `to` en anvil. `TestToken` no tiene `mint`: se escriben
+/// directamente el balance y el `totalSupply` con `anvil_setStorageAt`.
+#[instrument(skip(env), fields(token = ?token, to = ?to, amount = %amount))]
+pub async fn mint_local(
+    env: &Env,
+    token: Address,
+    to: Address,
+    amount: U256,
+) -> ClientResult<Vec<StorageWrite>> {
+    let provider = env.provider()?;
+    let writes = mint_plan(&provider, token, to, amount).await?;
+    apply_storage_writes(&provider, token, &writes).await?;
+
+    // Si el slot no era el del balance (otro layout de ERC20), mejor saberlo ya
+    if token_balance(env, token, to).await? != writes[0].after {
+        return Err(ClientError::config(t!(
+            "el token {token} no usa el layout de ERC20 de OpenZeppelin",
+            token = ?token
+        )));
+    }
+    info!("mint local aplicado");
+    Ok(writes)
+}
+
+/// Cantidad legible a unidades mínimas; rechaza más decimales de los que tiene el token.
+pub fn parse_token_amount(amount: &str, decimals: u8) -> ClientResult<U256> {
+    let amount = amount.trim();
+    let invalid = || {
+        ClientError::decode(t!(
+            "cantidad inválida \"{amount}\" para un token de {decimals} decimales",
+            amount = amount,
+            decimals = decimals
+        ))
+    };
+    if amount.starts_with('-') {
+        return Err(invalid());
+    }
+    if let Some((_, fraction)) = amount.split_once('.') {
+        if fraction.len() > decimals as usize {
+            return Err(invalid());
+        }
+    }
+    parse_units(amount, decimals as u32)
+        .map(Into::into)
+        .map_err(|_| invalid())
+}
+
+/// Unidades mínimas a texto: `1500500000000000000000` con 18 decimales es `"1500.5"`.
+pub fn format_token_amount(amount: U256, decimals: u8) -> String {
+    let Ok(formatted) = format_units(amount, decimals as u32) else {
+        return amount.to_string();
+    };
+    match formatted.split_once('.') {
+        Some((whole, fraction)) => {
+            let fraction = fraction.trim_end_matches('0');
+            if fraction.is_empty() {
+                whole.to_string()
+            } else {
+                format!("{}.{}", whole, fraction)
+            }
+        }
+        None => formatted,
+    }
+}
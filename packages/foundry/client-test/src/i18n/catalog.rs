@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::i18n::{locale, Locale};
+
+/// Traducción de `message` al idioma en uso. Los mensajes se escriben en
+/// español en el código y son la clave del catálogo; lo que no tenga
+/// traducción sale en español.
+pub fn tr(message: &'static str) -> &'static str {
+    match locale() {
+        Locale::Es => message,
+        Locale::En => english().get(message).copied().unwrap_or(message),
+    }
+}
+
+/// Sustituye cada `{nombre}` de `template` por su valor.
+pub fn fill(template: &str, args: &[(&str, String)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+fn english() -> &'static HashMap<&'static str, &'static str> {
+    static CATALOG: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+    CATALOG.get_or_init(|| EN.iter().copied().collect())
+}
+
+/// Mensaje en español -> inglés. Los `{nombre}` deben ser los mismos en ambos.
+const EN: &[(&str, &str)] = &[
+    // comunes
+    ("❌ {name} no está definida", "❌ {name} is not set"),
+    ("❌ La transacción no se confirmó", "❌ The transaction was not confirmed"),
+    ("❌ Error en setup", "❌ Setup failed"),
+    // entorno, deployment y artifacts
+    ("no hay dirección de loop en el deployment", "no loop address in the deployment"),
+    ("no hay dirección de organización en el deployment", "no organization address in the deployment"),
+    ("ABI no encontrada en el artifact de {contract}", "ABI not found in the {contract} artifact"),
+    ("ABI de {contract} inválida: {error}", "invalid {contract} ABI: {error}"),
+    ("no hay signer disponible", "no signer available"),
+    ("{key} no encontrado o inválido", "{key} missing or invalid"),
+    ("loop_contract no inicializado", "loop_contract not initialized"),
+    ("{name} no está definida", "{name} is not set"),
+    ("RPC_URL inválida {url}: {error}", "invalid RPC_URL {url}: {error}"),
+    ("no se pudo leer {path}: {error}", "could not read {path}: {error}"),
+    ("JSON inválido en {path}: {error}", "invalid JSON in {path}: {error}"),
+    ("dirección inválida: {value}", "invalid address: {value}"),
+    ("no se pudo leer el artifact {path}: {error}", "could not read artifact {path}: {error}"),
+    ("artifact {path} inválido: {error}", "invalid artifact {path}: {error}"),
+    ("{field} no encontrado para {contract}", "{field} not found for {contract}"),
+    ("selector inválido: {input}", "invalid selector: {input}"),
+    ("función desconocida: {input}", "unknown function: {input}"),
+    // errores de la librería
+    ("❌ Configuración: {detail}", "❌ Configuration: {detail}"),
+    ("❌ Transporte: {detail}", "❌ Transport: {detail}"),
+    ("❌ Firma: {detail}", "❌ Signing: {detail}"),
+    ("❌ Revert: {reason}", "❌ Revert: {reason}"),
+    ("❌ La transacción {tx} no se confirmó", "❌ Transaction {tx} was not confirmed"),
+    ("❌ Decodificación: {detail}", "❌ Decoding: {detail}"),
+    ("el contrato no está desplegado", "the contract is not deployed"),
+    // cli
+    ("{layout} de {address}", "{layout} of {address}"),
+    ("Simulación OK (from {admin})", "Simulation OK (from {admin})"),
+    ("✅ {action} minado en el bloque {block} (TX {tx})", "✅ {action} mined in block {block} (TX {tx})"),
+    ("❌ {action} revertido en el bloque {block} (TX {tx})", "❌ {action} reverted in block {block} (TX {tx})"),
+    ("❌ La TX {tx} revirtió", "❌ TX {tx} reverted"),
+    ("{artifact} con salt {salt} -> {address}", "{artifact} with salt {salt} -> {address}"),
+    ("{artifact} desplegada en {address} (TX {tx})", "{artifact} deployed at {address} (TX {tx})"),
+    ("❌ Algún escenario no se comportó como se esperaba", "❌ Some scenario did not behave as expected"),
+    ("Loop creado en {address} (TX {tx})", "Loop created at {address} (TX {tx})"),
+    ("periodo en curso:", "current period:"),
+    ("{period} (empezó en {start})", "{period} (started at {start})"),
+    ("registrados:", "registered:"),
+    ("pago individual:", "individual payout:"),
+    ("Organización creada en {address} (TX {tx})", "Organization created at {address} (TX {tx})"),
+    ("Organización {address}\n  nombre:      {name}\n  admin:       {admin}\n  descripción: {description}", "Organization {address}\n  name:        {name}\n  admin:       {admin}\n  description: {description}"),
+    ("Register {user} para el periodo {period}", "Register {user} for period {period}"),
+    ("Claim    {user} periodo {period} payout {payout}", "Claim    {user} period {period} payout {payout}"),
+    // consola
+    ("esta ayuda", "this help"),
+    ("contratos y signers de la sesión", "session contracts and signers"),
+    ("set <nombre> <dirección> [artifact...]: nombra un contrato", "set <name> <address> [artifact...]: name a contract"),
+    ("signer [nombre] [clave|VARIABLE]: lista, elige o añade un signer", "signer [name] [key|VARIABLE]: list, pick or add a signer"),
+    ("abi <contrato>: funciones y eventos de un contrato", "abi <contract>: functions and events of a contract"),
+    ("call <contrato>.<función> [args...]: eth_call", "call <contract>.<function> [args...]: eth_call"),
+    ("send <contrato>.<función> [args...]: envía la TX con el signer actual", "send <contract>.<function> [args...]: send the TX with the current signer"),
+    ("dry <contrato>.<función> [args...]: simula la TX y estima el gas", "dry <contract>.<function> [args...]: simulate the TX and estimate gas"),
+    ("logs <contrato> [desde_bloque]: eventos decodificados", "logs <contract> [from_block]: decoded events"),
+    ("decode <hex>: decodifica calldata o datos de revert", "decode <hex>: decode calldata or revert data"),
+    ("sale de la consola", "leave the console"),
+    ("❌ Comillas sin cerrar", "❌ Unclosed quotes"),
+    ("❌ Signer desconocido: {name}", "❌ Unknown signer: {name}"),
+    ("❌ Contrato desconocido: {name} (usa `set` para nombrarlo)", "❌ Unknown contract: {name} (use `set` to name it)"),
+    ("❌ Formato esperado contrato.función: {spec}", "❌ Expected format contract.function: {spec}"),
+    ("❌ {name} no tiene la función {method}", "❌ {name} has no function {method}"),
+    ("❌ {name}.{method} no recibe {args} argumentos", "❌ {name}.{method} does not take {args} arguments"),
+    ("❌ {name}.{method} es ambigua, indica la firma completa", "❌ {name}.{method} is ambiguous, give the full signature"),
+    ("❌ Argumento {arg} inválido para {kind}: {error}", "❌ Invalid argument {arg} for {kind}: {error}"),
+    ("{address} log anónimo", "{address} anonymous log"),
+    ("{address} evento desconocido {topic}", "{address} unknown event {topic}"),
+    ("❌ Comando desconocido: {command} (prueba `help`)", "❌ Unknown command: {command} (try `help`)"),
+    ("Las direcciones admiten nombres de la sesión (loop, trusted, ...)", "Addresses accept session names (loop, trusted, ...)"),
+    ("Contratos:", "Contracts:"),
+    ("Signers:", "Signers:"),
+    ("❌ Uso: set <nombre> <dirección> [artifact...]", "❌ Usage: set <name> <address> [artifact...]"),
+    ("❌ Indica al menos un artifact para {name}", "❌ Give at least one artifact for {name}"),
+    ("✅ {name} = {address} ({count} funciones)", "✅ {name} = {address} ({count} functions)"),
+    ("❌ Uso: signer [nombre] [clave|VARIABLE]", "❌ Usage: signer [name] [key|VARIABLE]"),
+    ("❌ Uso: abi <contrato>", "❌ Usage: abi <contract>"),
+    ("❌ Uso: call <contrato>.<función> [args...]", "❌ Usage: call <contract>.<function> [args...]"),
+    ("❌ revert: {reason}", "❌ revert: {reason}"),
+    ("❌ Uso: send <contrato>.<función> [args...]", "❌ Usage: send <contract>.<function> [args...]"),
+    ("⏳ {function} enviada (TX {tx})", "⏳ {function} sent (TX {tx})"),
+    ("❌ revertida", "❌ reverted"),
+    ("{status} bloque {block} gas {gas}", "{status} block {block} gas {gas}"),
+    ("❌ Uso: dry <contrato>.<función> [args...]", "❌ Usage: dry <contract>.<function> [args...]"),
+    ("❌ Uso: logs <contrato> [desde_bloque]", "❌ Usage: logs <contract> [from_block]"),
+    ("{count} eventos", "{count} events"),
+    ("❌ Uso: decode <hex>", "❌ Usage: decode <hex>"),
+    ("Consola de Gyralis en {rpc} (chain {chain}). `help` para ver los comandos.", "Gyralis console on {rpc} (chain {chain}). `help` lists the commands."),
+    // dashboard
+    (" ❌ Refresco fallido, se reintenta: {error}", " ❌ Refresh failed, retrying: {error}"),
+    ("❌ No se pudo leer el último bloque", "❌ Could not read the latest block"),
+    (" Gyralis · bloque {block} · {count} loops · q para salir", " Gyralis · block {block} · {count} loops · q to quit"),
+    (" Eventos ", " Events "),
+    ("Periodo {period}  ·  siguiente en {countdown}", "Period {period}  ·  next in {countdown}"),
+    ("Registrados {registered}  ·  maxPayout {max_payout}", "Registered {registered}  ·  maxPayout {max_payout}"),
+    ("Saldo {balance}  ·  {percent}% por periodo", "Balance {balance}  ·  {percent}% per period"),
+    // roles y auditoría de permisos
+    ("{call} = {live} pero los eventos indican {expected}", "{call} = {live} but the events say {expected}"),
+    ("{function} es invocable por {role}", "{function} is callable by {role}"),
+    ("address(0) tiene DEFAULT_ADMIN_ROLE", "address(0) holds DEFAULT_ADMIN_ROLE"),
+    ("Ningún usuario tiene DEFAULT_ADMIN_ROLE", "No user holds DEFAULT_ADMIN_ROLE"),
+    ("El admin {admin} del loop no es su organización {organization}", "Loop admin {admin} is not its organization {organization}"),
+    ("La organización {organization} no es admin de su loop", "Organization {organization} is not admin of its loop"),
+    ("No se encontró el LoopCreated de este loop", "LoopCreated for this loop not found"),
+    ("Permisos para {selector} ({function}), que el diamond no expone", "Permissions for {selector} ({function}), which the diamond does not expose"),
+    ("Historial ({count} eventos):", "History ({count} events):"),
+    ("{sign} {role} a {user}", "{sign} {role} to {user}"),
+    ("{sign} {role} en {function}", "{sign} {role} on {function}"),
+    ("Usuarios:", "Users:"),
+    ("Funciones:", "Functions:"),
+    ("✅ Sin hallazgos", "✅ No findings"),
+    ("❌ Rol inválido: {role}", "❌ Invalid role: {role}"),
+    ("El diamond no expone AccessControlFacet: roles leídos del storage", "The diamond does not expose AccessControlFacet: roles read from storage"),
+    ("FUNCIÓN", "FUNCTION"),
+    ("puede llamar {function}", "can call {function}"),
+    // diamonds y registry
+    ("❌ {selector} es inmutable en el diamond {diamond}: no se puede reemplazar", "❌ {selector} is immutable in diamond {diamond}: it cannot be replaced"),
+    ("❌ Formato esperado NOMBRE=DIRECCION: {spec}", "❌ Expected format NAME=ADDRESS: {spec}"),
+    ("❌ La simulación del diamondCut falló: {error}", "❌ The diamondCut simulation failed: {error}"),
+    ("Sin cambios: el diamond ya coincide con las facetas indicadas", "No changes: the diamond already matches the given facets"),
+    ("({count} selectores)", "({count} selectors)"),
+    ("⚠️  {facet} cambia el storage respecto de {old_facet}: +{added} -{removed}", "⚠️  {facet} changes storage compared to {old_facet}: +{added} -{removed}"),
+    ("FACETA", "FACET"),
+    ("NOMBRE", "NAME"),
+    ("SELECTORES", "SELECTORS"),
+    ("REGISTRADA", "REGISTERED"),
+    ("sí", "yes"),
+    ("NO", "NO"),
+    ("FIRMA", "SIGNATURE"),
+    ("AVISOS", "FLAGS"),
+    ("{facets} facetas, {selectors} selectores, {flagged} con advertencias", "{facets} facets, {selectors} selectors, {flagged} flagged"),
+    ("❌ No se encontró {event} en el receipt", "❌ {event} not found in the receipt"),
+    ("❌ Faceta desplegada en {deployed}, se esperaba {predicted}", "❌ Facet deployed at {deployed}, expected {predicted}"),
+    // analytics y benchmarks
+    ("{user} no se ha registrado en ningún loop", "{user} has not registered in any loop"),
+    ("Loop {address}: ganado {earned} | racha actual {current} | racha más larga {longest}", "Loop {address}: earned {earned} | current streak {current} | longest streak {longest}"),
+    ("cobrado {payout}", "claimed {payout}"),
+    ("perdido", "missed"),
+    ("en curso", "in progress"),
+    ("registrado", "registered"),
+    ("periodo", "period"),
+    ("{count} periodos", "{count} periods"),
+    ("sin límite", "unlimited"),
+    ("Loop {address} (periodo en curso {period})", "Loop {address} (current period {period})"),
+    ("PERIODO", "PERIOD"),
+    ("REGISTROS", "REGISTERED"),
+    ("TASA", "RATE"),
+    ("PAGO/USUARIO", "PAYOUT/USER"),
+    ("REPARTIDO", "DISTRIBUTED"),
+    ("Total repartido:", "Total distributed:"),
+    ("Total retirado:", "Total withdrawn:"),
+    ("Balance actual:", "Current balance:"),
+    ("Runway hasta {threshold}: {full} (todos cobran), {observed} (tasa observada)", "Runway down to {threshold}: {full} (everyone claims), {observed} (observed rate)"),
+    ("Loop {address} con {users} usuarios", "Loop {address} with {users} users"),
+    ("MEDIA", "MEAN"),
+    ("claimAndRegister (registro)", "claimAndRegister (registration)"),
+    ("claimAndRegister (1er claim)", "claimAndRegister (1st claim)"),
+    ("FASE", "PHASE"),
+    ("❌ La TX {tx} que financia el loop {address} no se minó con éxito", "❌ Loop {address} funding TX {tx} was not mined successfully"),
+    // escenarios, motor de envío y bot
+    ("TX enviadas:", "TX sent:"),
+    ("revertidas:", "reverted:"),
+    ("fallidas:", "failed:"),
+    ("Subidas gas:", "Gas bumps:"),
+    ("Reintentos:", "Retries:"),
+    ("Duración:", "Duration:"),
+    ("❌ No hay bad_signer disponible", "❌ No bad_signer available"),
+    ("CASO", "CASE"),
+    ("RESULTADO", "RESULT"),
+    ("ESPERADO", "EXPECTED"),
+    ("OBSERVADO", "OBSERVED"),
+    ("{passed}/{total} casos superados", "{passed}/{total} cases passed"),
+    ("❌ {from} no es un signer del motor", "❌ {from} is not an engine signer"),
+    ("❌ Envío rechazado (nonce {nonce}, {retries} reintentos): {error}", "❌ Submission rejected (nonce {nonce}, {retries} retries): {error}"),
+    ("❌ TX con nonce {nonce} de {from} sin minar tras {bumps} subidas de gas", "❌ TX with nonce {nonce} from {from} not mined after {bumps} gas bumps"),
+    ("❌ El backend no devolvió una firma", "❌ The backend returned no signature"),
+    ("TX descartada", "TX dropped"),
+    ("revert no reproducible", "revert not reproducible"),
+    ("no se pudo rotar el signer: {reason}", "could not rotate the signer: {reason}"),
+    // dry run, logging y métricas
+    ("🧪 Dry run (no se envía nada)", "🧪 Dry run (nothing is sent)"),
+    ("❌ {command} envía TX reales y no admite --dry-run", "❌ {command} sends real TXs and does not support --dry-run"),
+    ("función:", "function:"),
+    ("resultado:", "result:"),
+    ("no se pudo estimar", "could not estimate"),
+    ("❌ No se pudo levantar /metrics en {addr}: {error}", "❌ Could not start /metrics on {addr}: {error}"),
+    ("❌ No se pudo iniciar el logging: {error}", "❌ Could not initialize logging: {error}"),
+    ("revert sin datos", "revert without data"),
+    // funciones y eventos
+    ("string corto de {len} bytes en storage", "short string of {len} bytes in storage"),
+    ("string de {len} bytes en storage: el slot no parece un string", "string of {len} bytes in storage: the slot does not look like a string"),
+    ("no se encontró {event} en el receipt", "{event} not found in the receipt"),
+    ("no se encontró el evento en retrospectiva", "event not found in past blocks"),
+    ("no se encontró el evento esperado en tiempo real", "expected event not seen in real time"),
+    ("error al extraer los datos del evento", "failed to extract the event data"),
+    // fixtures JSON-RPC
+    ("petición sin respuesta en el fixture: {method} {params}", "request with no response in the fixture: {method} {params}"),
+    ("no se pudo leer el fixture {path}: {error}", "could not read fixture {path}: {error}"),
+    ("fixture {path} inválido: {error}", "invalid fixture {path}: {error}"),
+    ("no se pudo escribir el fixture {path}: {error}", "could not write fixture {path}: {error}"),
+    // tokens
+    ("el mint desborda el totalSupply", "the mint overflows totalSupply"),
+    ("el token {token} no usa el layout de ERC20 de OpenZeppelin", "token {token} does not use the OpenZeppelin ERC20 storage layout"),
+    ("cantidad inválida \"{amount}\" para un token de {decimals} decimales", "invalid amount \"{amount}\" for a token with {decimals} decimals"),
+    ("Token {address}\n  nombre:       {name}\n  símbolo:      {symbol}\n  decimales:    {decimals}\n  totalSupply:  {supply}", "Token {address}\n  name:         {name}\n  symbol:       {symbol}\n  decimals:     {decimals}\n  totalSupply:  {supply}"),
+    ("Acuñados {amount} para {to}", "Minted {amount} to {to}"),
+    ("🧪 Dry run: acuñar {amount} para {to} escribiría en {token}:", "🧪 Dry run: minting {amount} to {to} would write to {token}:"),
+    // lecturas en lote
+    ("respuesta inválida a un lote JSON-RPC: {detail}", "invalid response to a JSON-RPC batch: {detail}"),
+    ("id {id} fuera del lote", "id {id} outside the batch"),
+    ("falta la respuesta {id}", "missing response {id}"),
+    ("{got} respuestas para {sent} peticiones", "{got} responses for {sent} requests"),
+    ("Multicall3 no está desplegado en {address} y la red no es anvil ({client})", "Multicall3 is not deployed at {address} and the network is not anvil ({client})"),
+    ("retorno de aggregate3 inválido: {error}", "invalid aggregate3 return data: {error}"),
+    ("aggregate3 devolvió {got} resultados para {sent} llamadas", "aggregate3 returned {got} results for {sent} calls"),
+    ("retorno inesperado ({len} bytes): {error}", "unexpected return data ({len} bytes): {error}"),
+    ("retorno de eth_call inválido: {error}", "invalid eth_call return data: {error}"),
+    ("faltan resultados en el lote", "missing results in the batch"),
+    ("❌ No se pudo leer {path}: {error}", "❌ Could not read {path}: {error}"),
+    ("registrado: {value}", "registered: {value}"),
+    ("cobrado: {value}", "claimed: {value}"),
+    ("lotes JSON-RPC", "JSON-RPC batches"),
+    // diario de transacciones
+    ("no se pudo abrir el diario {path}: {error}", "could not open journal {path}: {error}"),
+    ("el diario {path} está en uso por otro proceso", "journal {path} is in use by another process"),
+    ("línea {line} del diario {path} inválida: {error}", "invalid line {line} in journal {path}: {error}"),
+    ("no se pudo escribir en el diario {path}: {error}", "could not write to journal {path}: {error}"),
+    ("la entrada {key} del diario es de otra llamada", "journal entry {key} belongs to a different call"),
+    ("la TX {tx} revirtió al minarse", "TX {tx} reverted when mined"),
+    ("  {step}: retomada (TX {tx})", "  {step}: resumed (TX {tx})"),
+    ("  {step}: enviada (TX {tx})", "  {step}: sent (TX {tx})"),
+    ("Organización {organization} con el loop {loop_address} (diario {journal})", "Organization {organization} with loop {loop_address} (journal {journal})"),
+    ("❌ No existe el diario {path}", "❌ Journal {path} does not exist"),
+];
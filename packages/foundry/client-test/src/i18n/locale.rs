@@ -0,0 +1,48 @@
+use clap::ValueEnum;
+use std::env;
+use std::sync::OnceLock;
+
+/// Idioma de la salida de la CLI y de los errores.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Locale {
+    #[default]
+    Es,
+    En,
+}
+
+/// Variable de entorno con el idioma cuando no se pasa `--lang`.
+pub const LANG_VAR: &str = "GYRALIS_LANG";
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+impl Locale {
+    /// `es`, `en`, `en_US.UTF-8`... Sólo cuenta el código de idioma.
+    pub fn parse(value: &str) -> Option<Self> {
+        let code = value.split(['_', '-', '.']).next()?.to_lowercase();
+        match code.as_str() {
+            "es" => Some(Self::Es),
+            "en" => Some(Self::En),
+            _ => None,
+        }
+    }
+
+    /// `GYRALIS_LANG`, si no `LANG`; español si ninguna dice un idioma soportado.
+    pub fn from_env() -> Self {
+        [LANG_VAR, "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find_map(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+}
+
+/// Fija el idioma del proceso. Hay que llamarlo antes de imprimir nada: una
+/// vez leído, el idioma ya no cambia.
+pub fn set_locale(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+/// Idioma en uso; sin `set_locale` sale del entorno.
+pub fn locale() -> Locale {
+    *LOCALE.get_or_init(Locale::from_env)
+}
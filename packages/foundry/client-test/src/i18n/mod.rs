@@ -0,0 +1,29 @@
+pub mod catalog;
+pub use catalog::*;
+pub mod locale;
+pub use locale::*;
+
+/// Traduce un mensaje del catálogo y rellena sus `{nombre}`. El mensaje es el
+/// texto en español; `nombre = valor` usa `Display` y `nombre = ?valor` `Debug`.
+///
+/// ```text
+/// t!("Loop creado en {address} (TX {tx})", address = ?loop_address, tx = ?tx_hash)
+/// ```
+#[macro_export]
+macro_rules! t {
+    (@args [$($done:tt)*] $name:ident = ?$value:expr $(, $($rest:tt)*)?) => {
+        $crate::t!(@args [$($done)* (stringify!($name), format!("{:?}", $value)),] $($($rest)*)?)
+    };
+    (@args [$($done:tt)*] $name:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::t!(@args [$($done)* (stringify!($name), ($value).to_string()),] $($($rest)*)?)
+    };
+    (@args [$($done:tt)*]) => {
+        [$($done)*]
+    };
+    ($message:literal $(,)?) => {
+        $crate::i18n::tr($message).to_string()
+    };
+    ($message:literal, $($args:tt)+) => {
+        $crate::i18n::fill($crate::i18n::tr($message), &$crate::t!(@args [] $($args)+))
+    };
+}
@@ -0,0 +1,13 @@
+//! Diario de transacciones en disco. Cada escritura deja constancia de la
+//! intención, la TX firmada (antes de difundirla), su hash y el receipt final,
+//! de modo que si el proceso muere a mitad se retoma sin volver a enviar nada:
+//! lo ya minado se reutiliza y lo firmado se vuelve a difundir tal cual.
+
+pub mod record;
+pub use record::*;
+pub mod sender;
+pub use sender::*;
+pub mod store;
+pub use store::*;
+pub mod workflow;
+pub use workflow::*;
@@ -0,0 +1,141 @@
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Una línea del diario. Las de una misma `key` se aplican en orden.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+pub enum JournalRecord {
+    /// Lo que se quiere enviar; abre un intento nuevo.
+    Intent {
+        key: String,
+        from: Address,
+        to: Address,
+        data: Bytes,
+        value: U256,
+        /// Segundos desde epoch.
+        timestamp: u64,
+    },
+    /// TX firmada. Se guarda antes de difundirla.
+    Signed {
+        key: String,
+        nonce: U256,
+        tx_hash: H256,
+        raw: Bytes,
+    },
+    /// Receipt final, con éxito o revertida.
+    Mined {
+        key: String,
+        receipt: Box<TransactionReceipt>,
+    },
+    /// El intento no llegó a minarse y ya no puede hacerlo (revert en la
+    /// simulación, rechazo del nodo o nonce ocupado por otra TX).
+    Failed { key: String, reason: String },
+}
+
+impl JournalRecord {
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Intent { key, .. }
+            | Self::Signed { key, .. }
+            | Self::Mined { key, .. }
+            | Self::Failed { key, .. } => key,
+        }
+    }
+}
+
+/// En qué punto se quedó una entrada.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryStatus {
+    /// Hay intención pero no TX firmada: no se envió nada.
+    Intended,
+    /// Firmada y quizá difundida, sin receipt todavía.
+    Signed,
+    Mined,
+    Reverted,
+    Failed,
+}
+
+/// Estado de una `key` tras aplicar sus líneas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    pub key: String,
+    pub from: Address,
+    pub to: Address,
+    pub data: Bytes,
+    pub value: U256,
+    pub nonce: Option<U256>,
+    pub tx_hash: Option<H256>,
+    pub raw: Option<Bytes>,
+    pub receipt: Option<TransactionReceipt>,
+    pub failure: Option<String>,
+    /// Intentos abiertos para esta `key`.
+    pub attempts: u32,
+}
+
+impl JournalEntry {
+    pub fn status(&self) -> EntryStatus {
+        match (&self.receipt, &self.failure, &self.tx_hash) {
+            (Some(receipt), _, _) if receipt.status == Some(U64::one()) => EntryStatus::Mined,
+            (Some(_), _, _) => EntryStatus::Reverted,
+            (None, Some(_), _) => EntryStatus::Failed,
+            (None, None, Some(_)) => EntryStatus::Signed,
+            (None, None, None) => EntryStatus::Intended,
+        }
+    }
+
+    /// `true` si la entrada es de la misma llamada (remitente, destino, datos y valor).
+    pub fn same_call(&self, from: Address, to: Address, data: &Bytes, value: U256) -> bool {
+        self.from == from && self.to == to && &self.data == data && self.value == value
+    }
+
+    /// Crea la entrada con su primer `Intent`; `None` para cualquier otra línea.
+    pub(crate) fn open(record: &JournalRecord) -> Option<Self> {
+        let JournalRecord::Intent {
+            key,
+            from,
+            to,
+            data,
+            value,
+            ..
+        } = record
+        else {
+            return None;
+        };
+        Some(Self {
+            key: key.clone(),
+            from: *from,
+            to: *to,
+            data: data.clone(),
+            value: *value,
+            nonce: None,
+            tx_hash: None,
+            raw: None,
+            receipt: None,
+            failure: None,
+            attempts: 1,
+        })
+    }
+
+    pub(crate) fn apply(&mut self, record: &JournalRecord) {
+        match record {
+            JournalRecord::Intent { .. } => {
+                let attempts = self.attempts + 1;
+                if let Some(entry) = Self::open(record) {
+                    *self = Self { attempts, ..entry };
+                }
+            }
+            JournalRecord::Signed {
+                nonce,
+                tx_hash,
+                raw,
+                ..
+            } => {
+                self.nonce = Some(*nonce);
+                self.tx_hash = Some(*tx_hash);
+                self.raw = Some(raw.clone());
+            }
+            JournalRecord::Mined { receipt, .. } => self.receipt = Some(*receipt.clone()),
+            JournalRecord::Failed { reason, .. } => self.failure = Some(reason.clone()),
+        }
+    }
+}
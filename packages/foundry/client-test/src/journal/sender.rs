@@ -0,0 +1,278 @@
+use ethers::abi::Detokenize;
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, instrument, warn, Span};
+
+use crate::error::{ClientError, ClientResult};
+use crate::journal::{EntryStatus, Journal, JournalRecord};
+use crate::t;
+use crate::telemetry;
+use crate::utils::SignerClient;
+
+/// Resultado de una escritura pasada por el diario.
+#[derive(Debug, Clone)]
+pub struct JournaledTx {
+    pub key: String,
+    pub tx_hash: H256,
+    pub receipt: TransactionReceipt,
+    /// `true` si no se firmó nada nuevo: ya estaba minada o se retomó la TX guardada.
+    pub resumed: bool,
+}
+
+/// Qué contestó el nodo al difundir una TX firmada.
+enum Broadcast {
+    Accepted,
+    /// El nodo la rechazó explícitamente: no está en su mempool.
+    Rejected(String),
+}
+
+impl Journal {
+    /// Envía `call` una sola vez para `key`. Si la entrada ya tiene receipt lo
+    /// devuelve; si quedó firmada la vuelve a difundir y espera; si no, simula,
+    /// firma, guarda la TX firmada y sólo entonces la difunde. `client` es el
+    /// signer con el que se construyó `call`.
+    #[instrument(skip(self, client, call), fields(from = ?client.address(), tx_hash))]
+    pub async fn send<P, D>(
+        &mut self,
+        key: &str,
+        client: &SignerClient<P>,
+        call: ContractCall<SignerClient<P>, D>,
+    ) -> ClientResult<JournaledTx>
+    where
+        P: JsonRpcClient + 'static,
+        D: Detokenize,
+    {
+        let from = client.address();
+        let to = call.tx.to_addr().copied().unwrap_or_default();
+        let data = call.tx.data().cloned().unwrap_or_default();
+        let value = call.tx.value().copied().unwrap_or_default();
+
+        if let Some(entry) = self.entry(key) {
+            let status = entry.status();
+            if matches!(status, EntryStatus::Mined | EntryStatus::Signed)
+                && !entry.same_call(from, to, &data, value)
+            {
+                return Err(ClientError::config(t!(
+                    "la entrada {key} del diario es de otra llamada",
+                    key = key
+                )));
+            }
+            match (
+                status,
+                entry.receipt.clone(),
+                entry.tx_hash,
+                entry.raw.clone(),
+            ) {
+                (EntryStatus::Mined, Some(receipt), _, _) => {
+                    info!(tx_hash = ?receipt.transaction_hash, "ya minada, no se reenvía");
+                    return Ok(JournaledTx {
+                        key: key.to_string(),
+                        tx_hash: receipt.transaction_hash,
+                        receipt,
+                        resumed: true,
+                    });
+                }
+                (EntryStatus::Signed, _, Some(tx_hash), Some(raw)) => {
+                    if let Some(done) = self.resume(key, client, tx_hash, raw).await? {
+                        return Ok(done);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.fresh(key, client, &call, data).await
+    }
+
+    /// Como `send`, pero una entrada ya minada no se reutiliza: sólo se retoma
+    /// la TX firmada que quedó a medias. Es lo que usan los comandos sueltos,
+    /// en los que repetir la orden es pedir otra TX.
+    pub async fn resume_or_send<P, D>(
+        &mut self,
+        key: &str,
+        client: &SignerClient<P>,
+        call: ContractCall<SignerClient<P>, D>,
+    ) -> ClientResult<JournaledTx>
+    where
+        P: JsonRpcClient + 'static,
+        D: Detokenize,
+    {
+        if self
+            .entry(key)
+            .is_some_and(|entry| entry.status() == EntryStatus::Mined)
+        {
+            let data = call.tx.data().cloned().unwrap_or_default();
+            return self.fresh(key, client, &call, data).await;
+        }
+        self.send(key, client, call).await
+    }
+
+    /// Retoma una TX firmada. `None` si el nodo la rechaza y no se minó: el
+    /// intento queda fallido y hay que firmar otra.
+    async fn resume<P: JsonRpcClient + 'static>(
+        &mut self,
+        key: &str,
+        client: &SignerClient<P>,
+        tx_hash: H256,
+        raw: Bytes,
+    ) -> ClientResult<Option<JournaledTx>> {
+        Span::current().record("tx_hash", tracing::field::debug(tx_hash));
+        let provider = client.provider();
+        if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? {
+            info!("la TX guardada ya estaba minada");
+            return self.mined(key, receipt, true).map(Some);
+        }
+        match broadcast(provider, raw).await? {
+            Broadcast::Accepted => {
+                info!("TX guardada difundida de nuevo");
+                self.wait(key, provider, tx_hash, true).await.map(Some)
+            }
+            Broadcast::Rejected(reason) => {
+                // Pudo minarse entre la consulta y el reenvío ("nonce too low")
+                if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? {
+                    return self.mined(key, receipt, true).map(Some);
+                }
+                warn!(%reason, "la TX guardada ya no puede minarse, se firma otra");
+                self.append(JournalRecord::Failed {
+                    key: key.to_string(),
+                    reason,
+                })?;
+                Ok(None)
+            }
+        }
+    }
+
+    async fn fresh<P, D>(
+        &mut self,
+        key: &str,
+        client: &SignerClient<P>,
+        call: &ContractCall<SignerClient<P>, D>,
+        data: Bytes,
+    ) -> ClientResult<JournaledTx>
+    where
+        P: JsonRpcClient + 'static,
+        D: Detokenize,
+    {
+        self.append(JournalRecord::Intent {
+            key: key.to_string(),
+            from: client.address(),
+            to: call.tx.to_addr().copied().unwrap_or_default(),
+            data,
+            value: call.tx.value().copied().unwrap_or_default(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+        })?;
+
+        // Un revert se detecta antes de gastar nonce
+        if let Err(e) = call.call().await {
+            let error = ClientError::from(e);
+            if let ClientError::Revert { reason, .. } = &error {
+                self.append(JournalRecord::Failed {
+                    key: key.to_string(),
+                    reason: reason.clone(),
+                })?;
+            }
+            return Err(error);
+        }
+
+        let mut tx = call.tx.clone();
+        client.fill_transaction(&mut tx, None).await?;
+        let signature = client.signer().sign_transaction(&tx).await?;
+        let raw = tx.rlp_signed(&signature);
+        let tx_hash = H256::from(keccak256(&raw));
+        Span::current().record("tx_hash", tracing::field::debug(tx_hash));
+        self.append(JournalRecord::Signed {
+            key: key.to_string(),
+            nonce: tx.nonce().copied().unwrap_or_default(),
+            tx_hash,
+            raw: raw.clone(),
+        })?;
+
+        let provider = client.provider();
+        match broadcast(provider, raw).await {
+            Ok(Broadcast::Accepted) => {
+                telemetry::tx_sent("journal");
+                info!("TX firmada y difundida");
+                self.wait(key, provider, tx_hash, false).await
+            }
+            Ok(Broadcast::Rejected(reason)) => {
+                telemetry::tx_rejected("journal", &reason);
+                self.append(JournalRecord::Failed {
+                    key: key.to_string(),
+                    reason: reason.clone(),
+                })?;
+                Err(ClientError::Transport(ProviderError::CustomError(reason)))
+            }
+            // Sin respuesta clara la TX pudo llegar al nodo: queda firmada y se
+            // retoma en la siguiente ejecución
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn wait<P: JsonRpcClient>(
+        &mut self,
+        key: &str,
+        provider: &Provider<P>,
+        tx_hash: H256,
+        resumed: bool,
+    ) -> ClientResult<JournaledTx> {
+        let receipt = PendingTransaction::new(tx_hash, provider)
+            .await?
+            .ok_or(ClientError::NotConfirmed(tx_hash))?;
+        self.mined(key, receipt, resumed)
+    }
+
+    fn mined(
+        &mut self,
+        key: &str,
+        receipt: TransactionReceipt,
+        resumed: bool,
+    ) -> ClientResult<JournaledTx> {
+        let success = receipt.status == Some(U64::one());
+        telemetry::tx_mined("journal", success);
+        self.append(JournalRecord::Mined {
+            key: key.to_string(),
+            receipt: Box::new(receipt.clone()),
+        })?;
+        if !success {
+            return Err(ClientError::Revert {
+                reason: t!(
+                    "la TX {tx} revirtió al minarse",
+                    tx = ?receipt.transaction_hash
+                ),
+                data: Bytes::default(),
+                custom: None,
+            });
+        }
+        Ok(JournaledTx {
+            key: key.to_string(),
+            tx_hash: receipt.transaction_hash,
+            receipt,
+            resumed,
+        })
+    }
+}
+
+async fn broadcast<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    raw: Bytes,
+) -> ClientResult<Broadcast> {
+    match provider.send_raw_transaction(raw).await {
+        Ok(_) => Ok(Broadcast::Accepted),
+        Err(e) => {
+            let message = e.to_string().to_lowercase();
+            if message.contains("already known")
+                || message.contains("known transaction")
+                || message.contains("already imported")
+            {
+                return Ok(Broadcast::Accepted);
+            }
+            match RpcError::as_error_response(&e) {
+                Some(_) => Ok(Broadcast::Rejected(e.to_string())),
+                None => Err(e.into()),
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::error::{ClientError, ClientResult};
+use crate::journal::{EntryStatus, JournalEntry, JournalRecord};
+use crate::t;
+
+/// Diario en un fichero JSON Lines de sólo añadir. Cada línea se sincroniza
+/// con el disco antes de seguir; una línea a medias al final (el proceso murió
+/// escribiéndola) se descarta al abrir. Mientras está abierto, el fichero
+/// queda bloqueado en exclusiva para este proceso.
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    file: File,
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Abre o crea el diario en `path` y reconstruye el estado de cada entrada.
+    pub fn open(path: impl AsRef<Path>) -> ClientResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let io = |e: std::io::Error| {
+            ClientError::config(t!(
+                "no se pudo abrir el diario {path}: {error}",
+                path = path.display(),
+                error = e
+            ))
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(io)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(io)?;
+        // Dos procesos sobre el mismo diario podrían firmar la misma entrada dos
+        // veces: el segundo falla en vez de esperar. El lock se suelta al cerrar.
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                return Err(ClientError::config(t!(
+                    "el diario {path} está en uso por otro proceso",
+                    path = path.display()
+                )))
+            }
+            Err(TryLockError::Error(e)) => return Err(io(e)),
+        }
+        let contents = fs::read_to_string(&path).map_err(io)?;
+
+        let complete = contents.rfind('\n').map_or(0, |end| end + 1);
+        if complete < contents.len() {
+            warn!(path = %path.display(), "línea incompleta al final del diario, se descarta");
+            file.set_len(complete as u64).map_err(io)?;
+        }
+
+        let mut journal = Self {
+            path,
+            file,
+            entries: Vec::new(),
+        };
+        for (number, line) in contents[..complete].lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: JournalRecord = serde_json::from_str(line).map_err(|e| {
+                ClientError::decode(t!(
+                    "línea {line} del diario {path} inválida: {error}",
+                    line = number + 1,
+                    path = journal.path.display(),
+                    error = e
+                ))
+            })?;
+            journal.fold(&record);
+        }
+        debug!(path = %journal.path.display(), entries = journal.entries.len(), "diario cargado");
+        Ok(journal)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Entradas en el orden en que se abrieron.
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    pub fn entry(&self, key: &str) -> Option<&JournalEntry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    /// Entradas firmadas sin receipt: las que hay que retomar.
+    pub fn in_flight(&self) -> impl Iterator<Item = &JournalEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status() == EntryStatus::Signed)
+    }
+
+    /// Escribe `record` y lo sincroniza con el disco antes de aplicarlo.
+    pub fn append(&mut self, record: JournalRecord) -> ClientResult<()> {
+        let mut line =
+            serde_json::to_string(&record).map_err(|e| ClientError::decode(e.to_string()))?;
+        line.push('\n');
+        let io = |e: std::io::Error| {
+            ClientError::config(t!(
+                "no se pudo escribir en el diario {path}: {error}",
+                path = self.path.display(),
+                error = e
+            ))
+        };
+        self.file.write_all(line.as_bytes()).map_err(io)?;
+        self.file.sync_data().map_err(io)?;
+        self.fold(&record);
+        Ok(())
+    }
+
+    fn fold(&mut self, record: &JournalRecord) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.key == record.key())
+        {
+            Some(entry) => entry.apply(record),
+            None => match JournalEntry::open(record) {
+                Some(entry) => self.entries.push(entry),
+                None => warn!(key = record.key(), "línea del diario sin intención previa"),
+            },
+        }
+    }
+}
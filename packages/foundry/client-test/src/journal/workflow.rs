@@ -0,0 +1,98 @@
+use ethers::prelude::*;
+use tracing::{info, instrument};
+
+use crate::error::{ClientError, ClientResult};
+use crate::events::loop_created_in_receipt;
+use crate::functions::{
+    create_loop_call, create_organization_call, organization_created_in_receipt, transfer_call,
+};
+use crate::journal::{Journal, JournaledTx};
+use crate::t;
+use crate::Env;
+
+/// Alta completa: organización, loop con el token del deployment y fondeo.
+/// Cada paso es una entrada `{id}/{paso}` del diario, así que volver a
+/// ejecutarlo con el mismo `id` continúa donde se quedó.
+#[derive(Debug, Clone)]
+pub struct Onboarding {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub admin: Address,
+    pub period_length: U256,
+    pub percent_per_period: U256,
+    /// Unidades mínimas del token; con 0 no se fondea.
+    pub funding: U256,
+}
+
+#[derive(Debug, Clone)]
+pub struct OnboardingResult {
+    pub organization: Address,
+    pub loop_address: Address,
+    pub token: Address,
+    /// Los pasos enviados o retomados, en orden.
+    pub steps: Vec<JournaledTx>,
+}
+
+impl Onboarding {
+    pub const CREATE_ORGANIZATION: &'static str = "create_organization";
+    pub const CREATE_LOOP: &'static str = "create_loop";
+    pub const FUND_LOOP: &'static str = "fund_loop";
+
+    pub fn key(&self, step: &str) -> String {
+        format!("{}/{}", self.id, step)
+    }
+
+    #[instrument(skip_all, fields(id = %self.id))]
+    pub async fn run(&self, env: &Env, journal: &mut Journal) -> ClientResult<OnboardingResult> {
+        let signer = env.signer()?;
+        let created = journal
+            .send(
+                &self.key(Self::CREATE_ORGANIZATION),
+                &signer,
+                create_organization_call(env, &self.name, self.admin, &self.description)?,
+            )
+            .await?;
+        let organization = organization_created_in_receipt(&created.receipt)?;
+        info!(organization = ?organization, resumed = created.resumed, "organización lista");
+
+        let loop_tx = journal
+            .send(
+                &self.key(Self::CREATE_LOOP),
+                &signer,
+                create_loop_call(
+                    env,
+                    organization,
+                    self.period_length,
+                    self.percent_per_period,
+                )?,
+            )
+            .await?;
+        let event = loop_created_in_receipt(&loop_tx.receipt, organization)?.ok_or_else(|| {
+            ClientError::decode(t!(
+                "no se encontró {event} en el receipt",
+                event = "LoopCreated"
+            ))
+        })?;
+        info!(loop_address = ?event.loop_address, resumed = loop_tx.resumed, "loop listo");
+
+        let mut steps = vec![created, loop_tx];
+        if !self.funding.is_zero() {
+            let funded = journal
+                .send(
+                    &self.key(Self::FUND_LOOP),
+                    &signer,
+                    transfer_call(env, event.token, event.loop_address, self.funding)?,
+                )
+                .await?;
+            info!(resumed = funded.resumed, "loop fondeado");
+            steps.push(funded);
+        }
+        Ok(OnboardingResult {
+            organization,
+            loop_address: event.loop_address,
+            token: event.token,
+            steps,
+        })
+    }
+}
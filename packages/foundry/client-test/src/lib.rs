@@ -0,0 +1,26 @@
+pub mod error;
+pub use error::*;
+
+pub mod utils;
+pub use utils::{get_provider, setup_env::Env};
+
+pub mod i18n;
+
+pub mod functions;
+pub use functions::create_loop::*;
+pub mod access;
+pub mod analytics;
+pub mod batch;
+pub mod bench;
+pub mod bot;
+pub mod cli;
+pub mod console;
+pub mod dashboard;
+pub mod diamond;
+pub mod engine;
+pub mod events;
+pub mod journal;
+pub mod scenarios;
+pub mod storage;
+pub mod telemetry;
+pub mod transport;
@@ -1,8 +0,0 @@
-pub mod utils;
-pub use utils::*;
-
-pub mod events;
-pub use events::*;
-
-pub mod functions;
-pub use functions::*;
@@ -0,0 +1,109 @@
+use ethers::core::rand::thread_rng;
+use ethers::prelude::*;
+use ethers::utils::parse_ether;
+use eyre::Result;
+use tokio::time::{Duration, Instant};
+
+use crate::engine::{ResubmitPolicy, TxEngine};
+use crate::functions::{current_period, loop_details, sign_eligibility};
+use crate::t;
+use crate::utils::{set_balance, warp};
+use crate::Env;
+
+/// Parámetros de la prueba de carga.
+#[derive(Debug, Clone, Copy)]
+pub struct LoadTest {
+    /// Wallets nuevas que hacen `claimAndRegister`.
+    pub wallets: usize,
+    /// Transferencias de 0 ETH a sí misma que encola cada wallet detrás del
+    /// claim, para tener varios nonces en vuelo por signer.
+    pub transfers_per_wallet: usize,
+    /// Tamaño de la cola del motor.
+    pub concurrency: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    pub submitted: usize,
+    pub succeeded: usize,
+    pub reverted: usize,
+    pub failed: usize,
+    pub bumps: u32,
+    pub retries: u32,
+    pub elapsed: Duration,
+    pub errors: Vec<String>,
+}
+
+impl LoadTest {
+    /// Lanza la carga contra anvil: crea y financia las wallets con
+    /// `anvil_setBalance` y envía todo a través de [`TxEngine`].
+    pub async fn run(&self, env: &Env, loop_address: Address) -> Result<LoadReport> {
+        let trusted: LocalWallet = env.trusted_signer_pk.parse()?;
+        let loop_facet = env.contract_at(loop_address, "LoopFacet")?;
+        let mut engine = TxEngine::new(env, self.concurrency, ResubmitPolicy::default())?;
+
+        // Periodo limpio para que ninguna wallet llegue ya registrada
+        let details = loop_details(env, loop_address).await?;
+        warp(engine.provider(), details.period_length.as_u64()).await?;
+        let next_period = current_period(env, loop_address).await? + 1;
+
+        let mut txs = Vec::new();
+        for _ in 0..self.wallets {
+            let wallet = engine.add_wallet(LocalWallet::new(&mut thread_rng()));
+            set_balance(engine.provider(), wallet, parse_ether(10)?).await?;
+
+            let signature = sign_eligibility(&trusted, wallet, next_period, loop_address).await?;
+            let claim = loop_facet.method::<Bytes, ()>("claimAndRegister", signature)?;
+            txs.push((wallet, claim.tx));
+            for _ in 0..self.transfers_per_wallet {
+                let transfer = TransactionRequest::new().to(wallet).value(0).gas(21_000);
+                txs.push((wallet, transfer.into()));
+            }
+        }
+
+        let started = Instant::now();
+        let outcomes = engine.submit_all(txs).await;
+        let mut report = LoadReport {
+            submitted: outcomes.len(),
+            elapsed: started.elapsed(),
+            ..Default::default()
+        };
+        for outcome in outcomes {
+            match outcome {
+                Ok(outcome) => {
+                    report.bumps += outcome.bumps;
+                    report.retries += outcome.retries;
+                    if outcome.succeeded() {
+                        report.succeeded += 1;
+                    } else {
+                        report.reverted += 1;
+                    }
+                }
+                Err(e) => {
+                    report.failed += 1;
+                    report.errors.push(e.to_string());
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+pub fn print_load_report(report: &LoadReport) {
+    let seconds = report.elapsed.as_secs_f64();
+    println!("{:<15}{}", t!("TX enviadas:"), report.submitted);
+    println!("  {:<13}{}", "ok:", report.succeeded);
+    println!("  {:<13}{}", t!("revertidas:"), report.reverted);
+    println!("  {:<13}{}", t!("fallidas:"), report.failed);
+    println!("{:<15}{}", t!("Subidas gas:"), report.bumps);
+    println!("{:<15}{}", t!("Reintentos:"), report.retries);
+    println!(
+        "{:<15}{:.2}s ({:.1} tx/s)",
+        t!("Duración:"),
+        seconds,
+        report.submitted as f64 / seconds.max(f64::EPSILON)
+    );
+    for error in report.errors.iter().take(10) {
+        println!("  {}", error);
+    }
+}
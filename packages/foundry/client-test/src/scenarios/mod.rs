@@ -0,0 +1,4 @@
+pub mod load;
+pub mod sybil;
+pub use load::*;
+pub use sybil::*;
@@ -0,0 +1,292 @@
+use ethers::core::rand::thread_rng;
+use ethers::prelude::*;
+use ethers::providers::RpcError;
+use eyre::Result;
+use futures::future::join;
+use std::sync::Arc;
+
+use crate::functions::{create_loop_confirmed, current_period, loop_details, sign_eligibility};
+use crate::t;
+use crate::utils::{warp, SelectorBook, SignerClient};
+use crate::Env;
+
+const INVALID_SIGNATURE: &str = "Invalid eligibility signature";
+
+/// Resultado de un caso de ataque.
+#[derive(Debug, Clone)]
+pub struct ScenarioResult {
+    pub name: &'static str,
+    pub expected: String,
+    pub observed: String,
+    pub passed: bool,
+}
+
+/// Qué pasó al enviar una transacción.
+#[derive(Debug, Clone)]
+pub enum Attempt {
+    Success(H256),
+    Reverted(String),
+}
+
+impl Attempt {
+    fn describe(&self) -> String {
+        match self {
+            Attempt::Success(tx_hash) => format!("ok (TX {:?})", tx_hash),
+            Attempt::Reverted(reason) => format!("revert: {}", reason),
+        }
+    }
+
+    fn reverted_with(&self, expected: &str) -> bool {
+        matches!(self, Attempt::Reverted(reason) if reason.contains(expected))
+    }
+}
+
+/// Suite de ataques Sybil: el `bad_signer` intenta reclamar más de lo que le toca.
+pub struct SybilSuite<'a> {
+    env: &'a Env,
+    book: SelectorBook,
+    attacker: Arc<SignerClient>,
+    trusted: LocalWallet,
+    loop_a: Address,
+    loop_b: Option<Address>,
+}
+
+impl<'a> SybilSuite<'a> {
+    pub fn new(env: &'a Env, loop_a: Address, loop_b: Option<Address>) -> Result<Self> {
+        let attacker = env
+            .bad_signer
+            .clone()
+            .ok_or_else(|| eyre::eyre!(t!("❌ No hay bad_signer disponible")))?;
+        Ok(Self {
+            env,
+            book: SelectorBook::from_artifacts()?,
+            attacker,
+            trusted: env.trusted_signer_pk.parse()?,
+            loop_a,
+            loop_b,
+        })
+    }
+
+    fn loop_as(
+        &self,
+        loop_address: Address,
+        client: Arc<SignerClient>,
+    ) -> Result<Contract<SignerClient>> {
+        Ok(self
+            .env
+            .contract_at(loop_address, "LoopFacet")?
+            .connect(client))
+    }
+
+    fn claim_and_register(
+        &self,
+        loop_address: Address,
+        signature: Bytes,
+    ) -> Result<ContractCall<SignerClient, ()>> {
+        Ok(self
+            .loop_as(loop_address, self.attacker.clone())?
+            .method::<Bytes, ()>("claimAndRegister", signature)?)
+    }
+
+    async fn sign(&self, next_period: U256, loop_address: Address) -> Result<Bytes> {
+        Ok(sign_eligibility(
+            &self.trusted,
+            self.attacker.address(),
+            next_period,
+            loop_address,
+        )
+        .await?)
+    }
+
+    /// Envía la llamada y traduce el resultado. Si la TX se mina revertida, se
+    /// repite como `eth_call` en ese bloque para recuperar el motivo.
+    async fn submit(&self, call: &ContractCall<SignerClient, ()>) -> Attempt {
+        let pending = match call.send().await {
+            Ok(pending) => pending,
+            Err(e) => {
+                return Attempt::Reverted(match e.as_revert() {
+                    Some(data) => self.book.decode_revert(data),
+                    None => e.to_string(),
+                })
+            }
+        };
+        match pending.await {
+            Ok(Some(receipt)) if receipt.status == Some(U64::one()) => {
+                Attempt::Success(receipt.transaction_hash)
+            }
+            Ok(Some(receipt)) => Attempt::Reverted(self.replay(call, receipt.block_number).await),
+            Ok(None) => Attempt::Reverted(t!("TX descartada")),
+            Err(e) => Attempt::Reverted(e.to_string()),
+        }
+    }
+
+    async fn replay(&self, call: &ContractCall<SignerClient, ()>, block: Option<U64>) -> String {
+        let provider = match self.env.provider() {
+            Ok(provider) => provider,
+            Err(e) => return e.to_string(),
+        };
+        match provider.call(&call.tx, block.map(Into::into)).await {
+            Ok(_) => t!("revert no reproducible"),
+            Err(e) => match RpcError::as_error_response(&e).and_then(|r| r.as_revert_data()) {
+                Some(data) => self.book.decode_revert(&data),
+                None => e.to_string(),
+            },
+        }
+    }
+
+    fn result(name: &'static str, expected: &str, attempt: &Attempt) -> ScenarioResult {
+        let passed = if expected == "ok" {
+            matches!(attempt, Attempt::Success(_))
+        } else {
+            attempt.reverted_with(expected)
+        };
+        ScenarioResult {
+            name,
+            expected: expected.to_string(),
+            observed: attempt.describe(),
+            passed,
+        }
+    }
+
+    /// Corre todos los casos en orden. Necesita anvil (usa `evm_increaseTime`).
+    pub async fn run(&self) -> Result<Vec<ScenarioResult>> {
+        let provider = self.env.provider()?;
+        let details = loop_details(self.env, self.loop_a).await?;
+        let period_length = details.period_length.as_u64();
+        let mut results = Vec::new();
+
+        // Empezamos en un periodo limpio para no arrastrar registros previos
+        warp(&provider, period_length).await?;
+
+        // 0) Caso base: firma válida para el siguiente periodo
+        let period = current_period(self.env, self.loop_a).await?;
+        let first_signature = self.sign(period + 1, self.loop_a).await?;
+        let attempt = self
+            .submit(&self.claim_and_register(self.loop_a, first_signature.clone())?)
+            .await;
+        results.push(Self::result("valid claimAndRegister", "ok", &attempt));
+
+        // 1) claim() sin estar registrado para el periodo en curso
+        let claim = self
+            .loop_as(self.loop_a, self.attacker.clone())?
+            .method::<(), ()>("claim", ())?;
+        let attempt = self.submit(&claim).await;
+        results.push(Self::result(
+            "claim without register",
+            "CannotClaim",
+            &attempt,
+        ));
+
+        // 2) Dos claimAndRegister simultáneos con la misma firma
+        warp(&provider, period_length).await?;
+        let period = current_period(self.env, self.loop_a).await?;
+        let signature = self.sign(period + 1, self.loop_a).await?;
+        let nonce = provider
+            .get_transaction_count(self.attacker.address(), Some(BlockNumber::Pending.into()))
+            .await?;
+        let mut first = self.claim_and_register(self.loop_a, signature.clone())?;
+        let mut second = self.claim_and_register(self.loop_a, signature)?;
+        first.tx.set_nonce(nonce).set_gas(500_000u64);
+        second.tx.set_nonce(nonce + 1).set_gas(500_000u64);
+        let (a, b) = join(self.submit(&first), self.submit(&second)).await;
+        let successes = [&a, &b]
+            .iter()
+            .filter(|x| matches!(x, Attempt::Success(_)))
+            .count();
+        let rejected = a.reverted_with("AlreadyRegistered") || b.reverted_with("AlreadyRegistered");
+        results.push(ScenarioResult {
+            name: "concurrent double claimAndRegister",
+            expected: "1 ok + AlreadyRegistered".to_string(),
+            observed: format!("{} | {}", a.describe(), b.describe()),
+            passed: successes == 1 && rejected,
+        });
+
+        // 3) Reutilizar la firma del caso base en un periodo posterior
+        warp(&provider, period_length).await?;
+        let attempt = self
+            .submit(&self.claim_and_register(self.loop_a, first_signature)?)
+            .await;
+        results.push(Self::result(
+            "signature replay across periods",
+            INVALID_SIGNATURE,
+            &attempt,
+        ));
+
+        // 4) y 5) necesitan un segundo loop
+        let loop_b = match self.loop_b {
+            Some(loop_b) => loop_b,
+            None => {
+                let organization = self.env.deployment_address("organization")?;
+                create_loop_confirmed(self.env, organization, details.period_length, U256::from(5))
+                    .await?
+                    .1
+            }
+        };
+
+        // 4) Firma emitida para el loop A usada en el loop B
+        let period_b = current_period(self.env, loop_b).await?;
+        let signature = self.sign(period_b + 1, self.loop_a).await?;
+        let attempt = self
+            .submit(&self.claim_and_register(loop_b, signature)?)
+            .await;
+        results.push(Self::result(
+            "signature replay across loops",
+            INVALID_SIGNATURE,
+            &attempt,
+        ));
+
+        // 5) Firma de un signer rotado: el admin cambia el trusted signer de B
+        let rotate = self
+            .loop_as(loop_b, self.env.signer()?)?
+            .method::<Address, ()>(
+                "setTrustedBackendSigner",
+                LocalWallet::new(&mut thread_rng()).address(),
+            )?;
+        let attempt = match self.submit(&rotate).await {
+            Attempt::Success(_) => {
+                let signature = self.sign(period_b + 1, loop_b).await?;
+                self.submit(&self.claim_and_register(loop_b, signature)?)
+                    .await
+            }
+            Attempt::Reverted(reason) => {
+                Attempt::Reverted(t!("no se pudo rotar el signer: {reason}", reason = reason))
+            }
+        };
+        results.push(Self::result(
+            "rotated-out signer",
+            INVALID_SIGNATURE,
+            &attempt,
+        ));
+
+        Ok(results)
+    }
+}
+
+pub fn print_scenario_report(results: &[ScenarioResult]) {
+    println!(
+        "{:<38} {:<9} {:<32} {}",
+        t!("CASO"),
+        t!("RESULTADO"),
+        t!("ESPERADO"),
+        t!("OBSERVADO")
+    );
+    for result in results {
+        println!(
+            "{:<38} {:<9} {:<32} {}",
+            result.name,
+            if result.passed { "PASS" } else { "FAIL" },
+            result.expected,
+            result.observed
+        );
+    }
+    let passed = results.iter().filter(|r| r.passed).count();
+    println!();
+    println!(
+        "{}",
+        t!(
+            "{passed}/{total} casos superados",
+            passed = passed,
+            total = results.len()
+        )
+    );
+}
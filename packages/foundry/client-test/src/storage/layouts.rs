@@ -0,0 +1,172 @@
+use ethers::prelude::*;
+use eyre::Result;
+
+use crate::storage::{
+    address_key, mapping_slot, namespace_slot, read_address, read_slot, read_string, read_uint,
+    selector_key, uint_key,
+};
+use crate::Env;
+
+pub const LOOP_STORAGE_NAMESPACE: &str = "diamond.loop.storage";
+pub const ORGANIZATION_STORAGE_NAMESPACE: &str = "diamond.organization.storage";
+pub const ACCESS_CONTROL_STORAGE_NAMESPACE: &str = "access-control.storage";
+
+/// Offsets de los campos de `LoopStorage.Layout` respecto al slot base.
+pub mod loop_slots {
+    pub const LOOP_ADMIN: u64 = 0;
+    pub const TOKEN: u64 = 1;
+    pub const PERIOD_LENGTH: u64 = 2;
+    pub const PERCENT_PER_PERIOD: u64 = 3;
+    pub const FIRST_PERIOD_START: u64 = 4;
+    pub const TRUSTED_BACKEND_SIGNER: u64 = 5;
+    pub const CLAIMERS: u64 = 6;
+    pub const PERIODS: u64 = 7;
+}
+
+/// Offsets de los mappings de `AccessControlStorage.Layout`.
+pub mod access_control_slots {
+    pub const USER_ROLES: u64 = 0;
+    pub const FUNCTION_ROLES: u64 = 1;
+}
+
+/// Campos escalares de `LoopStorage.Layout` (los mappings se leen aparte).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoopLayout {
+    pub loop_admin: Address,
+    pub token: Address,
+    pub period_length: U256,
+    pub percent_per_period: U256,
+    pub first_period_start: U256,
+    pub trusted_backend_signer: Address,
+}
+
+/// `LoopStorage.Claimer`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClaimerSlot {
+    pub registered_for_period: U256,
+    pub latest_claim_period: U256,
+}
+
+/// `LoopStorage.Period`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PeriodSlot {
+    pub total_registered_users: U256,
+    pub max_payout: U256,
+}
+
+/// `OrganizationStorage.Layout`
+#[derive(Debug, Clone, Default)]
+pub struct OrganizationLayout {
+    pub name: String,
+    pub admin: Address,
+    pub description: String,
+}
+
+fn loop_base() -> U256 {
+    namespace_slot(LOOP_STORAGE_NAMESPACE)
+}
+
+/// Slot donde empieza `claimers[user]`.
+pub fn claimer_slot(user: Address) -> U256 {
+    mapping_slot(address_key(user), loop_base() + loop_slots::CLAIMERS)
+}
+
+/// Slot donde empieza `periods[period]`.
+pub fn period_slot(period: U256) -> U256 {
+    mapping_slot(uint_key(period), loop_base() + loop_slots::PERIODS)
+}
+
+/// Slot de `userRoles[user]`.
+pub fn user_roles_slot(user: Address) -> U256 {
+    mapping_slot(
+        address_key(user),
+        namespace_slot(ACCESS_CONTROL_STORAGE_NAMESPACE) + access_control_slots::USER_ROLES,
+    )
+}
+
+/// Slot de `functionRoles[selector]`.
+pub fn function_roles_slot(selector: [u8; 4]) -> U256 {
+    mapping_slot(
+        selector_key(selector),
+        namespace_slot(ACCESS_CONTROL_STORAGE_NAMESPACE) + access_control_slots::FUNCTION_ROLES,
+    )
+}
+
+pub async fn read_loop_layout(env: &Env, loop_address: Address) -> Result<LoopLayout> {
+    let provider = env.provider()?;
+    let base = loop_base();
+    Ok(LoopLayout {
+        loop_admin: read_address(&provider, loop_address, base + loop_slots::LOOP_ADMIN).await?,
+        token: read_address(&provider, loop_address, base + loop_slots::TOKEN).await?,
+        period_length: read_uint(&provider, loop_address, base + loop_slots::PERIOD_LENGTH).await?,
+        percent_per_period: read_uint(
+            &provider,
+            loop_address,
+            base + loop_slots::PERCENT_PER_PERIOD,
+        )
+        .await?,
+        first_period_start: read_uint(
+            &provider,
+            loop_address,
+            base + loop_slots::FIRST_PERIOD_START,
+        )
+        .await?,
+        trusted_backend_signer: read_address(
+            &provider,
+            loop_address,
+            base + loop_slots::TRUSTED_BACKEND_SIGNER,
+        )
+        .await?,
+    })
+}
+
+pub async fn read_claimer(env: &Env, loop_address: Address, user: Address) -> Result<ClaimerSlot> {
+    let provider = env.provider()?;
+    let slot = claimer_slot(user);
+    Ok(ClaimerSlot {
+        registered_for_period: read_uint(&provider, loop_address, slot).await?,
+        latest_claim_period: read_uint(&provider, loop_address, slot + 1).await?,
+    })
+}
+
+pub async fn read_period(env: &Env, loop_address: Address, period: U256) -> Result<PeriodSlot> {
+    let provider = env.provider()?;
+    let slot = period_slot(period);
+    Ok(PeriodSlot {
+        total_registered_users: read_uint(&provider, loop_address, slot).await?,
+        max_payout: read_uint(&provider, loop_address, slot + 1).await?,
+    })
+}
+
+pub async fn read_organization_layout(
+    env: &Env,
+    organization: Address,
+) -> Result<OrganizationLayout> {
+    let provider = env.provider()?;
+    let base = namespace_slot(ORGANIZATION_STORAGE_NAMESPACE);
+    Ok(OrganizationLayout {
+        name: read_string(&provider, organization, base).await?,
+        admin: read_address(&provider, organization, base + 1).await?,
+        description: read_string(&provider, organization, base + 2).await?,
+    })
+}
+
+/// Bitmap `userRoles[user]` de `AccessControlStorage`, esté o no la faceta.
+pub async fn read_user_roles(env: &Env, diamond: Address, user: Address) -> Result<[u8; 32]> {
+    Ok(read_slot(&env.provider()?, diamond, user_roles_slot(user))
+        .await?
+        .0)
+}
+
+/// Bitmap `functionRoles[selector]` de `AccessControlStorage`.
+pub async fn read_function_roles(
+    env: &Env,
+    diamond: Address,
+    selector: [u8; 4],
+) -> Result<[u8; 32]> {
+    Ok(
+        read_slot(&env.provider()?, diamond, function_roles_slot(selector))
+            .await?
+            .0,
+    )
+}
@@ -0,0 +1,4 @@
+pub mod layouts;
+pub mod slots;
+pub use layouts::*;
+pub use slots::*;
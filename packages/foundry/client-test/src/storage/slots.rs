@@ -0,0 +1,138 @@
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use eyre::Result;
+
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+
+/// Slot base de un namespace de diamond storage: `keccak256(namespace)`.
+pub fn namespace_slot(namespace: &str) -> U256 {
+    U256::from_big_endian(&keccak256(namespace))
+}
+
+/// Slot de `mapping[key]` cuando el mapping está en `slot`: `keccak256(key . slot)`.
+pub fn mapping_slot(key: H256, slot: U256) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(key.as_bytes());
+    slot.to_big_endian(&mut preimage[32..]);
+    U256::from_big_endian(&keccak256(preimage))
+}
+
+/// Clave de mapping para una `address` (alineada a la derecha en 32 bytes).
+pub fn address_key(address: Address) -> H256 {
+    H256::from(address)
+}
+
+/// Clave de mapping para un `bytes4` (alineado a la izquierda en 32 bytes).
+pub fn selector_key(selector: [u8; 4]) -> H256 {
+    let mut key = H256::zero();
+    key.0[..4].copy_from_slice(&selector);
+    key
+}
+
+/// Clave de mapping para un `uint256`.
+pub fn uint_key(value: U256) -> H256 {
+    H256::from_uint(&value)
+}
+
+/// Lee un slot crudo con `eth_getStorageAt`.
+pub async fn read_slot<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    contract: Address,
+    slot: U256,
+) -> Result<H256> {
+    Ok(provider
+        .get_storage_at(contract, H256::from_uint(&slot), None)
+        .await?)
+}
+
+pub async fn read_uint<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    contract: Address,
+    slot: U256,
+) -> Result<U256> {
+    Ok(U256::from_big_endian(
+        read_slot(provider, contract, slot).await?.as_bytes(),
+    ))
+}
+
+pub async fn read_address<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    contract: Address,
+    slot: U256,
+) -> Result<Address> {
+    Ok(Address::from_slice(
+        &read_slot(provider, contract, slot).await?.as_bytes()[12..],
+    ))
+}
+
+/// Tope de longitud para un `string` leído de storage. Un slot que no es un
+/// string (p.ej. el contrato no es una organización) puede codificar
+/// cualquier longitud; más allá de esto se trata como corrupto.
+pub const MAX_STORAGE_STRING_LEN: usize = 64 * 1024;
+
+/// Cabecera de un `string` en su slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoredString {
+    /// Menos de 32 bytes: van en el propio slot.
+    Short(String),
+    /// `len` bytes a partir de `keccak256(slot)`.
+    Long { len: usize },
+}
+
+/// Decodifica el slot de un `string`. Si mide menos de 32 bytes va en el
+/// propio slot (último byte = `len * 2`); si no, el slot guarda `len * 2 + 1`.
+pub fn decode_string_slot(word: H256) -> ClientResult<StoredString> {
+    let bytes = word.as_bytes();
+    if bytes[31] & 1 == 0 {
+        let len = (bytes[31] / 2) as usize;
+        if len > 31 {
+            return Err(ClientError::decode(t!(
+                "string corto de {len} bytes en storage",
+                len = len
+            )));
+        }
+        return Ok(StoredString::Short(
+            String::from_utf8_lossy(&bytes[..len]).into_owned(),
+        ));
+    }
+
+    let len = (U256::from_big_endian(bytes) - 1) / 2;
+    if len > U256::from(MAX_STORAGE_STRING_LEN) {
+        return Err(ClientError::decode(t!(
+            "string de {len} bytes en storage: el slot no parece un string",
+            len = len
+        )));
+    }
+    Ok(StoredString::Long {
+        len: len.as_usize(),
+    })
+}
+
+/// Slot donde empiezan los datos de un `string` largo: `keccak256(slot)`.
+pub fn string_data_slot(slot: U256) -> U256 {
+    let mut preimage = [0u8; 32];
+    slot.to_big_endian(&mut preimage);
+    U256::from_big_endian(&keccak256(preimage))
+}
+
+/// Lee un `string` de storage (ver `decode_string_slot`).
+pub async fn read_string<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    contract: Address,
+    slot: U256,
+) -> Result<String> {
+    let len = match decode_string_slot(read_slot(provider, contract, slot).await?)? {
+        StoredString::Short(value) => return Ok(value),
+        StoredString::Long { len } => len,
+    };
+
+    let data_slot = string_data_slot(slot);
+    let mut data = Vec::with_capacity(len);
+    for i in 0..len.div_ceil(32) {
+        let chunk = read_slot(provider, contract, data_slot + i).await?;
+        data.extend_from_slice(chunk.as_bytes());
+    }
+    data.truncate(len);
+    Ok(String::from_utf8_lossy(&data).into_owned())
+}
@@ -0,0 +1,66 @@
+use eyre::Result;
+use metrics::{describe_counter, describe_gauge, describe_histogram, Unit};
+use metrics_exporter_prometheus::PrometheusBuilder;
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use tracing::info;
+
+use crate::t;
+use crate::telemetry::{
+    INDEXER_LAG, LOOP_BALANCE, RPC_ERRORS, RPC_LATENCY, SIGNATURES, TX_CONFIRMED, TX_REJECTED,
+    TX_REVERTED, TX_SENT,
+};
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// `true` si hay un exporter instalado; sirve para no hacer lecturas que sólo
+/// alimentan métricas (saldos de los loops) cuando nadie las va a ver.
+pub fn metrics_enabled() -> bool {
+    ENABLED.get().copied().unwrap_or_default()
+}
+
+/// Levanta el endpoint `/metrics` en `addr`. Sin llamarlo, las métricas no hacen nada.
+pub fn install_exporter(addr: SocketAddr) -> Result<()> {
+    PrometheusBuilder::new()
+        .with_http_listener(addr)
+        .install()
+        .map_err(|e| {
+            eyre::eyre!(t!(
+                "❌ No se pudo levantar /metrics en {addr}: {error}",
+                addr = addr,
+                error = e
+            ))
+        })?;
+    describe_metrics();
+    let _ = ENABLED.set(true);
+    info!(%addr, "exporter de Prometheus escuchando");
+    Ok(())
+}
+
+fn describe_metrics() {
+    describe_counter!(TX_SENT, "Transacciones enviadas");
+    describe_counter!(TX_CONFIRMED, "Transacciones minadas con éxito");
+    describe_counter!(
+        TX_REVERTED,
+        "Transacciones minadas revertidas, por tipo de error"
+    );
+    describe_counter!(
+        TX_REJECTED,
+        "Envíos rechazados antes de minarse, por tipo de error"
+    );
+    describe_counter!(
+        SIGNATURES,
+        "Firmas de elegibilidad emitidas o rechazadas, por política"
+    );
+    describe_gauge!(
+        INDEXER_LAG,
+        Unit::Count,
+        "Bloques por detrás de la cabeza de la cadena"
+    );
+    describe_histogram!(RPC_LATENCY, Unit::Seconds, "Latencia de las llamadas RPC");
+    describe_counter!(RPC_ERRORS, "Llamadas RPC fallidas");
+    describe_gauge!(
+        LOOP_BALANCE,
+        "Saldo de token de cada loop, en unidades del token"
+    );
+}
@@ -0,0 +1,4 @@
+pub mod exporter;
+pub use exporter::*;
+pub mod recorders;
+pub use recorders::*;
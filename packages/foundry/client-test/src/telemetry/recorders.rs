@@ -0,0 +1,72 @@
+use ethers::types::{Address, U256};
+use ethers::utils::format_units;
+use metrics::{counter, gauge};
+
+pub const TX_SENT: &str = "gyralis_tx_sent_total";
+pub const TX_CONFIRMED: &str = "gyralis_tx_confirmed_total";
+pub const TX_REVERTED: &str = "gyralis_tx_reverted_total";
+pub const TX_REJECTED: &str = "gyralis_tx_rejected_total";
+pub const SIGNATURES: &str = "gyralis_signatures_total";
+pub const INDEXER_LAG: &str = "gyralis_indexer_lag_blocks";
+pub const RPC_LATENCY: &str = "gyralis_rpc_request_duration_seconds";
+pub const RPC_ERRORS: &str = "gyralis_rpc_errors_total";
+pub const LOOP_BALANCE: &str = "gyralis_loop_balance";
+
+/// Agrupa un mensaje de error en pocas categorías para usarlo como etiqueta.
+pub fn error_kind(message: &str) -> &'static str {
+    let message = message.to_lowercase();
+    if message.contains("revert") {
+        "revert"
+    } else if message.contains("nonce") {
+        "nonce"
+    } else if message.contains("underpriced") || message.contains("fee") {
+        "gas_price"
+    } else if message.contains("stuck") {
+        "stuck"
+    } else if message.contains("insufficient funds") {
+        "funds"
+    } else if message.contains("error sending request")
+        || message.contains("connect")
+        || message.contains("timed out")
+    {
+        "transport"
+    } else {
+        "other"
+    }
+}
+
+/// `source` identifica quién envía: `engine`, `claim`...
+pub fn tx_sent(source: &'static str) {
+    counter!(TX_SENT, "source" => source).increment(1);
+}
+
+/// Registra el resultado de una TX minada según su `status`.
+pub fn tx_mined(source: &'static str, success: bool) {
+    if success {
+        counter!(TX_CONFIRMED, "source" => source).increment(1);
+    } else {
+        counter!(TX_REVERTED, "source" => source, "error" => "revert").increment(1);
+    }
+}
+
+pub fn tx_rejected(source: &'static str, error: &str) {
+    counter!(TX_REJECTED, "source" => source, "error" => error_kind(error)).increment(1);
+}
+
+/// `policy` es la fuente de la firma (`local`, `remote`).
+pub fn signature(policy: &'static str, issued: bool) {
+    let outcome = if issued { "issued" } else { "refused" };
+    counter!(SIGNATURES, "policy" => policy, "outcome" => outcome).increment(1);
+}
+
+pub fn indexer_lag(stream: &'static str, lag: u64) {
+    gauge!(INDEXER_LAG, "stream" => stream).set(lag as f64);
+}
+
+pub fn loop_balance(loop_address: Address, balance: U256, decimals: u8) {
+    let value = format_units(balance, decimals as u32)
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or_default();
+    gauge!(LOOP_BALANCE, "loop" => format!("{:?}", loop_address)).set(value);
+}
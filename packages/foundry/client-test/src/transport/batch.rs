@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use ethers::providers::JsonRpcClient;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+use crate::transport::{ReplayTransport, RpcFailure, TransportError};
+
+/// Respuesta a una petición de un lote: el resultado o el error JSON-RPC de esa
+/// petición. Un fallo de una no invalida el resto.
+pub type BatchResponse = Result<Value, RpcFailure>;
+
+/// Transporte que manda varias peticiones JSON-RPC en un solo viaje.
+#[async_trait]
+pub trait BatchClient: Send + Sync {
+    /// Las respuestas salen en el mismo orden que `requests` (`(método, params)`).
+    async fn request_batch(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<BatchResponse>, TransportError>;
+}
+
+/// Lotes JSON-RPC sobre HTTP: un POST con un array de peticiones.
+#[derive(Debug, Clone)]
+pub struct HttpBatch {
+    client: reqwest::Client,
+    url: reqwest::Url,
+}
+
+impl HttpBatch {
+    pub fn new(rpc_url: &str) -> ClientResult<Self> {
+        let url = rpc_url.parse::<reqwest::Url>().map_err(|e| {
+            ClientError::config(t!(
+                "RPC_URL inválida {url}: {error}",
+                url = rpc_url,
+                error = e
+            ))
+        })?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Reply {
+    id: u64,
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    error: Option<RpcFailure>,
+}
+
+#[async_trait]
+impl BatchClient for HttpBatch {
+    async fn request_batch(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<BatchResponse>, TransportError> {
+        if requests.is_empty() {
+            return Ok(Vec::new());
+        }
+        let body: Vec<Value> = requests
+            .iter()
+            .enumerate()
+            .map(|(id, (method, params))| {
+                json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+            })
+            .collect();
+        let response: Value = self
+            .client
+            .post(self.url.clone())
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        // Un nodo sin soporte de lotes contesta con un único objeto de error
+        let replies: Vec<Reply> = match response {
+            Value::Array(_) => serde_json::from_value(response)?,
+            other => {
+                return Err(match serde_json::from_value::<Reply>(other.clone()) {
+                    Ok(Reply {
+                        error: Some(failure),
+                        ..
+                    }) => TransportError::JsonRpc(failure.into()),
+                    _ => TransportError::Batch(other.to_string()),
+                })
+            }
+        };
+
+        // El orden de las respuestas no está garantizado: se colocan por id
+        let mut responses: Vec<Option<BatchResponse>> = vec![None; requests.len()];
+        for reply in replies {
+            let slot = responses.get_mut(reply.id as usize).ok_or_else(|| {
+                TransportError::Batch(t!("id {id} fuera del lote", id = reply.id))
+            })?;
+            *slot = Some(match reply.error {
+                Some(failure) => Err(failure),
+                None => Ok(reply.result),
+            });
+        }
+        responses
+            .into_iter()
+            .enumerate()
+            .map(|(id, response)| {
+                response
+                    .ok_or_else(|| TransportError::Batch(t!("falta la respuesta {id}", id = id)))
+            })
+            .collect()
+    }
+}
+
+/// Cada petición del lote se contesta desde el fixture como si llegara sola.
+#[async_trait]
+impl BatchClient for ReplayTransport {
+    async fn request_batch(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<BatchResponse>, TransportError> {
+        let mut responses = Vec::with_capacity(requests.len());
+        for (method, params) in requests {
+            match self.request::<_, Value>(method, params).await {
+                Ok(value) => responses.push(Ok(value)),
+                Err(TransportError::JsonRpc(error)) => responses.push(Err((&error).into())),
+                Err(other) => return Err(other),
+            }
+        }
+        Ok(responses)
+    }
+}
@@ -0,0 +1,70 @@
+use ethers::providers::{JsonRpcError, ProviderError, RpcError};
+use serde_json::Value;
+use std::fmt;
+use thiserror::Error;
+
+use crate::t;
+
+/// Errores de los transportes de fixtures.
+#[derive(Debug, Error)]
+pub enum TransportError {
+    /// El fixture no tiene respuesta para la petición.
+    Unexpected { method: String, params: Value },
+    /// Error JSON-RPC grabado (revert, nonce, gas...).
+    JsonRpc(JsonRpcError),
+    /// La respuesta no encaja con el tipo pedido.
+    Serde(#[from] serde_json::Error),
+    /// Error del transporte envuelto al grabar.
+    Provider(ProviderError),
+    /// Fallo HTTP al mandar un lote de peticiones.
+    Http(#[from] reqwest::Error),
+    /// El nodo contestó al lote con algo que no es una respuesta por petición.
+    Batch(String),
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unexpected { method, params } => f.write_str(&t!(
+                "petición sin respuesta en el fixture: {method} {params}",
+                method = method,
+                params = params
+            )),
+            Self::JsonRpc(error) => write!(f, "{}", error),
+            Self::Serde(error) => write!(f, "{}", error),
+            Self::Provider(error) => write!(f, "{}", error),
+            Self::Http(error) => write!(f, "{}", error),
+            Self::Batch(detail) => f.write_str(&t!(
+                "respuesta inválida a un lote JSON-RPC: {detail}",
+                detail = detail
+            )),
+        }
+    }
+}
+
+impl RpcError for TransportError {
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            Self::JsonRpc(error) => Some(error),
+            Self::Provider(error) => error.as_error_response(),
+            _ => None,
+        }
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            Self::Serde(error) => Some(error),
+            Self::Provider(error) => error.as_serde_error(),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransportError> for ProviderError {
+    fn from(error: TransportError) -> Self {
+        match error {
+            TransportError::Provider(error) => error,
+            other => ProviderError::JsonRpcClientError(Box::new(other)),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use ethers::providers::JsonRpcError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+
+/// Error JSON-RPC tal como se guarda en el fixture.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcFailure {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl From<&JsonRpcError> for RpcFailure {
+    fn from(error: &JsonRpcError) -> Self {
+        Self {
+            code: error.code,
+            message: error.message.clone(),
+            data: error.data.clone(),
+        }
+    }
+}
+
+impl From<RpcFailure> for JsonRpcError {
+    fn from(failure: RpcFailure) -> Self {
+        Self {
+            code: failure.code,
+            message: failure.message,
+            data: failure.data,
+        }
+    }
+}
+
+/// Una petición y lo que contestó el nodo. Con `error` se responde con el
+/// error; si no, con `result` (que puede ser `null`, p.ej. un receipt pendiente).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcExchange {
+    pub method: String,
+    /// `null` casa con cualquier parámetro.
+    #[serde(default)]
+    pub params: Value,
+    #[serde(default)]
+    pub result: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcFailure>,
+}
+
+impl RpcExchange {
+    /// `true` si el intercambio responde a `method` con `params`. Las cadenas
+    /// se comparan sin distinguir mayúsculas para que el hex escrito a mano
+    /// (checksums, hashes) case con el que serializa ethers.
+    pub fn matches(&self, method: &str, params: &Value) -> bool {
+        self.method == method && (self.params.is_null() || same_json(&self.params, params))
+    }
+}
+
+fn same_json(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::String(a), Value::String(b)) => a.eq_ignore_ascii_case(b),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_json(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| same_json(a, b)))
+        }
+        _ => a == b,
+    }
+}
+
+/// Secuencia de intercambios JSON-RPC grabada en disco.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RpcFixture {
+    pub exchanges: Vec<RpcExchange>,
+}
+
+impl RpcFixture {
+    pub fn load(path: impl AsRef<Path>) -> ClientResult<Self> {
+        let path = path.as_ref();
+        let data = fs::read_to_string(path).map_err(|e| {
+            ClientError::config(t!(
+                "no se pudo leer el fixture {path}: {error}",
+                path = path.display(),
+                error = e
+            ))
+        })?;
+        serde_json::from_str(&data).map_err(|e| {
+            ClientError::config(t!(
+                "fixture {path} inválido: {error}",
+                path = path.display(),
+                error = e
+            ))
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> ClientResult<()> {
+        let path = path.as_ref();
+        let io = |e: std::io::Error| {
+            ClientError::config(t!(
+                "no se pudo escribir el fixture {path}: {error}",
+                path = path.display(),
+                error = e
+            ))
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io)?;
+        }
+        let data =
+            serde_json::to_string_pretty(self).map_err(|e| ClientError::decode(e.to_string()))?;
+        fs::write(path, data).map_err(io)
+    }
+}
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use ethers::providers::{JsonRpcClient, ProviderError};
+use metrics::{counter, histogram};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use std::time::Instant;
+
+use crate::telemetry::{error_kind, RPC_ERRORS, RPC_LATENCY};
+use crate::transport::{BatchClient, BatchResponse, TransportError};
+
+/// Envuelve un transporte y mide cada petición: latencia por método en
+/// `RPC_LATENCY` y errores por método y categoría en `RPC_ERRORS`. Es el
+/// transporte de `Env::provider()`, así que cubre todas las llamadas del
+/// cliente, también las que hacen `Contract` y `SignerMiddleware`.
+#[derive(Debug, Clone)]
+pub struct MeteredTransport<T> {
+    inner: T,
+}
+
+impl<T> MeteredTransport<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<C: JsonRpcClient> JsonRpcClient for MeteredTransport<C> {
+    type Error = TransportError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, TransportError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let started = Instant::now();
+        let response = self.inner.request::<T, R>(method, params).await;
+        histogram!(RPC_LATENCY, "method" => method.to_string())
+            .record(started.elapsed().as_secs_f64());
+        response.map_err(|error| {
+            let error: ProviderError = error.into();
+            counter!(
+                RPC_ERRORS,
+                "method" => method.to_string(),
+                "error" => error_kind(&error.to_string())
+            )
+            .increment(1);
+            TransportError::Provider(error)
+        })
+    }
+}
+
+/// Un lote cuenta como una petición `batch`; los errores JSON-RPC de cada
+/// llamada se cuentan con el método de esa llamada.
+#[async_trait]
+impl<C: BatchClient> BatchClient for MeteredTransport<C> {
+    async fn request_batch(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<BatchResponse>, TransportError> {
+        let started = Instant::now();
+        let responses = self.inner.request_batch(requests).await;
+        histogram!(RPC_LATENCY, "method" => "batch").record(started.elapsed().as_secs_f64());
+        match &responses {
+            Ok(responses) => {
+                for ((method, _), response) in requests.iter().zip(responses) {
+                    if let Err(failure) = response {
+                        counter!(
+                            RPC_ERRORS,
+                            "method" => method.clone(),
+                            "error" => error_kind(&failure.message)
+                        )
+                        .increment(1);
+                    }
+                }
+            }
+            Err(error) => {
+                counter!(RPC_ERRORS, "method" => "batch", "error" => error_kind(&error.to_string()))
+                    .increment(1);
+            }
+        }
+        responses
+    }
+}
@@ -0,0 +1,18 @@
+//! Transportes JSON-RPC para probar la lógica del cliente sin nodo:
+//! `ReplayTransport` contesta desde un fixture y `RecordingTransport` graba
+//! los fixtures a partir de un nodo real (anvil). `BatchClient` manda varias
+//! peticiones en un solo viaje (lotes JSON-RPC). `MeteredTransport` mide cada
+//! petición del provider de `Env` para las métricas.
+
+pub mod batch;
+pub use batch::*;
+pub mod error;
+pub use error::*;
+pub mod fixture;
+pub use fixture::*;
+pub mod metered;
+pub use metered::*;
+pub mod record;
+pub use record::*;
+pub mod replay;
+pub use replay::*;
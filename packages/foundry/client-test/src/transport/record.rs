@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use ethers::providers::{Http, JsonRpcClient, Provider, ProviderError, RpcError};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+use crate::transport::{RpcExchange, RpcFixture, TransportError};
+
+/// Envuelve un transporte real y graba cada petición con su respuesta, para
+/// guardarlas como fixture y reproducirlas luego con `ReplayTransport`.
+#[derive(Debug, Clone)]
+pub struct RecordingTransport<T> {
+    inner: T,
+    exchanges: Arc<Mutex<Vec<RpcExchange>>>,
+}
+
+impl<T> RecordingTransport<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            exchanges: Arc::default(),
+        }
+    }
+
+    /// Lo grabado hasta ahora.
+    pub fn fixture(&self) -> RpcFixture {
+        RpcFixture {
+            exchanges: self.exchanges.lock().expect("lock de grabación").clone(),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> ClientResult<()> {
+        self.fixture().save(path)
+    }
+}
+
+impl RecordingTransport<Http> {
+    /// Graba contra un nodo HTTP, p.ej. el anvil local.
+    pub fn connect(rpc_url: &str) -> ClientResult<Self> {
+        let http = rpc_url.parse::<Http>().map_err(|e| {
+            ClientError::config(t!(
+                "RPC_URL inválida {url}: {error}",
+                url = rpc_url,
+                error = e
+            ))
+        })?;
+        Ok(Self::new(http))
+    }
+
+    /// Provider que graba; el transporte clonado comparte la grabación.
+    pub fn provider(&self) -> Provider<Self> {
+        Provider::new(self.clone())
+    }
+}
+
+#[async_trait]
+impl<C: JsonRpcClient> JsonRpcClient for RecordingTransport<C> {
+    type Error = TransportError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, TransportError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let recorded = serde_json::to_value(&params)?;
+        let response = self.inner.request::<T, Value>(method, params).await;
+        let mut exchange = RpcExchange {
+            method: method.to_string(),
+            params: recorded,
+            result: Value::Null,
+            error: None,
+        };
+        let result = match response {
+            Ok(value) => {
+                exchange.result = value.clone();
+                Ok(value)
+            }
+            Err(error) => {
+                let error: ProviderError = error.into();
+                exchange.error = error.as_error_response().map(Into::into);
+                Err(TransportError::Provider(error))
+            }
+        };
+        // Los fallos de conexión no son respuestas del nodo: no se graban
+        if result.is_ok() || exchange.error.is_some() {
+            self.exchanges
+                .lock()
+                .expect("lock de grabación")
+                .push(exchange);
+        }
+        Ok(serde_json::from_value(result?)?)
+    }
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use ethers::providers::{JsonRpcClient, Provider};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::error::ClientResult;
+use crate::transport::{RpcExchange, RpcFixture, TransportError};
+
+/// Transporte que contesta desde un fixture. Cada petición consume el primer
+/// intercambio sin usar con el mismo método y parámetros; cuando ya se han
+/// usado todos, repite el último (así los sondeos de receipts o bloques no se
+/// quedan sin respuesta).
+#[derive(Debug)]
+pub struct ReplayTransport {
+    exchanges: Vec<RpcExchange>,
+    used: Mutex<Vec<bool>>,
+    requests: Mutex<Vec<(String, Value)>>,
+}
+
+impl ReplayTransport {
+    pub fn new(fixture: RpcFixture) -> Self {
+        let used = vec![false; fixture.exchanges.len()];
+        Self {
+            exchanges: fixture.exchanges,
+            used: Mutex::new(used),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> ClientResult<Self> {
+        Ok(Self::new(RpcFixture::load(path)?))
+    }
+
+    /// Provider listo para usar con `contract_at`, `Contract::new`, etc.
+    pub fn provider(fixture: RpcFixture) -> Provider<Self> {
+        Provider::new(Self::new(fixture))
+    }
+
+    /// Peticiones recibidas, en orden.
+    pub fn requests(&self) -> Vec<(String, Value)> {
+        self.requests.lock().expect("lock de peticiones").clone()
+    }
+
+    /// Cuántas veces se pidió `method`.
+    pub fn count(&self, method: &str) -> usize {
+        self.requests
+            .lock()
+            .expect("lock de peticiones")
+            .iter()
+            .filter(|(m, _)| m == method)
+            .count()
+    }
+
+    fn answer(&self, method: &str, params: &Value) -> Result<&RpcExchange, TransportError> {
+        let mut used = self.used.lock().expect("lock de intercambios");
+        let matching: Vec<usize> = self
+            .exchanges
+            .iter()
+            .enumerate()
+            .filter(|(_, exchange)| exchange.matches(method, params))
+            .map(|(index, _)| index)
+            .collect();
+        let index = matching
+            .iter()
+            .find(|index| !used[**index])
+            .or(matching.last())
+            .copied()
+            .ok_or_else(|| TransportError::Unexpected {
+                method: method.to_string(),
+                params: params.clone(),
+            })?;
+        used[index] = true;
+        Ok(&self.exchanges[index])
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for ReplayTransport {
+    type Error = TransportError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, TransportError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params = serde_json::to_value(params)?;
+        self.requests
+            .lock()
+            .expect("lock de peticiones")
+            .push((method.to_string(), params.clone()));
+        let exchange = self.answer(method, &params)?;
+        match &exchange.error {
+            Some(failure) => Err(TransportError::JsonRpc(failure.clone().into())),
+            None => Ok(serde_json::from_value(exchange.result.clone())?),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use ethers::prelude::*;
+use eyre::Result;
+
+/// Adelanta el reloj de anvil `seconds` segundos y mina un bloque.
+pub async fn warp<P: JsonRpcClient>(provider: &Provider<P>, seconds: u64) -> Result<()> {
+    provider
+        .request::<_, serde_json::Value>("evm_increaseTime", [seconds])
+        .await?;
+    mine(provider).await
+}
+
+/// Mina un bloque vacío.
+pub async fn mine<P: JsonRpcClient>(provider: &Provider<P>) -> Result<()> {
+    provider
+        .request::<_, serde_json::Value>("evm_mine", ())
+        .await?;
+    Ok(())
+}
+
+/// Timestamp del último bloque.
+pub async fn chain_time<P: JsonRpcClient>(provider: &Provider<P>) -> Result<U256> {
+    Ok(provider
+        .get_block(BlockNumber::Latest)
+        .await?
+        .map(|b| b.timestamp)
+        .unwrap_or_default())
+}
+
+/// Fija el balance de ETH de `address` (`anvil_setBalance`).
+pub async fn set_balance<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    address: Address,
+    balance: U256,
+) -> Result<()> {
+    provider
+        .request::<_, serde_json::Value>("anvil_setBalance", (address, balance))
+        .await?;
+    Ok(())
+}
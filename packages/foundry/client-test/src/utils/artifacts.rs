@@ -0,0 +1,263 @@
+use ethers::abi::{ethabi::AbiError, Abi, Function, ParamType, Token};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+use crate::utils::ARTIFACTS_DIR;
+
+/// Custom error decodificado de los datos de un revert.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedError {
+    pub name: String,
+    pub args: Vec<Token>,
+}
+
+impl fmt::Display for DecodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args: Vec<String> = self.args.iter().map(ToString::to_string).collect();
+        write!(f, "{}({})", self.name, args.join(", "))
+    }
+}
+
+/// Lee el artifact de forge `out/<Contrato>.sol/<Contrato>.json`.
+pub fn load_artifact(contract: &str) -> ClientResult<Value> {
+    let path = format!("{}/{}.sol/{}.json", ARTIFACTS_DIR, contract, contract);
+    let data = fs::read_to_string(&path).map_err(|e| {
+        ClientError::config(t!(
+            "no se pudo leer el artifact {path}: {error}",
+            path = path,
+            error = e
+        ))
+    })?;
+    serde_json::from_str(&data).map_err(|e| {
+        ClientError::config(t!(
+            "artifact {path} inválido: {error}",
+            path = path,
+            error = e
+        ))
+    })
+}
+
+/// Extrae y parsea la ABI de un artifact de forge.
+pub fn load_abi(contract: &str) -> ClientResult<Abi> {
+    let artifact = load_artifact(contract)?;
+    let abi = artifact
+        .get("abi")
+        .ok_or_else(|| {
+            ClientError::config(t!(
+                "ABI no encontrada en el artifact de {contract}",
+                contract = contract
+            ))
+        })?
+        .clone();
+    serde_json::from_value(abi).map_err(|e| {
+        ClientError::config(t!(
+            "ABI de {contract} inválida: {error}",
+            contract = contract,
+            error = e
+        ))
+    })
+}
+
+/// Selectores que una faceta expone en un diamond: todas las funciones de la ABI
+/// salvo los inicializadores (`*_init`), que sólo se usan vía delegatecall.
+pub fn facet_selectors(abi: &Abi) -> Vec<[u8; 4]> {
+    abi.functions()
+        .filter(|f| !f.name.ends_with("_init"))
+        .map(Function::short_signature)
+        .collect()
+}
+
+/// Bytecode desplegado (`deployedBytecode.object`) de un artifact de forge.
+pub fn load_deployed_bytecode(contract: &str) -> ClientResult<Vec<u8>> {
+    load_bytecode(contract, "deployedBytecode")
+}
+
+/// Creation code (`bytecode.object`) de un artifact de forge.
+pub fn load_creation_code(contract: &str) -> ClientResult<Vec<u8>> {
+    load_bytecode(contract, "bytecode")
+}
+
+fn load_bytecode(contract: &str, field: &str) -> ClientResult<Vec<u8>> {
+    let artifact = load_artifact(contract)?;
+    let object = artifact[field]["object"].as_str().ok_or_else(|| {
+        ClientError::config(t!(
+            "{field} no encontrado para {contract}",
+            field = field,
+            contract = contract
+        ))
+    })?;
+    ethers::utils::hex::decode(object.trim_start_matches("0x"))
+        .map_err(|e| ClientError::decode(format!("{} de {}: {}", field, contract, e)))
+}
+
+/// Firma canónica de una función, p.ej. `claimAndRegister(bytes)`.
+pub fn function_signature(function: &Function) -> String {
+    let inputs: Vec<String> = function.inputs.iter().map(|p| p.kind.to_string()).collect();
+    format!("{}({})", function.name, inputs.join(","))
+}
+
+/// Índice `selector -> firmas` construido a partir de las ABIs locales.
+#[derive(Debug, Default, Clone)]
+pub struct SelectorBook {
+    entries: HashMap<[u8; 4], BTreeSet<String>>,
+    owners: HashMap<[u8; 4], BTreeSet<String>>,
+    errors: HashMap<[u8; 4], AbiError>,
+}
+
+impl SelectorBook {
+    /// Recorre `ARTIFACTS_DIR` y registra las funciones de todos los artifacts.
+    pub fn from_artifacts() -> ClientResult<Self> {
+        let io = |e: std::io::Error| ClientError::config(format!("{}: {}", ARTIFACTS_DIR, e));
+        let mut book = Self::default();
+        for entry in fs::read_dir(ARTIFACTS_DIR).map_err(io)? {
+            let dir = entry.map_err(io)?.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(&dir).map_err(io)? {
+                let file = file.map_err(io)?.path();
+                if file.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                // Algunos artifacts (build-info, metadata) no traen ABI: se ignoran
+                if let Some(abi) = read_abi_file(&file) {
+                    let name = file
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    book.add_abi(&name, &abi);
+                }
+            }
+        }
+        Ok(book)
+    }
+
+    pub fn add_abi(&mut self, contract: &str, abi: &Abi) {
+        for function in abi.functions() {
+            let selector = function.short_signature();
+            self.entries
+                .entry(selector)
+                .or_default()
+                .insert(function_signature(function));
+            self.owners
+                .entry(selector)
+                .or_default()
+                .insert(contract.to_string());
+        }
+        for error in abi.errors() {
+            let selector = error_selector(error);
+            self.errors.entry(selector).or_insert_with(|| error.clone());
+        }
+    }
+
+    /// Traduce los datos de un revert a texto: `Error(string)`, `Panic(uint256)`
+    /// o un custom error de las ABIs locales (`AlreadyRegistered()`, ...).
+    pub fn decode_revert(&self, data: &[u8]) -> String {
+        if data.len() < 4 {
+            return t!("revert sin datos");
+        }
+        let selector = [data[0], data[1], data[2], data[3]];
+        if selector == ethers::utils::id("Error(string)") {
+            if let Ok(tokens) = ethers::abi::decode(&[ParamType::String], &data[4..]) {
+                if let Some(Token::String(reason)) = tokens.into_iter().next() {
+                    return reason;
+                }
+            }
+        }
+        if selector == ethers::utils::id("Panic(uint256)") {
+            return format!("Panic(0x{})", ethers::utils::hex::encode(&data[4..]));
+        }
+        match self.decode_custom_error(data) {
+            Some(error) => error.to_string(),
+            None => format!("0x{}", ethers::utils::hex::encode(data)),
+        }
+    }
+
+    /// Custom error de las ABIs locales con sus argumentos, si el selector es
+    /// uno de ellos. Si los argumentos no decodifican se devuelve sin ellos.
+    pub fn decode_custom_error(&self, data: &[u8]) -> Option<DecodedError> {
+        let selector: [u8; 4] = data.get(..4)?.try_into().ok()?;
+        let error = self.errors.get(&selector)?;
+        Some(DecodedError {
+            name: error.name.clone(),
+            args: error.decode(&data[4..]).unwrap_or_default(),
+        })
+    }
+
+    /// Devuelve la firma del selector si es conocida y no ambigua.
+    pub fn resolve(&self, selector: [u8; 4]) -> Option<&str> {
+        match self.entries.get(&selector) {
+            Some(sigs) if sigs.len() == 1 => sigs.iter().next().map(String::as_str),
+            _ => None,
+        }
+    }
+
+    /// Todas las firmas conocidas para un selector.
+    pub fn signatures(&self, selector: [u8; 4]) -> Vec<&str> {
+        self.entries
+            .get(&selector)
+            .map(|sigs| sigs.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Contratos locales que declaran el selector.
+    pub fn contracts(&self, selector: [u8; 4]) -> Vec<&str> {
+        self.owners
+            .get(&selector)
+            .map(|c| c.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Selector de una firma completa (`withdrawDeposit(address)`) o de un nombre
+    /// de función conocido (`withdrawDeposit`).
+    pub fn lookup(&self, function: &str) -> Option<[u8; 4]> {
+        if function.contains('(') {
+            let hash = ethers::utils::keccak256(function.replace(' ', ""));
+            return Some([hash[0], hash[1], hash[2], hash[3]]);
+        }
+        let prefix = format!("{}(", function);
+        self.entries
+            .iter()
+            .find(|(_, sigs)| sigs.iter().any(|s| s.starts_with(&prefix)))
+            .map(|(selector, _)| *selector)
+    }
+
+    /// `true` si dos firmas distintas comparten el mismo selector.
+    pub fn is_collision(&self, selector: [u8; 4]) -> bool {
+        self.entries.get(&selector).is_some_and(|s| s.len() > 1)
+    }
+}
+
+fn error_selector(error: &AbiError) -> [u8; 4] {
+    let inputs: Vec<String> = error.inputs.iter().map(|p| p.kind.to_string()).collect();
+    ethers::utils::id(format!("{}({})", error.name, inputs.join(",")))
+}
+
+fn read_abi_file(path: &Path) -> Option<Abi> {
+    let data = fs::read_to_string(path).ok()?;
+    let json: Value = serde_json::from_str(&data).ok()?;
+    serde_json::from_value(json.get("abi")?.clone()).ok()
+}
+
+/// Interpreta un selector dado como `0x12345678`, como firma o como nombre de función.
+pub fn parse_selector(input: &str, book: &SelectorBook) -> ClientResult<[u8; 4]> {
+    if let Some(hex) = input.strip_prefix("0x") {
+        return ethers::utils::hex::decode(hex)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| ClientError::decode(t!("selector inválido: {input}", input = input)));
+    }
+    book.lookup(input)
+        .ok_or_else(|| ClientError::decode(t!("función desconocida: {input}", input = input)))
+}
+
+/// Formatea un selector como `0x12345678`.
+pub fn format_selector(selector: [u8; 4]) -> String {
+    format!("0x{}", ethers::utils::hex::encode(selector))
+}
@@ -1 +1,13 @@
 pub const RPC_URL: &str = "http://127.0.0.1:8545";
+pub const DEPLOYMENTS_DIR: &str = "../deployments";
+pub const ARTIFACTS_DIR: &str = "../out";
+pub const CONTRACTS_DIR: &str = "../contracts";
+/// Diarios de transacciones de `onboard` (uno por `--id`).
+pub const JOURNAL_DIR: &str = ".journal";
+
+/// Roles de `Constants.sol`: cada rol es un bit del bitmap `bytes32`.
+pub const DEFAULT_ADMIN_ROLE: u8 = 0;
+pub const LOOP_ADMIN_ROLE: u8 = 1;
+
+/// Historial de la consola interactiva.
+pub const CONSOLE_HISTORY: &str = ".console_history";
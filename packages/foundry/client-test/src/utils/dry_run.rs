@@ -0,0 +1,111 @@
+use ethers::abi::Detokenize;
+use ethers::prelude::*;
+use std::fmt::Debug;
+
+use crate::t;
+use crate::utils::{format_selector, SelectorBook};
+
+/// Lo que haría una escritura si se enviase ahora, sin enviarla.
+#[derive(Debug, Clone)]
+pub struct DryRun<R> {
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    /// Firma de la función llamada, si está en las ABIs locales.
+    pub function: Option<String>,
+    /// Valor de retorno decodificado o motivo del revert.
+    pub result: std::result::Result<R, String>,
+    pub gas: Option<U256>,
+}
+
+impl<R> DryRun<R> {
+    pub fn would_succeed(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Traduce un error de contrato a texto, decodificando el revert si lo hay.
+pub fn contract_error_reason<M: Middleware>(
+    error: &ContractError<M>,
+    book: &SelectorBook,
+) -> String {
+    match error.as_revert() {
+        Some(data) => book.decode_revert(data),
+        None => error.to_string(),
+    }
+}
+
+/// Simula `call` con `eth_call` sobre el estado `pending` y estima su gas
+/// sobre ese mismo bloque.
+pub async fn dry_run<M, R>(call: &ContractCall<M, R>, book: &SelectorBook) -> DryRun<R>
+where
+    M: Middleware + 'static,
+    R: Detokenize,
+{
+    let function = call
+        .tx
+        .data()
+        .filter(|data| data.len() >= 4)
+        .map(|data| [data[0], data[1], data[2], data[3]])
+        .map(|selector| {
+            book.resolve(selector)
+                .map(str::to_string)
+                .unwrap_or_else(|| format_selector(selector))
+        });
+
+    let pinned = call.clone().block(BlockNumber::Pending);
+    let result = pinned
+        .call()
+        .await
+        .map_err(|e| contract_error_reason(&e, book));
+    // Si revierte no hay nada que estimar
+    let gas = match result {
+        Ok(_) => pinned.estimate_gas().await.ok(),
+        Err(_) => None,
+    };
+
+    DryRun {
+        from: call.tx.from().copied(),
+        to: call.tx.to_addr().copied(),
+        function,
+        result,
+        gas,
+    }
+}
+
+pub fn print_dry_run<R: Debug>(dry_run: &DryRun<R>) {
+    println!("{}", t!("🧪 Dry run (no se envía nada)"));
+    println!("  {:<10}{:?}", "from:", dry_run.from.unwrap_or_default());
+    println!("  {:<10}{:?}", "to:", dry_run.to.unwrap_or_default());
+    println!(
+        "  {:<10}{}",
+        t!("función:"),
+        dry_run.function.as_deref().unwrap_or("?")
+    );
+    match &dry_run.result {
+        Ok(value) => {
+            println!("  {:<10} ✅ {:?}", t!("resultado:"), value);
+            match dry_run.gas {
+                Some(gas) => println!("  {:<10}{}", "gas:", gas),
+                None => println!("  {:<10}{}", "gas:", t!("no se pudo estimar")),
+            }
+        }
+        Err(reason) => println!("  {:<10} ❌ revert: {}", t!("resultado:"), reason),
+    }
+}
+
+pub fn dry_run_json<R: Debug>(dry_run: &DryRun<R>) -> serde_json::Value {
+    let (result, revert) = match &dry_run.result {
+        Ok(value) => (Some(format!("{:?}", value)), None),
+        Err(reason) => (None, Some(reason.clone())),
+    };
+    serde_json::json!({
+        "dry_run": true,
+        "from": dry_run.from,
+        "to": dry_run.to,
+        "function": dry_run.function,
+        "success": dry_run.would_succeed(),
+        "result": result,
+        "revert": revert,
+        "gas": dry_run.gas.map(|gas| gas.as_u64()),
+    })
+}
@@ -0,0 +1,34 @@
+use clap::ValueEnum;
+use eyre::Result;
+use std::env;
+use tracing_subscriber::EnvFilter;
+
+use crate::t;
+
+/// Formato de los logs, que siempre van a stderr para no mezclarse con la salida.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    Text,
+    /// Una línea JSON por evento, con los campos de los spans activos.
+    Json,
+}
+
+/// Instala el subscriber global. El nivel sale de `level` (p.ej. `debug` o
+/// `client_test=trace,ethers=warn`), si no de `RUST_LOG` y si no es `info`.
+pub fn init_logging(level: Option<&str>, format: LogFormat) -> Result<()> {
+    let filter = match level {
+        Some(level) => EnvFilter::try_new(level)?,
+        None => match env::var(EnvFilter::DEFAULT_ENV) {
+            Ok(level) => EnvFilter::try_new(level)?,
+            Err(_) => EnvFilter::new("info"),
+        },
+    };
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    match format {
+        LogFormat::Text => builder.try_init(),
+        LogFormat::Json => builder.json().with_current_span(true).try_init(),
+    }
+    .map_err(|e| eyre::eyre!(t!("❌ No se pudo iniciar el logging: {error}", error = e)))
+}
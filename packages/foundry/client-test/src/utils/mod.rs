@@ -1,8 +1,14 @@
 pub mod setup_env;
 pub use setup_env::*;
 pub mod provider;
-pub mod waiter;
 pub use provider::*;
-pub use waiter::*;
 pub mod constants;
 pub use constants::*;
+pub mod artifacts;
+pub use artifacts::*;
+pub mod anvil;
+pub use anvil::*;
+pub mod dry_run;
+pub use dry_run::*;
+pub mod logging;
+pub use logging::*;
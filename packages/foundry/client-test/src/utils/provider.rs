@@ -1,9 +1,10 @@
-use ethers::providers::{Http, Provider, Ws};
-use eyre::Result;
+use ethers::providers::{Provider, ProviderError, Ws};
 use std::sync::Arc;
 
+use crate::error::ClientResult;
+
 /// Obtiene un `Provider<Http>` envuelto en `Arc`, a partir de una URL de RPC.
-pub async fn get_provider(rpc_url: &str) -> Result<Arc<Provider<Ws>>> {
+pub async fn get_provider(rpc_url: &str) -> ClientResult<Arc<Provider<Ws>>> {
     let ws_url = if rpc_url.starts_with("http://") {
         rpc_url.replacen("http://", "ws://", 1)
     } else if rpc_url.starts_with("https://") {
@@ -12,7 +13,7 @@ pub async fn get_provider(rpc_url: &str) -> Result<Arc<Provider<Ws>>> {
         rpc_url.to_string()
     };
 
-    let ws = Ws::connect(ws_url).await?;
+    let ws = Ws::connect(ws_url).await.map_err(ProviderError::from)?;
     let provider = Arc::new(Provider::new(ws));
     Ok(provider)
 }
@@ -1,17 +1,26 @@
-use ethers::abi::{Abi, AbiDecode, ParamType};
+use ethers::abi::Abi;
 use ethers::contract::Contract;
-use ethers::core::k256::ecdsa::SigningKey;
 use ethers::middleware::SignerMiddleware;
-use ethers::providers::{Http, Provider};
+use ethers::providers::{Http, Middleware, Provider};
 use ethers::signers::{LocalWallet, Signer};
-use ethers::types::{Address, TransactionReceipt, H256, U256};
-use eyre::{Ok, Result};
+use ethers::types::{Address, U256};
 use serde_json::Value;
 use std::env;
 use std::fs;
-use std::future::pending;
-use std::result::Result::Ok as StdOk;
 use std::sync::Arc;
+use tracing::{debug, info, instrument, Span};
+
+use crate::error::{ClientError, ClientResult};
+use crate::t;
+use crate::transport::MeteredTransport;
+use crate::utils::{load_abi, load_artifact, DEPLOYMENTS_DIR};
+
+/// Provider HTTP con cada petición medida (ver `MeteredTransport`).
+pub type RpcProvider = Provider<MeteredTransport<Http>>;
+
+/// Cliente con signer local; por defecto sobre el provider HTTP medido. Los
+/// tests lo usan sobre `ReplayTransport`.
+pub type SignerClient<P = MeteredTransport<Http>> = SignerMiddleware<Provider<P>, LocalWallet>;
 
 #[derive(Debug, Clone)]
 pub struct ContractStr {
@@ -19,16 +28,29 @@ pub struct ContractStr {
     abi: Abi,
 }
 
+/// Valores de la línea de comandos que pisan las variables de entorno.
+#[derive(Debug, Clone, Default)]
+pub struct EnvOverrides {
+    pub rpc_url: Option<String>,
+    /// Clave con la que se envían las transacciones en vez de TRUSTED_SIGNER_PK.
+    pub signer_pk: Option<String>,
+    /// Valor de TRUSTED_SIGNER_PK sin leer la variable de entorno.
+    pub trusted_signer_pk: Option<String>,
+    /// Valor de BAD_ACTOR_PK sin leer la variable de entorno.
+    pub bad_actor_pk: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Env {
     pub rpc_url: String,
+    pub chain_id: u64,
     pub bad_actor_pk: String,
     pub trusted_signer_pk: String,
     pub deployemt_data: Value,
-    pub loop_contract: Option<Contract<Provider<Http>>>, // Instancia sin signer
-    pub org_contract: Option<Contract<Provider<Http>>>,  // Instancia sin signer
-    pub bad_signer: Option<Arc<SignerMiddleware<Provider<Http>, LocalWallet>>>,
-    pub trusted_signer: Option<Arc<SignerMiddleware<Provider<Http>, LocalWallet>>>,
+    pub loop_contract: Option<Contract<RpcProvider>>, // Instancia sin signer
+    pub org_contract: Option<Contract<RpcProvider>>,  // Instancia sin signer
+    pub bad_signer: Option<Arc<SignerClient>>,
+    pub trusted_signer: Option<Arc<SignerClient>>,
 }
 
 impl Env {
@@ -36,6 +58,7 @@ impl Env {
     fn default() -> Self {
         Self {
             rpc_url: String::new(),
+            chain_id: Self::CHAIN_ID,
             bad_actor_pk: String::new(),
             trusted_signer_pk: String::new(),
             deployemt_data: Value::default(),
@@ -45,19 +68,23 @@ impl Env {
             trusted_signer: None,
         }
     }
-    fn setup_providers(&mut self, loop_data: &ContractStr, org_data: &ContractStr) -> Result<()> {
+    fn setup_providers(
+        &mut self,
+        loop_data: &ContractStr,
+        org_data: &ContractStr,
+    ) -> ClientResult<()> {
         // Construcción de la estructura Env
-        let provider = Provider::<Http>::try_from(&self.rpc_url)?;
+        let provider = self.provider()?;
 
         // 2️⃣ Crear wallets desde claves privadas
         let wallet1 = self
             .bad_actor_pk
             .parse::<LocalWallet>()?
-            .with_chain_id(Self::CHAIN_ID);
+            .with_chain_id(self.chain_id);
         let wallet2 = self
             .trusted_signer_pk
             .parse::<LocalWallet>()?
-            .with_chain_id(Self::CHAIN_ID);
+            .with_chain_id(self.chain_id);
 
         let bad_signer = Arc::new(SignerMiddleware::new(provider.clone(), wallet1));
         let trusted_signer = Arc::new(SignerMiddleware::new(provider.clone(), wallet2));
@@ -80,53 +107,88 @@ impl Env {
 
         Ok(())
     }
-    pub async fn setup() -> Result<Self> {
+    pub async fn setup() -> ClientResult<Self> {
+        Self::setup_with(EnvOverrides::default()).await
+    }
+
+    #[instrument(skip_all, fields(rpc_url, chain_id, signer))]
+    pub async fn setup_with(overrides: EnvOverrides) -> ClientResult<Self> {
         // Cargar variables de entorno
-        let rpc_url = env::var("RPC_URL")?;
-        let bad_actor_pk = env::var("BAD_ACTOR_PK")?;
-        let trusted_signer_pk = env::var("TRUSTED_SIGNER_PK")?;
+        let rpc_url = match overrides.rpc_url {
+            Some(rpc_url) => rpc_url,
+            None => env_var("RPC_URL")?,
+        };
+        Span::current().record("rpc_url", rpc_url.as_str());
+        let bad_actor_pk = match overrides.bad_actor_pk {
+            Some(pk) => pk,
+            None => env_var("BAD_ACTOR_PK")?,
+        };
+        let trusted_signer_pk = match overrides.trusted_signer_pk {
+            Some(pk) => pk,
+            None => env_var("TRUSTED_SIGNER_PK")?,
+        };
 
-        // Leer archivos JSON
-        let data = fs::read_to_string("../deployments/31337.json")?;
-        let org_facet_data =
-            fs::read_to_string("../out/OrganizationFacet.sol/OrganizationFacet.json")?;
-        let loop_facet_data = fs::read_to_string("../out/LoopFacet.sol/LoopFacet.json")?;
+        // El deployment depende de la red: ../deployments/<chain_id>.json
+        let chain_id = http_provider(&rpc_url)?.get_chainid().await?.as_u64();
+        Span::current().record("chain_id", chain_id);
 
-        // Parsear JSON
-        let json: Value = serde_json::from_str(&data)?;
-        let org_json: Value = serde_json::from_str(&org_facet_data)?;
-        let loop_json: Value = serde_json::from_str(&loop_facet_data)?;
+        // Leer y parsear archivos JSON
+        let json = read_json(&format!("{}/{}.json", DEPLOYMENTS_DIR, chain_id))?;
+        let org_json = load_artifact("OrganizationFacet")?;
+        let loop_json = load_artifact("LoopFacet")?;
 
         // Extraer dirección del contrato "loop"
-        let loop_address: Address = json["loop"]
-            .as_str()
-            .ok_or_else(|| eyre::eyre!("Loop address not found in JSON."))?
-            .parse()?;
+        let loop_address = parse_address(json["loop"].as_str().ok_or_else(|| {
+            ClientError::config(t!("no hay dirección de loop en el deployment"))
+        })?)?;
 
-        println!(" Loop address: {:?}", loop_address);
+        debug!(loop_address = ?loop_address, "loop del deployment");
 
         // Extraer dirección del contrato "org"
-        let org_address: Address = json["organization"]
-            .as_str()
-            .ok_or_else(|| eyre::eyre!("Organization address not found in JSON."))?
-            .parse()?;
+        let org_address = parse_address(json["organization"].as_str().ok_or_else(|| {
+            ClientError::config(t!("no hay dirección de organización en el deployment"))
+        })?)?;
 
-        println!("Organization address: {:?}", org_address);
+        debug!(organization = ?org_address, "organización del deployment");
 
         // Extraer y parsear ABI del contrato
         let loop_contract_abi: Abi = serde_json::from_value(
             loop_json
                 .get("abi")
-                .ok_or_else(|| eyre::eyre!("ABI not found in loop JSON."))?
+                .ok_or_else(|| {
+                    ClientError::config(t!(
+                        "ABI no encontrada en el artifact de {contract}",
+                        contract = "LoopFacet"
+                    ))
+                })?
                 .clone(),
-        )?;
+        )
+        .map_err(|e| {
+            ClientError::config(t!(
+                "ABI de {contract} inválida: {error}",
+                contract = "LoopFacet",
+                error = e
+            ))
+        })?;
 
         let org_contract_abi: Abi = serde_json::from_value(
             org_json
                 .get("abi")
-                .ok_or_else(|| eyre::eyre!("ABI not found in organization JSON."))?
+                .ok_or_else(|| {
+                    ClientError::config(t!(
+                        "ABI no encontrada en el artifact de {contract}",
+                        contract = "OrganizationFacet"
+                    ))
+                })?
                 .clone(),
-        )?;
+        )
+        .map_err(|e| {
+            ClientError::config(t!(
+                "ABI de {contract} inválida: {error}",
+                contract = "OrganizationFacet",
+                error = e
+            ))
+        })?;
         let org_struct: ContractStr = ContractStr {
             address: loop_address,
             abi: loop_contract_abi,
@@ -138,120 +200,120 @@ impl Env {
 
         let mut env_struct = Env::default();
         env_struct.rpc_url = rpc_url;
+        env_struct.chain_id = chain_id;
         env_struct.bad_actor_pk = bad_actor_pk;
         env_struct.trusted_signer_pk = trusted_signer_pk;
         env_struct.deployemt_data = json;
         env_struct.setup_providers(&org_struct, &loop_struct)?;
+        if let Some(pk) = overrides.signer_pk {
+            env_struct.trusted_signer = Some(env_struct.wallet(&pk)?);
+        }
 
-        println!("Loop Contract ABI cargado correctamente.");
-        println!("Organization Contract ABI cargado correctamente.");
+        let signer = env_struct.signer()?.address();
+        Span::current().record("signer", tracing::field::debug(signer));
+        info!("entorno cargado");
 
         Ok(env_struct)
     }
-}
 
-impl Env {
-    // pub async fn create_loop(
-    //     env: &Env,
-    //     contract: Option<Contract<Provider<Http>>>,
-    //     time: U256,
-    // ) -> Result<H256> {
-    //     let system_diamond: Address = env
-    //         .deployemt_data
-    //         .get("system_diamond")
-    //         .and_then(Value::as_str)
-    //         .ok_or_else(|| eyre::eyre!("❌ system_diamond no encontrado o inválido"))?
-    //         .parse()?;
-
-    //     let token: Address = env
-    //         .deployemt_data
-    //         .get("test_token_address")
-    //         .and_then(Value::as_str)
-    //         .ok_or_else(|| eyre::eyre!("❌ test_token_address no encontrado o inválido"))?
-    //         .parse()?;
-
-    //     let percent_per_period: U256 = U256::from(5);
-
-    //     match contract {
-    //         Some(c) => {
-    //             let signer = env
-    //                 .trusted_signer
-    //                 .clone()
-    //                 .ok_or_else(|| eyre::eyre!("❌ No hay signer disponible"))?;
-    //             let c_with_user = c.connect(signer);
-    //             // Conectar el contrato con el signer
-    //             // let org_contract_with_signer = c.clone().connect(signer);
-
-    //             // Enviar la transacción
-
-    //             // Enviar la transacción y obtener el objeto PendingTransaction
-    //             let pending_tx = c_with_user
-    //                 .method::<(Address, Address, U256, U256), Address>(
-    //                     "createNewLoop",
-    //                     (system_diamond, token, time, percent_per_period),
-    //                 )?
-    //                 .send()
-    //                 .await?
-    //                 .tx_hash();
-
-    //             // let receipt = pending_tx
-    //             //     .await?
-    //             //     .ok_or_else(|| eyre::eyre!("❌ La transacción no se confirmó"))?;
-    //             // // Extraer el valor de retorno desde los logs (si `createNewLoop` lo devuelve)
-    //             // let new_loop_address = receipt
-    //             //     .clone()
-    //             //     .logs
-    //             //     .iter()
-    //             //     .find_map(|log| {
-    //             //         log.topics.get(0).and_then(|topic| {
-    //             //             if *topic == c.abi().event("LoopCreated").unwrap().signature() {
-    //             //                 Some(
-    //             //                     ethers::abi::decode(&[ParamType::Address], &log.data)
-    //             //                         .ok()?
-    //             //                         .remove(0),
-    //             //                 )
-    //             //             } else {
-    //             //                 None
-    //             //             }
-    //             //         })
-    //             //     })
-    //             //     .ok_or_else(|| {
-    //             //         eyre::eyre!("❌ No se pudo recuperar la dirección del nuevo Loop")
-    //             //     })?;
-    //             Ok(pending_tx)
-    //             // println!("✅ Loop creado en la dirección: {:?}", new_loop_address);
-    //         }
-    //         // Ok((, new_loop_address.into_address().unwrap()))
-    //         None => Ok(H256::default()),
-    //     }
-    // }
-    pub async fn claim_and_register(env: &Env, signature: Vec<u8>) -> Result<H256> {
-        match &env.loop_contract {
-            Some(c) => {
-                let tx_hash = c
-                    .method::<Vec<u8>, H256>("claimAndRegister", signature)
-                    .unwrap()
-                    .send()
-                    .await?
-                    .tx_hash();
-
-                Ok(tx_hash)
-            }
-            None => Ok(H256::zero()),
-        }
+    /// Trusted signer con el que se envían las transacciones administrativas.
+    pub fn signer(&self) -> ClientResult<Arc<SignerClient>> {
+        self.trusted_signer
+            .clone()
+            .ok_or_else(|| ClientError::config(t!("no hay signer disponible")))
+    }
+
+    /// Construye un cliente firmante a partir de una clave privada.
+    pub fn wallet(&self, pk: &str) -> ClientResult<Arc<SignerClient>> {
+        let wallet = pk.parse::<LocalWallet>()?.with_chain_id(self.chain_id);
+        Ok(Arc::new(SignerMiddleware::new(self.provider()?, wallet)))
+    }
+
+    /// Provider HTTP medido, sin signer, contra `rpc_url`.
+    pub fn provider(&self) -> ClientResult<RpcProvider> {
+        http_provider(&self.rpc_url)
+    }
+
+    /// Lee una dirección del JSON de deployment (p.ej. `system_diamond`).
+    pub fn deployment_address(&self, key: &str) -> ClientResult<Address> {
+        parse_address(
+            self.deployemt_data
+                .get(key)
+                .and_then(Value::as_str)
+                .ok_or_else(|| {
+                    ClientError::config(t!("{key} no encontrado o inválido", key = key))
+                })?,
+        )
     }
 
-    pub async fn get_current_period(env: &Env) -> Result<U256> {
-        match &env.loop_contract {
-            Some(c) => {
-                let period: U256 = c
-                    .method::<(), U256>("getCurrentPeriod", ())
-                    .unwrap()
-                    .call()
-                    .await?;
-                Ok(period)
-            }
-            None => Ok(U256::default()),
+    /// Acepta una dirección o una clave del JSON de deployment (`loop`, `organization`...).
+    pub fn resolve_address(&self, target: &str) -> ClientResult<Address> {
+        match target.parse::<Address>() {
+            Ok(address) => Ok(address),
+            Err(_) => self.deployment_address(target),
         }
     }
+
+    /// Instancia sin signer de `address` usando la ABI del artifact `contract`.
+    pub fn contract_at(
+        &self,
+        address: Address,
+        contract: &str,
+    ) -> ClientResult<Contract<RpcProvider>> {
+        let abi = load_abi(contract)?;
+        Ok(Contract::new(address, abi, Arc::new(self.provider()?)))
+    }
+}
+
+impl Env {
+    /// `getCurrentPeriod` del loop del deployment.
+    pub async fn get_current_period(env: &Env) -> ClientResult<U256> {
+        let contract = env
+            .loop_contract
+            .as_ref()
+            .ok_or_else(|| ClientError::config(t!("loop_contract no inicializado")))?;
+        let period: U256 = contract
+            .method::<(), U256>("getCurrentPeriod", ())?
+            .call()
+            .await?;
+        Ok(period)
+    }
+}
+
+fn env_var(name: &str) -> ClientResult<String> {
+    env::var(name).map_err(|_| ClientError::config(t!("{name} no está definida", name = name)))
+}
+
+fn http_provider(rpc_url: &str) -> ClientResult<RpcProvider> {
+    let http = rpc_url.parse::<Http>().map_err(|e| {
+        ClientError::config(t!(
+            "RPC_URL inválida {url}: {error}",
+            url = rpc_url,
+            error = e
+        ))
+    })?;
+    Ok(Provider::new(MeteredTransport::new(http)))
+}
+
+fn read_json(path: &str) -> ClientResult<Value> {
+    let data = fs::read_to_string(path).map_err(|e| {
+        ClientError::config(t!(
+            "no se pudo leer {path}: {error}",
+            path = path,
+            error = e
+        ))
+    })?;
+    serde_json::from_str(&data).map_err(|e| {
+        ClientError::config(t!(
+            "JSON inválido en {path}: {error}",
+            path = path,
+            error = e
+        ))
+    })
+}
+
+fn parse_address(value: &str) -> ClientResult<Address> {
+    value
+        .parse()
+        .map_err(|_| ClientError::config(t!("dirección inválida: {value}", value = value)))
 }
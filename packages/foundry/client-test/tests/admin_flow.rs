@@ -0,0 +1,134 @@
+//! Operaciones de administración de un loop y rotación del signer de elegibilidad.
+
+mod common;
+
+use client_test::events::TrustedBackendSignerUpdatedFilter;
+use client_test::functions::*;
+use common::{events, mined, revert_reason, TestNet};
+use ethers::core::rand::thread_rng;
+use ethers::prelude::*;
+
+/// `SetPercentPerPeriod(uint256)`; el cliente no lo expone porque nadie lo escucha.
+#[derive(Debug, Clone, PartialEq, Eq, EthEvent)]
+#[ethevent(name = "SetPercentPerPeriod", abi = "SetPercentPerPeriod(uint256)")]
+struct SetPercentPerPeriodFilter {
+    percent_per_period: U256,
+}
+
+#[tokio::test]
+#[ignore = "necesita anvil y los artifacts de ../out"]
+async fn admin_updates_percent_per_period() {
+    let net = TestNet::spawn().await;
+    let env = &net.env;
+    let loop_address = net.address("loop");
+
+    let receipt =
+        mined(set_percent_per_period_call(env, loop_address, U256::from(25)).unwrap()).await;
+    assert_eq!(
+        events::<SetPercentPerPeriodFilter>(&receipt),
+        vec![SetPercentPerPeriodFilter {
+            percent_per_period: U256::from(25),
+        }]
+    );
+    let details = loop_details(env, loop_address).await.unwrap();
+    assert_eq!(details.percent_per_period, U256::from(25));
+}
+
+#[tokio::test]
+#[ignore = "necesita anvil y los artifacts de ../out"]
+async fn non_admin_cannot_manage_loop() {
+    let net = TestNet::spawn().await;
+    let env = &net.env;
+    let loop_address = net.address("loop");
+    let intruder = env.bad_signer.clone().unwrap();
+    let loop_facet = env
+        .contract_at(loop_address, "LoopFacet")
+        .unwrap()
+        .connect(intruder.clone());
+
+    let calls = [
+        loop_facet
+            .method::<U256, ()>("setPercentPerPeriod", U256::from(50))
+            .unwrap(),
+        loop_facet
+            .method::<Address, ()>("withdrawDeposit", intruder.address())
+            .unwrap(),
+        loop_facet
+            .method::<Address, ()>("setTrustedBackendSigner", intruder.address())
+            .unwrap(),
+    ];
+    for call in calls {
+        let error = call.call().await.unwrap_err();
+        assert!(
+            error.as_revert().is_some(),
+            "se esperaba un revert: {error}"
+        );
+    }
+
+    let details = loop_details(env, loop_address).await.unwrap();
+    assert_eq!(details.percent_per_period, U256::from(10));
+}
+
+#[tokio::test]
+#[ignore = "necesita anvil y los artifacts de ../out"]
+async fn rotated_signer_invalidates_old_signatures() {
+    let net = TestNet::spawn().await;
+    let env = &net.env;
+    let loop_address = net.address("loop");
+    let user = net.user(0);
+    let old_signer = net.trusted_wallet();
+    let new_signer = LocalWallet::new(&mut thread_rng());
+
+    let receipt =
+        mined(set_trusted_backend_signer_call(env, loop_address, new_signer.address()).unwrap())
+            .await;
+    assert_eq!(
+        events::<TrustedBackendSignerUpdatedFilter>(&receipt),
+        vec![TrustedBackendSignerUpdatedFilter {
+            new_signer: new_signer.address(),
+        }]
+    );
+
+    // La firma del signer anterior ya no vale
+    let next_period = current_period(env, loop_address).await.unwrap() + 1;
+    let signature = sign_eligibility(&old_signer, user.address(), next_period, loop_address)
+        .await
+        .unwrap();
+    let error = claim_and_register_call(env, user.clone(), loop_address, signature)
+        .unwrap()
+        .call()
+        .await
+        .unwrap_err();
+    assert_eq!(revert_reason(&error), "Invalid eligibility signature");
+
+    // La del nuevo sí
+    let signature = sign_eligibility(&new_signer, user.address(), next_period, loop_address)
+        .await
+        .unwrap();
+    claim_and_register_as(env, user.clone(), loop_address, signature)
+        .await
+        .unwrap();
+    assert_eq!(
+        claimer_status(env, loop_address, user.address())
+            .await
+            .unwrap(),
+        (false, false)
+    );
+
+    // Reutilizar la firma en otro loop no sirve
+    let other_user = net.user(1);
+    let signature = sign_eligibility(
+        &new_signer,
+        other_user.address(),
+        next_period,
+        Address::random(),
+    )
+    .await
+    .unwrap();
+    let error = claim_and_register_call(env, other_user, loop_address, signature)
+        .unwrap()
+        .call()
+        .await
+        .unwrap_err();
+    assert_eq!(revert_reason(&error), "Invalid eligibility signature");
+}
@@ -0,0 +1,292 @@
+//! Lecturas en lote: contra fixtures construidos aquí (JSON-RPC y Multicall3)
+//! y contra anvil, comparando con las lecturas de una en una.
+
+mod common;
+
+use client_test::batch::{BatchBackend, BatchReader, ClaimerStatus, MULTICALL_ADDRESS};
+use client_test::functions::{
+    claim_and_register_as, claimer_status, current_period, current_period_data, loop_details,
+    sign_eligibility,
+};
+use client_test::transport::{ReplayTransport, RpcExchange, RpcFailure, RpcFixture};
+use client_test::ClientError;
+use common::TestNet;
+use ethers::abi::{encode, AbiDecode, AbiEncode, Token};
+use ethers::contract::multicall_contract::{
+    Aggregate3Call, Aggregate3Return, Result as Call3Result,
+};
+use ethers::prelude::*;
+use ethers::utils::id;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const LOOP: &str = "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0";
+
+fn user(index: u64) -> Address {
+    Address::from_low_u64_be(0x1000 + index)
+}
+
+fn status_calldata(user: Address) -> Bytes {
+    let mut data = id("getClaimerStatus(address)").to_vec();
+    data.extend(encode(&[Token::Address(user)]));
+    data.into()
+}
+
+fn status_return(registered: bool, claimed: bool) -> Bytes {
+    encode(&[Token::Bool(registered), Token::Bool(claimed)]).into()
+}
+
+/// `AlreadyRegistered()` como lo devuelve anvil en `error.data`.
+fn revert_failure() -> RpcFailure {
+    RpcFailure {
+        code: 3,
+        message: "execution reverted".into(),
+        data: Some(json!(Bytes::from(id("AlreadyRegistered()").to_vec()))),
+    }
+}
+
+/// Sin artifacts el motivo sale en hex: se comparan los datos del revert.
+fn assert_already_registered<T: std::fmt::Debug>(result: &Result<T, ClientError>) {
+    match result {
+        Err(ClientError::Revert { data, .. }) => {
+            assert_eq!(data.as_ref(), id("AlreadyRegistered()").as_slice())
+        }
+        other => panic!("se esperaba un revert: {other:?}"),
+    }
+}
+
+fn eth_call(to: Address, data: &Bytes, result: Value, error: Option<RpcFailure>) -> RpcExchange {
+    RpcExchange {
+        method: "eth_call".into(),
+        params: json!([{ "to": to, "data": data }, "latest"]),
+        result,
+        error,
+    }
+}
+
+#[tokio::test]
+async fn json_rpc_batches_claimer_statuses_in_chunks() {
+    let loop_address: Address = LOOP.parse().unwrap();
+    let users: Vec<Address> = (0..5).map(user).collect();
+    let mut exchanges: Vec<RpcExchange> = users
+        .iter()
+        .enumerate()
+        .map(|(i, user)| {
+            eth_call(
+                loop_address,
+                &status_calldata(*user),
+                json!(status_return(i % 2 == 0, i == 0)),
+                None,
+            )
+        })
+        .collect();
+    exchanges[3] = eth_call(
+        loop_address,
+        &status_calldata(users[3]),
+        Value::Null,
+        Some(revert_failure()),
+    );
+    let transport = Arc::new(ReplayTransport::new(RpcFixture { exchanges }));
+    let reader = BatchReader::new(transport.clone(), BatchBackend::JsonRpc).with_chunk_size(2);
+
+    let statuses = reader
+        .loop_claimer_statuses(loop_address, &users)
+        .await
+        .unwrap();
+
+    assert_eq!(reader.round_trips(), 3);
+    assert_eq!(transport.count("eth_call"), 5);
+    assert_eq!(statuses.len(), 5);
+    assert_eq!(
+        statuses[0].as_ref().unwrap(),
+        &ClaimerStatus {
+            loop_address,
+            user: users[0],
+            is_registered: true,
+            has_claimed: true,
+        }
+    );
+    let registered: Vec<bool> = [0, 1, 2, 4]
+        .iter()
+        .map(|i| statuses[*i].as_ref().unwrap().is_registered)
+        .collect();
+    assert_eq!(registered, [true, false, true, true]);
+    // El revert de uno sale en su posición sin tumbar el lote
+    assert_already_registered(&statuses[3]);
+}
+
+#[tokio::test]
+async fn multicall3_aggregates_each_chunk_into_one_eth_call() {
+    let loop_address: Address = LOOP.parse().unwrap();
+    let users: Vec<Address> = (0..3).map(user).collect();
+    let aggregated = Aggregate3Return {
+        return_data: vec![
+            Call3Result {
+                success: true,
+                return_data: status_return(true, false),
+            },
+            Call3Result {
+                success: false,
+                return_data: id("AlreadyRegistered()").to_vec().into(),
+            },
+            Call3Result {
+                success: true,
+                return_data: status_return(false, false),
+            },
+        ],
+    };
+    let exchange = RpcExchange {
+        method: "eth_call".into(),
+        params: Value::Null,
+        result: json!(Bytes::from(aggregated.encode())),
+        error: None,
+    };
+    let transport = Arc::new(ReplayTransport::new(RpcFixture {
+        exchanges: vec![exchange],
+    }));
+    let reader = BatchReader::new(
+        transport.clone(),
+        BatchBackend::Multicall3(MULTICALL_ADDRESS),
+    );
+
+    let statuses = reader
+        .loop_claimer_statuses(loop_address, &users)
+        .await
+        .unwrap();
+
+    assert_eq!(reader.round_trips(), 1);
+    assert!(statuses[0].as_ref().unwrap().is_registered);
+    assert_already_registered(&statuses[1]);
+    assert!(!statuses[2].as_ref().unwrap().is_registered);
+
+    // Lo que se mandó a Multicall3: una llamada por usuario, todas con allowFailure
+    let (_, params) = transport.requests().remove(0);
+    assert_eq!(
+        params[0]["to"]
+            .as_str()
+            .unwrap()
+            .parse::<Address>()
+            .unwrap(),
+        MULTICALL_ADDRESS
+    );
+    let data: Bytes = serde_json::from_value(params[0]["data"].clone()).unwrap();
+    let sent = Aggregate3Call::decode(&data).unwrap();
+    assert_eq!(sent.calls.len(), 3);
+    for (call, user) in sent.calls.iter().zip(&users) {
+        assert_eq!(call.target, loop_address);
+        assert!(call.allow_failure);
+        assert_eq!(call.call_data, status_calldata(*user));
+    }
+}
+
+#[tokio::test]
+async fn batch_errors_keep_transport_failures_apart() {
+    let loop_address: Address = LOOP.parse().unwrap();
+    let failure = RpcFailure {
+        code: -32000,
+        message: "header not found".into(),
+        data: None,
+    };
+    let transport = Arc::new(ReplayTransport::new(RpcFixture {
+        exchanges: vec![eth_call(
+            loop_address,
+            &status_calldata(user(0)),
+            Value::Null,
+            Some(failure),
+        )],
+    }));
+    let reader = BatchReader::new(transport, BatchBackend::JsonRpc);
+
+    // Un error JSON-RPC sin datos de revert se queda en su posición como error de transporte
+    let statuses = reader
+        .loop_claimer_statuses(loop_address, &[user(0)])
+        .await
+        .unwrap();
+    assert!(matches!(statuses[0], Err(ClientError::Transport(_))));
+
+    // Una petición que el fixture no conoce tumba el lote entero
+    let error = reader
+        .loop_claimer_statuses(loop_address, &[user(0), user(9)])
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::Transport(_)));
+}
+
+#[tokio::test]
+#[ignore = "necesita anvil y los artifacts de ../out"]
+async fn batched_reads_match_single_reads_on_anvil() {
+    let net = TestNet::spawn().await;
+    let env = &net.env;
+    let loop_address = net.address("loop");
+
+    // Un usuario registrado para el periodo siguiente y luego dentro de él
+    let wallet = net.user(0);
+    let period = current_period(env, loop_address).await.unwrap();
+    let signature = sign_eligibility(
+        &net.trusted_wallet(),
+        wallet.address(),
+        period + 1,
+        loop_address,
+    )
+    .await
+    .unwrap();
+    claim_and_register_as(env, wallet.clone(), loop_address, signature)
+        .await
+        .unwrap();
+    net.warp(60).await;
+
+    let users: Vec<Address> = (0..4).map(|i| net.user(i).address()).collect();
+
+    let multicall = BatchReader::connect(env).await.unwrap();
+    assert_eq!(
+        multicall.backend(),
+        BatchBackend::Multicall3(MULTICALL_ADDRESS)
+    );
+    let json_rpc = BatchReader::json_rpc(env).unwrap();
+
+    for reader in [&multicall, &json_rpc] {
+        let statuses = reader
+            .loop_claimer_statuses(loop_address, &users)
+            .await
+            .unwrap();
+        for (user, status) in users.iter().zip(statuses) {
+            let status = status.unwrap();
+            let single = claimer_status(env, loop_address, *user).await.unwrap();
+            assert_eq!((status.is_registered, status.has_claimed), single);
+        }
+        assert!(
+            reader
+                .loop_claimer_statuses(loop_address, &users[..1])
+                .await
+                .unwrap()[0]
+                .as_ref()
+                .unwrap()
+                .is_registered
+        );
+
+        // Una dirección sin código no es un loop: falla sólo su posición
+        let snapshots = reader
+            .loop_snapshots(&[loop_address, Address::repeat_byte(0x42)])
+            .await
+            .unwrap();
+        let snapshot = snapshots[0].as_ref().unwrap();
+        let details = loop_details(env, loop_address).await.unwrap();
+        assert_eq!(snapshot.details.token, details.token);
+        assert_eq!(snapshot.details.period_length, details.period_length);
+        let (registered, max_payout) = current_period_data(env, loop_address).await.unwrap();
+        assert_eq!(snapshot.period_data.total_registered_users, registered);
+        assert_eq!(snapshot.period_data.max_payout, max_payout);
+        assert!(snapshots[1].is_err());
+    }
+
+    // Muchos claimers en pocos viajes
+    let sweep: Vec<Address> = (0..1200).map(user).collect();
+    let reader = BatchReader::connect(env).await.unwrap();
+    let statuses = reader
+        .loop_claimer_statuses(loop_address, &sweep)
+        .await
+        .unwrap();
+    assert_eq!(statuses.len(), sweep.len());
+    assert!(statuses.iter().all(|status| status.is_ok()));
+    assert_eq!(reader.round_trips(), 3);
+}
@@ -0,0 +1,333 @@
+//! Fixture compartida por los tests de integración: levanta un anvil propio,
+//! despliega el sistema como `script/deploy/Deploy.s.sol` a partir de los
+//! artifacts de `../out` y deja un `Env` apuntando a él.
+//!
+//! Los tests que la usan llevan `#[ignore]`: se ejecutan a propósito con
+//! `cargo test -- --ignored` donde haya `anvil` y `forge build`.
+
+#![allow(dead_code)]
+
+use client_test::transport::MeteredTransport;
+use client_test::utils::{
+    contract_error_reason, load_abi, load_creation_code, warp, EnvOverrides, SelectorBook,
+    SignerClient, ARTIFACTS_DIR, DEPLOYMENTS_DIR,
+};
+use client_test::Env;
+use ethers::abi::{self, Token};
+use ethers::prelude::*;
+use ethers::utils::{hex, keccak256, Anvil, AnvilInstance};
+use eyre::Result;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+/// Helpers de `BaseScript.setUp`, en el mismo orden.
+const FACET_HELPERS: [(&str, &str); 7] = [
+    ("DiamondCutFacetHelper", "DiamondCutFacet"),
+    ("DiamondLoupeFacetHelper", "DiamondLoupeFacet"),
+    ("AccessControlFacetHelper", "AccessControlFacet"),
+    ("OrganizationFactoryHelper", "OrganizationFactoryFacet"),
+    ("OrganizationHelper", "OrganizationFacet"),
+    ("LoopFactoryHelper", "LoopFactoryFacet"),
+    ("LoopHelper", "LoopFacet"),
+];
+
+/// `MULTI_INIT_ADDRESS` de `Constants.sol`.
+const MULTI_INIT_ADDRESS: &str = "0xD1a302d1A302d1A302d1A302d1A302D1A302D1a3";
+/// `systemAdmin` fijo de `Deploy.s.sol` (cuenta 1 de anvil).
+const SYSTEM_ADMIN: &str = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+
+/// Cuentas de anvil: 0 despliega y firma elegibilidad, 1 es el systemAdmin,
+/// 2 el bad actor y de la 3 en adelante usuarios.
+const DEPLOYER: usize = 0;
+const BAD_ACTOR: usize = 2;
+const FIRST_USER: usize = 3;
+
+pub const ONE_TOKEN: u128 = 1_000_000_000_000_000_000;
+
+/// Anvil con el sistema desplegado. Al soltarlo se borra el JSON de deployment.
+pub struct TestNet {
+    pub anvil: AnvilInstance,
+    pub env: Env,
+    deployment: PathBuf,
+}
+
+impl TestNet {
+    /// Falla si no hay `anvil` o faltan los artifacts de forge: un test que
+    /// no puede ejecutarse nunca cuenta como pasado.
+    pub async fn spawn() -> Self {
+        assert!(
+            Command::new("anvil").arg("--version").output().is_ok(),
+            "anvil no está instalado"
+        );
+        assert!(
+            Path::new(ARTIFACTS_DIR).join("LoopFacet.sol").exists(),
+            "faltan los artifacts de forge en {ARTIFACTS_DIR} (forge build)"
+        );
+        Self::deploy().await.expect("despliegue en anvil")
+    }
+
+    async fn deploy() -> Result<Self> {
+        // Un chain id por instancia para no pisar ../deployments/31337.json
+        let port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+        let anvil = Anvil::new()
+            .port(port)
+            .chain_id(1_000_000 + u64::from(port))
+            .spawn();
+
+        let keys: Vec<String> = anvil
+            .keys()
+            .iter()
+            .map(|k| format!("0x{}", hex::encode(k.to_bytes())))
+            .collect();
+
+        let provider = Provider::new(MeteredTransport::new(anvil.endpoint().parse::<Http>()?));
+        let deployer: LocalWallet = anvil.keys()[DEPLOYER].clone().into();
+        let deployer = Arc::new(SignerMiddleware::new(
+            provider,
+            deployer.with_chain_id(anvil.chain_id()),
+        ));
+        let data = deploy_system(deployer).await?;
+
+        std::fs::create_dir_all(DEPLOYMENTS_DIR)?;
+        let deployment = Path::new(DEPLOYMENTS_DIR).join(format!("{}.json", anvil.chain_id()));
+        std::fs::write(&deployment, serde_json::to_string_pretty(&data)?)?;
+
+        let env = Env::setup_with(EnvOverrides {
+            rpc_url: Some(anvil.endpoint()),
+            signer_pk: None,
+            trusted_signer_pk: Some(keys[DEPLOYER].clone()),
+            bad_actor_pk: Some(keys[BAD_ACTOR].clone()),
+        })
+        .await?;
+        Ok(Self {
+            anvil,
+            env,
+            deployment,
+        })
+    }
+
+    /// Cliente firmante para la cuenta `index` de anvil (a partir de los usuarios).
+    pub fn user(&self, index: usize) -> Arc<SignerClient> {
+        let key = &self.anvil.keys()[FIRST_USER + index];
+        self.env
+            .wallet(&hex::encode(key.to_bytes()))
+            .expect("wallet de usuario")
+    }
+
+    /// Wallet con la que el backend firma la elegibilidad.
+    pub fn trusted_wallet(&self) -> LocalWallet {
+        self.env
+            .trusted_signer_pk
+            .parse()
+            .expect("TRUSTED_SIGNER_PK")
+    }
+
+    pub fn address(&self, key: &str) -> Address {
+        self.env
+            .deployment_address(key)
+            .expect("dirección de deployment")
+    }
+
+    pub async fn warp(&self, seconds: u64) {
+        warp(&self.env.provider().expect("provider"), seconds)
+            .await
+            .expect("evm_increaseTime");
+    }
+
+    pub async fn token_balance(&self, token: Address, owner: Address) -> U256 {
+        self.env
+            .contract_at(token, "TestToken")
+            .expect("ABI de TestToken")
+            .method::<Address, U256>("balanceOf", owner)
+            .expect("balanceOf")
+            .call()
+            .await
+            .expect("balanceOf")
+    }
+}
+
+impl Drop for TestNet {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.deployment);
+    }
+}
+
+/// Hace `eth_call` para obtener el valor de retorno y luego envía la TX.
+async fn send<D: abi::Detokenize>(call: ContractCall<SignerClient, D>) -> Result<D> {
+    let value = call.call().await?;
+    call.send()
+        .await?
+        .await?
+        .ok_or_else(|| eyre::eyre!("TX sin receipt"))?;
+    Ok(value)
+}
+
+async fn deploy_contract<T: abi::Tokenize>(
+    client: Arc<SignerClient>,
+    name: &str,
+    args: T,
+) -> Result<Contract<SignerClient>> {
+    let factory = ContractFactory::new(load_abi(name)?, load_creation_code(name)?.into(), client);
+    Ok(factory.deploy(args)?.send().await?)
+}
+
+/// Réplica de `Deploy._run()`; devuelve el JSON que escribiría `wrap_deployment`.
+async fn deploy_system(client: Arc<SignerClient>) -> Result<serde_json::Value> {
+    let deployer = client.address();
+    let trusted_signer = deployer;
+    let salt = keccak256("MASSIVA_LA_SALT_BRO");
+
+    let registry = deploy_contract(client.clone(), "FacetRegistry", ()).await?;
+
+    let mut helpers = Vec::new();
+    let mut facets = Vec::new();
+    for (helper_name, _) in FACET_HELPERS {
+        let helper = deploy_contract(client.clone(), helper_name, ()).await?;
+        let creation_code = helper
+            .method::<_, Bytes>("creationCode", ())?
+            .call()
+            .await?;
+        let selectors = helper
+            .method::<_, Vec<[u8; 4]>>("selectors", ())?
+            .call()
+            .await?;
+        let facet =
+            send(registry.method::<_, Address>("deployFacet", (salt, creation_code, selectors))?)
+                .await?;
+        helpers.push(helper);
+        facets.push(facet);
+    }
+
+    let factory = deploy_contract(client.clone(), "DiamondFactory", ()).await?;
+
+    // FacetCutAction.Add == 0
+    let mut base_facets = Vec::new();
+    for (helper, facet) in helpers.iter().zip(&facets) {
+        let cut = helper
+            .method::<_, Token>("makeFacetCut", (*facet, 0u8))?
+            .call()
+            .await?;
+        base_facets.push(cut);
+    }
+
+    let system_admin: Address = SYSTEM_ADMIN.parse()?;
+    let init_args = [
+        (0, abi::encode(&[Token::Address(system_admin)])),
+        (1, Vec::new()),
+        (2, abi::encode(&[Token::Address(deployer)])),
+        (
+            3,
+            abi::encode(&[
+                Token::Address(factory.address()),
+                Token::Address(registry.address()),
+            ]),
+        ),
+        (
+            5,
+            abi::encode(&[
+                Token::Address(factory.address()),
+                Token::Address(registry.address()),
+                Token::Address(trusted_signer),
+            ]),
+        ),
+    ];
+    let mut init_data = Vec::new();
+    for (i, args) in init_args {
+        let multi_init = helpers[i]
+            .method::<_, Token>("makeInitData", (facets[i], Bytes::from(args)))?
+            .call()
+            .await?;
+        init_data.push(multi_init);
+    }
+
+    let init_params = Token::Tuple(vec![
+        Token::Array(base_facets),
+        Token::Address(MULTI_INIT_ADDRESS.parse()?),
+        Token::Bytes(abi::encode(&[Token::Array(init_data)])),
+    ]);
+    let system_diamond =
+        send(factory.method::<_, Address>("createDiamond", (init_params,))?).await?;
+    send(factory.method::<_, ()>("setSystemDiamond", system_diamond)?).await?;
+
+    let organization_factory = Contract::new(
+        system_diamond,
+        load_abi("OrganizationFactoryFacet")?,
+        client.clone(),
+    );
+    let organization = send(organization_factory.method::<_, Address>(
+        "createOrganization",
+        (
+            "1Hive".to_string(),
+            deployer,
+            "1Hive DAO Organization".to_string(),
+        ),
+    )?)
+    .await?;
+
+    let token = deploy_contract(
+        client.clone(),
+        "TestToken",
+        ("Honey".to_string(), "HNY".to_string()),
+    )
+    .await?;
+
+    let organization_facet =
+        Contract::new(organization, load_abi("OrganizationFacet")?, client.clone());
+    let loop_address = send(organization_facet.method::<_, Address>(
+        "createNewLoop",
+        (
+            system_diamond,
+            token.address(),
+            U256::from(60),
+            U256::from(10),
+        ),
+    )?)
+    .await?;
+    send(token.method::<_, bool>("transfer", (loop_address, U256::from(1000 * ONE_TOKEN)))?)
+        .await?;
+
+    let mut data = json!({
+        "facet_registry": registry.address(),
+        "factory_diamond": factory.address(),
+        "system_diamond": system_diamond,
+        "test_token_address": token.address(),
+        "organization": organization,
+        "loop": loop_address,
+        "networkName": client.signer().chain_id().to_string(),
+    });
+    for ((_, facet_name), facet) in FACET_HELPERS.iter().zip(&facets) {
+        data[facet_name] = json!(facet);
+    }
+    Ok(data)
+}
+
+/// Motivo del revert de una llamada fallida (`Error(string)` o custom error).
+pub fn revert_reason<M: Middleware>(error: &ContractError<M>) -> String {
+    contract_error_reason(error, &SelectorBook::from_artifacts().expect("artifacts"))
+}
+
+/// Envía la llamada y espera el receipt; falla si la TX revierte.
+pub async fn mined<D: abi::Detokenize>(call: ContractCall<SignerClient, D>) -> TransactionReceipt {
+    let receipt = call
+        .send()
+        .await
+        .expect("envío de la TX")
+        .await
+        .expect("receipt")
+        .expect("TX sin receipt");
+    assert_eq!(receipt.status, Some(U64::one()), "TX revertida");
+    receipt
+}
+
+/// Eventos de tipo `E` contenidos en un receipt.
+pub fn events<E: EthEvent>(receipt: &TransactionReceipt) -> Vec<E> {
+    receipt
+        .logs
+        .iter()
+        .filter_map(|log| ethers::contract::parse_log::<E>(log.clone()).ok())
+        .collect()
+}
@@ -0,0 +1,151 @@
+//! Planificador de `diamondCut` contra el estado del loupe grabado en
+//! `tests/fixtures/diamond_loupe.json`, sin nodo ni artifacts.
+
+use client_test::diamond::{
+    loupe_state, namespace_changes, plan_cuts, DiamondState, FacetCut, FacetCutAction, NewFacet,
+};
+use client_test::transport::{ReplayTransport, RpcFixture};
+use ethers::abi::parse_abi;
+use ethers::prelude::*;
+use ethers::utils::{id, keccak256};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// El diamond enruta `diamondCut` a sí mismo (inmutable), `claim()`,
+/// `register()` y `legacyPayout()` a `OLD` y `owner()` a `KEEP`.
+const DIAMOND: u8 = 0xd1;
+const OLD: u8 = 0x01;
+const KEEP: u8 = 0x02;
+const NEW: u8 = 0x03;
+
+fn address(byte: u8) -> Address {
+    Address::repeat_byte(byte)
+}
+
+fn replay() -> Provider<ReplayTransport> {
+    let fixture = RpcFixture::load("tests/fixtures/diamond_loupe.json").unwrap();
+    ReplayTransport::provider(fixture)
+}
+
+async fn diamond_state() -> DiamondState {
+    let abi = parse_abi(&[
+        "function facetAddresses() external view returns (address[])",
+        "function facetFunctionSelectors(address) external view returns (bytes4[])",
+    ])
+    .unwrap();
+    let loupe = Contract::new(address(DIAMOND), abi, Arc::new(replay()));
+    loupe_state(&loupe).await.unwrap()
+}
+
+fn new_facet(signatures: &[&str]) -> NewFacet {
+    NewFacet {
+        name: "LoopFacet".to_string(),
+        address: address(NEW),
+        selectors: signatures.iter().map(id).collect(),
+    }
+}
+
+#[tokio::test]
+async fn loupe_state_routes_every_selector() {
+    let state = diamond_state().await;
+    assert_eq!(state.diamond, address(DIAMOND));
+    assert_eq!(state.routes.len(), 5);
+    assert_eq!(state.routes[&id("owner()")], address(KEEP));
+    assert_eq!(
+        state.selectors_of(address(OLD)).len(),
+        3,
+        "claim, register y legacyPayout"
+    );
+}
+
+#[tokio::test]
+async fn plans_add_replace_and_remove_against_the_loupe() {
+    let state = diamond_state().await;
+    let facet = new_facet(&["claim()", "register()", "payout()", "owner()"]);
+    // `owner()` se repite en otra faceta de la lista: gana la primera
+    let duplicate = NewFacet {
+        address: address(0x04),
+        ..new_facet(&["owner()"])
+    };
+
+    let cuts = plan_cuts(&state, &[facet, duplicate]).unwrap();
+    assert_eq!(
+        cuts,
+        vec![
+            FacetCut {
+                facet: address(NEW),
+                action: FacetCutAction::Add,
+                selectors: vec![id("payout()")],
+            },
+            FacetCut {
+                facet: address(NEW),
+                action: FacetCutAction::Replace,
+                selectors: vec![id("claim()"), id("register()"), id("owner()")],
+            },
+            // Lo que la faceta reemplazada tenía y nadie aporta se elimina
+            FacetCut {
+                facet: address(OLD),
+                action: FacetCutAction::Remove,
+                selectors: vec![id("legacyPayout()")],
+            },
+        ]
+    );
+
+    // Volver a planificar con las facetas ya instaladas no produce cortes
+    let mut upgraded = state.clone();
+    for selector in [id("claim()"), id("register()"), id("legacyPayout()")] {
+        upgraded.routes.insert(selector, address(NEW));
+    }
+    let cuts = plan_cuts(&upgraded, &[new_facet(&["claim()", "register()"])]).unwrap();
+    assert!(cuts.is_empty(), "{cuts:?}");
+}
+
+#[tokio::test]
+async fn rejects_replacing_immutable_selectors() {
+    let state = diamond_state().await;
+    let facet = new_facet(&[
+        "claim()",
+        "diamondCut((address,uint8,bytes4[])[],address,bytes)",
+    ]);
+    let error = plan_cuts(&state, &[facet]).unwrap_err();
+    assert!(error.to_string().contains("0x1f931c1c"), "{error}");
+}
+
+#[tokio::test]
+async fn detects_storage_namespace_changes_of_replaced_facets() {
+    let state = diamond_state().await;
+    let namespaces: BTreeMap<[u8; 32], String> =
+        ["diamond.loop.storage", "diamond.loop.v2.storage"]
+            .into_iter()
+            .map(|name| (keccak256(name), name.to_string()))
+            .collect();
+    // La faceta desplegada usa `diamond.loop.storage`; la nueva, la v2
+    let mut code = vec![0x60, 0x80, 0x7f];
+    code.extend(keccak256("diamond.loop.v2.storage"));
+    let facet = new_facet(&["claim()", "payout()"]);
+
+    let provider = replay();
+    let changes = namespace_changes(&provider, &state, &[(facet, code.clone())], &namespaces)
+        .await
+        .unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].facet, "LoopFacet");
+    assert_eq!(changes[0].old_facet, address(OLD));
+    assert_eq!(
+        changes[0].added,
+        vec!["diamond.loop.v2.storage".to_string()]
+    );
+    assert_eq!(changes[0].removed, vec!["diamond.loop.storage".to_string()]);
+
+    // Una faceta que solo añade selectores no reemplaza a nadie
+    let changes = namespace_changes(
+        &provider,
+        &state,
+        &[(new_facet(&["payout()"]), code)],
+        &namespaces,
+    )
+    .await
+    .unwrap();
+    assert!(changes.is_empty());
+    assert_eq!(provider.as_ref().count("eth_getCode"), 1);
+}
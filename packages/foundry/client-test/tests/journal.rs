@@ -0,0 +1,388 @@
+//! Diario de transacciones: reconstrucción del estado desde el fichero, líneas
+//! cortadas por un crash, reanudación de TX firmadas contra fixtures y del
+//! alta org → loop → fondeo en anvil.
+
+mod common;
+
+use client_test::cli::{call_key, command_journal};
+use client_test::functions::{organization_info, token_balance, transfer_call};
+use client_test::journal::{EntryStatus, Journal, JournalRecord, Onboarding};
+use client_test::transport::{MeteredTransport, ReplayTransport, RpcFixture};
+use client_test::utils::SignerClient;
+use common::{TestNet, ONE_TOKEN};
+use ethers::abi::parse_abi;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::utils::keccak256;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+const TOKEN: &str = "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9";
+/// Primera cuenta de anvil.
+const SIGNER_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Fichero de diario propio del test, vacío.
+fn journal_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "client-test-journal-{}-{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn intent(key: &str) -> JournalRecord {
+    JournalRecord::Intent {
+        key: key.into(),
+        from: Address::repeat_byte(1),
+        to: Address::repeat_byte(2),
+        data: Bytes::from(vec![0xde, 0xad]),
+        value: U256::zero(),
+        timestamp: 1,
+    }
+}
+
+fn signed(key: &str, byte: u8) -> JournalRecord {
+    JournalRecord::Signed {
+        key: key.into(),
+        nonce: U256::from(byte),
+        tx_hash: H256::repeat_byte(byte),
+        raw: Bytes::from(vec![byte]),
+    }
+}
+
+#[test]
+fn rebuilds_entries_and_drops_a_torn_last_line() {
+    let path = journal_path("rebuild");
+    {
+        let mut journal = Journal::open(&path).unwrap();
+        journal.append(intent("a")).unwrap();
+        journal.append(signed("a", 1)).unwrap();
+        journal
+            .append(JournalRecord::Failed {
+                key: "a".into(),
+                reason: "nonce too low".into(),
+            })
+            .unwrap();
+        journal.append(intent("a")).unwrap();
+        journal.append(signed("a", 2)).unwrap();
+        journal.append(intent("b")).unwrap();
+    }
+    // El proceso murió escribiendo una línea
+    fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap()
+        .write_all(br#"{"record":"signed","key":"b","non"#)
+        .unwrap();
+
+    let mut journal = Journal::open(&path).unwrap();
+    let a = journal.entry("a").unwrap();
+    assert_eq!(a.status(), EntryStatus::Signed);
+    assert_eq!(a.attempts, 2);
+    assert_eq!(a.tx_hash, Some(H256::repeat_byte(2)));
+    assert_eq!(a.failure, None);
+    assert_eq!(journal.entry("b").unwrap().status(), EntryStatus::Intended);
+    assert_eq!(journal.in_flight().count(), 1);
+
+    // Lo que se añade después queda en una línea propia
+    journal.append(signed("b", 3)).unwrap();
+    // Abierto por otro, el diario está bloqueado hasta que se cierra
+    assert!(Journal::open(&path).is_err());
+    drop(journal);
+    let journal = Journal::open(&path).unwrap();
+    assert_eq!(journal.entry("b").unwrap().status(), EntryStatus::Signed);
+    let keys: Vec<&str> = journal.entries().iter().map(|e| e.key.as_str()).collect();
+    assert_eq!(keys, ["a", "b"]);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rejects_corrupt_lines_in_the_middle() {
+    let path = journal_path("corrupt");
+    fs::write(&path, "{\"record\":\"nope\"}\n").unwrap();
+    assert!(Journal::open(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+/// Signer sobre el fixture `name` y un `transfer` del token construido con él.
+fn replay_transfer(
+    name: &str,
+) -> (
+    Arc<SignerClient<ReplayTransport>>,
+    ContractCall<SignerClient<ReplayTransport>, bool>,
+) {
+    let fixture = RpcFixture::load(format!("tests/fixtures/{name}.json")).unwrap();
+    let provider = ReplayTransport::provider(fixture).interval(Duration::from_millis(1));
+    let wallet = SIGNER_KEY
+        .parse::<LocalWallet>()
+        .unwrap()
+        .with_chain_id(31337u64);
+    let client = Arc::new(SignerMiddleware::new(provider, wallet));
+    let abi = parse_abi(&["function transfer(address,uint256) returns (bool)"]).unwrap();
+    let call = Contract::new(TOKEN.parse::<Address>().unwrap(), abi, client.clone())
+        .method::<_, bool>(
+            "transfer",
+            (Address::repeat_byte(0x42), U256::from(ONE_TOKEN)),
+        )
+        .unwrap();
+    (client, call)
+}
+
+/// Diario con la TX de `call` ya firmada como `tx_hash`: el crash fue justo
+/// después de guardarla.
+fn journal_with_signed(
+    name: &str,
+    client: &SignerClient<ReplayTransport>,
+    call: &ContractCall<SignerClient<ReplayTransport>, bool>,
+    tx_hash: H256,
+) -> (PathBuf, Journal) {
+    let path = journal_path(name);
+    let mut journal = Journal::open(&path).unwrap();
+    journal
+        .append(JournalRecord::Intent {
+            key: "transfer".into(),
+            from: client.address(),
+            to: TOKEN.parse().unwrap(),
+            data: call.tx.data().cloned().unwrap(),
+            value: U256::zero(),
+            timestamp: 0,
+        })
+        .unwrap();
+    journal
+        .append(JournalRecord::Signed {
+            key: "transfer".into(),
+            nonce: U256::from(7),
+            tx_hash,
+            raw: Bytes::from(vec![0x02, 0x77]),
+        })
+        .unwrap();
+    (path, journal)
+}
+
+#[tokio::test]
+async fn signed_entry_is_rebroadcast_as_saved() {
+    let tx_hash = H256::repeat_byte(0x77);
+    let (client, call) = replay_transfer("journal_rebroadcast");
+    let (path, mut journal) = journal_with_signed("rebroadcast", &client, &call, tx_hash);
+
+    let sent = journal.send("transfer", &client, call).await.unwrap();
+    assert!(sent.resumed);
+    assert_eq!(sent.tx_hash, tx_hash);
+    assert_eq!(
+        journal.entry("transfer").unwrap().status(),
+        EntryStatus::Mined
+    );
+
+    // Se difunden los bytes guardados: ni simulación ni firma nueva
+    let replay = client.provider().as_ref();
+    let raw = replay
+        .requests()
+        .into_iter()
+        .find(|(method, _)| method == "eth_sendRawTransaction")
+        .map(|(_, params)| params)
+        .unwrap();
+    assert_eq!(raw, serde_json::json!(["0x0277"]));
+    assert_eq!(replay.count("eth_call"), 0);
+    assert_eq!(replay.count("eth_getTransactionCount"), 0);
+
+    drop(journal);
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn nonce_too_low_on_rebroadcast_recovers_the_mined_receipt() {
+    let tx_hash = H256::repeat_byte(0x88);
+    let (client, call) = replay_transfer("journal_nonce_too_low_mined");
+    let (path, mut journal) = journal_with_signed("nonce-too-low", &client, &call, tx_hash);
+
+    // Se minó entre la consulta del receipt y el reenvío: el nodo contesta
+    // "nonce too low", pero el receipt ya está y no se firma otra
+    let sent = journal.send("transfer", &client, call).await.unwrap();
+    assert!(sent.resumed);
+    assert_eq!(sent.tx_hash, tx_hash);
+    let entry = journal.entry("transfer").unwrap();
+    assert_eq!(entry.status(), EntryStatus::Mined);
+    assert_eq!(entry.attempts, 1);
+    let replay = client.provider().as_ref();
+    assert_eq!(replay.count("eth_sendRawTransaction"), 1);
+    assert_eq!(replay.count("eth_getTransactionReceipt"), 2);
+    assert_eq!(replay.count("eth_getTransactionCount"), 0);
+
+    drop(journal);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn command_keys_identify_the_call() {
+    let provider = Provider::new(MeteredTransport::new(
+        "http://127.0.0.1:8545".parse::<Http>().unwrap(),
+    ));
+    let wallet = SIGNER_KEY.parse::<LocalWallet>().unwrap();
+    let client = SignerMiddleware::new(provider, wallet.clone().with_chain_id(31337u64));
+    let transfer = |amount: u64| -> TypedTransaction {
+        TransactionRequest::new()
+            .to(TOKEN.parse::<Address>().unwrap())
+            .data(vec![0xa9, 0x05, 0x9c, 0xbb, amount as u8])
+            .into()
+    };
+
+    let key = call_key("transfer", &client, &transfer(1));
+    assert!(key.starts_with("transfer/0x"));
+    assert_eq!(key, call_key("transfer", &client, &transfer(1)));
+    assert_ne!(key, call_key("transfer", &client, &transfer(2)));
+    // Otra red es otra TX aunque los datos coincidan
+    let other_chain = SignerMiddleware::new(client.provider().clone(), wallet.with_chain_id(1u64));
+    assert_ne!(key, call_key("transfer", &other_chain, &transfer(1)));
+    let from = client.address();
+    assert_eq!(
+        command_journal("transfer", from),
+        PathBuf::from(format!(".journal/transfer-{:?}.jsonl", from))
+    );
+    // Otro remitente escribe en otro diario y no choca con el lock de este
+    assert_ne!(
+        command_journal("transfer", from),
+        command_journal("transfer", Address::repeat_byte(0x11))
+    );
+}
+
+#[tokio::test]
+#[ignore = "necesita anvil y los artifacts de ../out"]
+async fn onboarding_resumes_without_double_sending() {
+    let net = TestNet::spawn().await;
+    let env = &net.env;
+    let signer = env.signer().unwrap();
+    let provider = env.provider().unwrap();
+    let nonce = || async {
+        provider
+            .get_transaction_count(signer.address(), None)
+            .await
+            .unwrap()
+    };
+    let path = journal_path("onboarding");
+    let plan = Onboarding {
+        id: "acme".into(),
+        name: "Acme".into(),
+        description: "Acme DAO".into(),
+        admin: signer.address(),
+        period_length: U256::from(60),
+        percent_per_period: U256::from(10),
+        funding: U256::from(250 * ONE_TOKEN),
+    };
+
+    let before = nonce().await;
+    let first = plan
+        .run(env, &mut Journal::open(&path).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(nonce().await, before + 3);
+    assert!(first.steps.iter().all(|step| !step.resumed));
+    assert_eq!(
+        organization_info(env, first.organization)
+            .await
+            .unwrap()
+            .name,
+        "Acme"
+    );
+    assert_eq!(
+        token_balance(env, first.token, first.loop_address)
+            .await
+            .unwrap(),
+        U256::from(250 * ONE_TOKEN)
+    );
+
+    // Otra ejecución con el diario completo no envía nada
+    let again = plan
+        .run(env, &mut Journal::open(&path).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(nonce().await, before + 3);
+    assert!(again.steps.iter().all(|step| step.resumed));
+    assert_eq!(again.loop_address, first.loop_address);
+
+    // Crash tras difundir el fondeo y antes de guardar su receipt: se quita la
+    // última línea y el receipt se recupera de la red
+    let contents = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert!(lines.last().unwrap().contains("\"mined\""));
+    fs::write(&path, lines[..lines.len() - 1].join("\n") + "\n").unwrap();
+    let journal = Journal::open(&path).unwrap();
+    assert_eq!(
+        journal
+            .entry(&plan.key(Onboarding::FUND_LOOP))
+            .unwrap()
+            .status(),
+        EntryStatus::Signed
+    );
+    drop(journal);
+    let resumed = plan
+        .run(env, &mut Journal::open(&path).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(nonce().await, before + 3);
+    assert_eq!(resumed.steps[2].tx_hash, first.steps[2].tx_hash);
+    assert!(resumed.steps[2].resumed);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+#[ignore = "necesita anvil y los artifacts de ../out"]
+async fn signed_but_unsent_transaction_is_broadcast_as_saved() {
+    let net = TestNet::spawn().await;
+    let env = &net.env;
+    let signer = env.signer().unwrap();
+    let token = net.address("test_token_address");
+    let to = net.user(0).address();
+    let path = journal_path("unsent");
+    let call = transfer_call(env, token, to, U256::from(ONE_TOKEN)).unwrap();
+
+    // Crash justo después de guardar la TX firmada: nunca llegó al nodo
+    let mut tx = call.tx.clone();
+    signer.fill_transaction(&mut tx, None).await.unwrap();
+    let signature = signer.signer().sign_transaction(&tx).await.unwrap();
+    let raw = tx.rlp_signed(&signature);
+    let tx_hash = H256::from(keccak256(&raw));
+    {
+        let mut journal = Journal::open(&path).unwrap();
+        journal
+            .append(JournalRecord::Intent {
+                key: "transfer".into(),
+                from: signer.address(),
+                to: token,
+                data: call.tx.data().cloned().unwrap(),
+                value: U256::zero(),
+                timestamp: 0,
+            })
+            .unwrap();
+        journal
+            .append(JournalRecord::Signed {
+                key: "transfer".into(),
+                nonce: *tx.nonce().unwrap(),
+                tx_hash,
+                raw,
+            })
+            .unwrap();
+    }
+
+    let mut journal = Journal::open(&path).unwrap();
+    let sent = journal.send("transfer", &signer, call).await.unwrap();
+    assert!(sent.resumed);
+    assert_eq!(sent.tx_hash, tx_hash);
+    assert_eq!(
+        journal.entry("transfer").unwrap().status(),
+        EntryStatus::Mined
+    );
+    assert_eq!(net.token_balance(token, to).await, U256::from(ONE_TOKEN));
+
+    // Con otros datos la misma clave no se reutiliza
+    let other = transfer_call(env, token, to, U256::from(2 * ONE_TOKEN)).unwrap();
+    assert!(journal.send("transfer", &signer, other).await.is_err());
+
+    fs::remove_file(&path).unwrap();
+}
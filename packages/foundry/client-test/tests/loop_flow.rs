@@ -0,0 +1,146 @@
+//! Ciclo de vida completo de un loop contra un anvil local:
+//! organización → loop → fondeo → registro → claim → retiro.
+
+mod common;
+
+use client_test::events::{ClaimFilter, RegisterFilter, WithdrawFilter};
+use client_test::functions::*;
+use common::{events, mined, TestNet, ONE_TOKEN};
+use ethers::prelude::*;
+
+const PERIOD_LENGTH: u64 = 60;
+const PERCENT_PER_PERIOD: u64 = 10;
+
+#[tokio::test]
+#[ignore = "necesita anvil y los artifacts de ../out"]
+async fn organization_loop_claim_and_withdraw() {
+    let net = TestNet::spawn().await;
+    let env = &net.env;
+    let admin = env.signer().unwrap().address();
+
+    let (_, organization) = create_organization(env, "Gyralis", admin, "org de integración")
+        .await
+        .unwrap();
+    let info = organization_info(env, organization).await.unwrap();
+    assert_eq!(info.admin, admin);
+
+    let (_, loop_address) = create_loop_confirmed(
+        env,
+        organization,
+        U256::from(PERIOD_LENGTH),
+        U256::from(PERCENT_PER_PERIOD),
+    )
+    .await
+    .unwrap();
+    let details = loop_details(env, loop_address).await.unwrap();
+    assert_eq!(details.token, net.address("test_token_address"));
+    assert_eq!(details.period_length, U256::from(PERIOD_LENGTH));
+    assert_eq!(details.percent_per_period, U256::from(PERCENT_PER_PERIOD));
+
+    // Fondeo
+    let deposit = U256::from(500 * ONE_TOKEN);
+    mined(fund_loop_call(env, details.token, loop_address, deposit).unwrap()).await;
+    assert_eq!(
+        net.token_balance(details.token, loop_address).await,
+        deposit
+    );
+
+    // Registro: la firma cubre el periodo siguiente
+    let user = net.user(0);
+    let period = current_period(env, loop_address).await.unwrap();
+    let signature = sign_eligibility(
+        &net.trusted_wallet(),
+        user.address(),
+        period + 1,
+        loop_address,
+    )
+    .await
+    .unwrap();
+    let receipt = claim_and_register_as(env, user.clone(), loop_address, signature)
+        .await
+        .unwrap();
+    assert_eq!(
+        events::<RegisterFilter>(&receipt),
+        vec![RegisterFilter {
+            sender: user.address(),
+            period_number: period + 1,
+        }]
+    );
+    assert!(claims_in_receipt(&receipt).is_empty());
+
+    // Siguiente periodo: claimAndRegister cobra y se vuelve a registrar
+    net.warp(PERIOD_LENGTH).await;
+    let period = current_period(env, loop_address).await.unwrap();
+    assert_eq!(
+        claimer_status(env, loop_address, user.address())
+            .await
+            .unwrap(),
+        (true, false)
+    );
+    let signature = sign_eligibility(
+        &net.trusted_wallet(),
+        user.address(),
+        period + 1,
+        loop_address,
+    )
+    .await
+    .unwrap();
+    let before = net.token_balance(details.token, user.address()).await;
+    let receipt = claim_and_register_as(env, user.clone(), loop_address, signature)
+        .await
+        .unwrap();
+
+    // Único registrado: cobra todo el máximo del periodo
+    let payout = deposit * PERCENT_PER_PERIOD / 100;
+    assert_eq!(
+        claims_in_receipt(&receipt),
+        vec![ClaimFilter {
+            claimer: user.address(),
+            period_number: period,
+            payout,
+        }]
+    );
+    assert_eq!(events::<RegisterFilter>(&receipt).len(), 1);
+    assert_eq!(
+        net.token_balance(details.token, user.address()).await - before,
+        payout
+    );
+    assert_eq!(
+        claimer_status(env, loop_address, user.address())
+            .await
+            .unwrap(),
+        (true, true)
+    );
+
+    // Un segundo claim en el mismo periodo revierte
+    let claim = env
+        .contract_at(loop_address, "LoopFacet")
+        .unwrap()
+        .connect(user.clone())
+        .method::<(), ()>("claim", ())
+        .unwrap();
+    let error = claim.call().await.unwrap_err();
+    assert_eq!(common::revert_reason(&error), "CannotClaim()");
+
+    // Retiro de todo el saldo restante
+    let remaining = net.token_balance(details.token, loop_address).await;
+    assert_eq!(remaining, deposit - payout);
+    let admin_before = net.token_balance(details.token, admin).await;
+    let receipt = mined(withdraw_deposit_call(env, loop_address, admin).unwrap()).await;
+    assert_eq!(
+        events::<WithdrawFilter>(&receipt),
+        vec![WithdrawFilter {
+            admin,
+            to: admin,
+            amount: remaining,
+        }]
+    );
+    assert!(net
+        .token_balance(details.token, loop_address)
+        .await
+        .is_zero());
+    assert_eq!(
+        net.token_balance(details.token, admin).await - admin_before,
+        remaining
+    );
+}
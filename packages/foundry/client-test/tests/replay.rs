@@ -0,0 +1,294 @@
+//! Lógica del cliente contra respuestas JSON-RPC grabadas en `tests/fixtures`,
+//! sin nodo. El último test graba contra anvil y comprueba que la
+//! reproducción da lo mismo que el nodo real.
+
+mod common;
+
+use client_test::engine::{ResubmitPolicy, TxEngine};
+use client_test::events::{
+    find_loop_created_event_with, wait_loop_created_event, LoopCreatedEvent, RegisterFilter,
+};
+use client_test::functions::{claims_in_receipt, create_loop_confirmed};
+use client_test::transport::{MeteredTransport, RecordingTransport, ReplayTransport, RpcFixture};
+use client_test::utils::SelectorBook;
+use client_test::ClientError;
+use common::{events, TestNet};
+use ethers::abi::{encode, parse_abi, Token};
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use std::sync::Arc;
+use std::time::Duration;
+
+const ORGANIZATION: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+const LOOP: &str = "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0";
+const TOKEN: &str = "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9";
+const USER: &str = "0x90F79bf6EB2c4f870365E785982E1f101E93b906";
+/// Primera cuenta de anvil.
+const ENGINE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn replay(name: &str) -> Provider<ReplayTransport> {
+    let fixture = RpcFixture::load(format!("tests/fixtures/{name}.json")).unwrap();
+    ReplayTransport::provider(fixture)
+}
+
+fn tx_hash(byte: u8) -> H256 {
+    H256::repeat_byte(byte)
+}
+
+#[tokio::test]
+async fn finds_loop_created_in_organization_logs() {
+    let provider = replay("loop_created");
+    let organization: Address = ORGANIZATION.parse().unwrap();
+
+    // El receipt trae también el `LoopCreated` del LoopFactory: se ignora
+    let event = find_loop_created_event_with(&provider, organization, tx_hash(0x11))
+        .await
+        .unwrap();
+    assert_eq!(
+        event,
+        Some(LoopCreatedEvent {
+            loop_address: LOOP.parse().unwrap(),
+            token: TOKEN.parse().unwrap(),
+            period_length: U256::from(60),
+            percent_per_period: U256::from(10),
+        })
+    );
+
+    let event = find_loop_created_event_with(&provider, organization, tx_hash(0x22))
+        .await
+        .unwrap();
+    assert_eq!(event, None);
+}
+
+#[tokio::test]
+async fn waits_for_the_loop_created_receipt_instead_of_sleeping() {
+    let provider = replay("loop_created").interval(Duration::from_millis(1));
+    let organization: Address = ORGANIZATION.parse().unwrap();
+
+    // La primera consulta aún no la ve minada: se sigue sondeando
+    let event = wait_loop_created_event(&provider, organization, tx_hash(0x11))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.loop_address, LOOP.parse::<Address>().unwrap());
+    assert_eq!(provider.as_ref().count("eth_getTransactionByHash"), 2);
+}
+
+#[tokio::test]
+async fn loop_created_lookup_maps_missing_and_malformed_data() {
+    let provider = replay("loop_created");
+    let organization: Address = ORGANIZATION.parse().unwrap();
+
+    let error = find_loop_created_event_with(&provider, organization, tx_hash(0x33))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::NotConfirmed(hash) if hash == tx_hash(0x33)));
+
+    let error = find_loop_created_event_with(&provider, organization, tx_hash(0x44))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::Decode(_)));
+
+    // Una petición que el fixture no conoce es un error de transporte
+    let error = find_loop_created_event_with(&provider, organization, tx_hash(0x99))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::Transport(_)));
+}
+
+#[tokio::test]
+async fn decodes_claim_and_register_from_receipt() {
+    let provider = replay("claim_receipt");
+    let receipt = provider
+        .get_transaction_receipt(tx_hash(0x55))
+        .await
+        .unwrap()
+        .unwrap();
+    let user: Address = USER.parse().unwrap();
+
+    let claims = claims_in_receipt(&receipt);
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].claimer, user);
+    assert_eq!(claims[0].period_number, U256::from(7));
+    assert_eq!(claims[0].payout, U256::from(50) * U256::exp10(18));
+    assert_eq!(
+        events::<RegisterFilter>(&receipt),
+        vec![RegisterFilter {
+            sender: user,
+            period_number: U256::from(8),
+        }]
+    );
+}
+
+fn loop_contract(provider: Provider<ReplayTransport>) -> Contract<Provider<ReplayTransport>> {
+    let abi = parse_abi(&["function claimAndRegister(bytes)"]).unwrap();
+    Contract::new(LOOP.parse::<Address>().unwrap(), abi, Arc::new(provider))
+}
+
+#[tokio::test]
+async fn maps_reverts_and_node_errors() {
+    let call = loop_contract(replay("revert"))
+        .method::<Bytes, ()>("claimAndRegister", Bytes::default())
+        .unwrap();
+    let error = ClientError::from(call.call().await.unwrap_err());
+    match error {
+        ClientError::Revert { reason, .. } => {
+            assert_eq!(reason, "Invalid eligibility signature")
+        }
+        other => panic!("se esperaba un revert: {other:?}"),
+    }
+
+    let call = loop_contract(replay("node_error"))
+        .method::<Bytes, ()>("claimAndRegister", Bytes::default())
+        .unwrap();
+    let error = ClientError::from(call.call().await.unwrap_err());
+    assert!(matches!(error, ClientError::Transport(_)));
+}
+
+#[test]
+fn custom_errors_come_from_the_decoded_revert_not_the_reason() {
+    let mut book = SelectorBook::default();
+    book.add_abi(
+        "LoopFacet",
+        &parse_abi(&["error AlreadyRegistered(address)"]).unwrap(),
+    );
+    let user: Address = USER.parse().unwrap();
+
+    let mut data = ethers::utils::id("AlreadyRegistered(address)").to_vec();
+    data.extend(encode(&[Token::Address(user)]));
+    let error = ClientError::revert_with(&book, data.into());
+    assert!(error.is_custom_error("AlreadyRegistered"));
+    match error {
+        ClientError::Revert {
+            custom: Some(custom),
+            ..
+        } => assert_eq!(custom.args, vec![Token::Address(user)]),
+        other => panic!("se esperaba un custom error: {other:?}"),
+    }
+
+    // Un `Error(string)` cuyo mensaje parece un custom error no lo es
+    let mut data = ethers::utils::id("Error(string)").to_vec();
+    data.extend(encode(&[Token::String("AlreadyRegistered(0x1)".into())]));
+    let error = ClientError::revert_with(&book, data.into());
+    assert_eq!(error.custom_error(), None);
+}
+
+/// Como `replay`, pero detrás del transporte medido que usa `Env::provider()`.
+fn metered(name: &str) -> Provider<MeteredTransport<ReplayTransport>> {
+    let fixture = RpcFixture::load(format!("tests/fixtures/{name}.json")).unwrap();
+    Provider::new(MeteredTransport::new(ReplayTransport::new(fixture)))
+}
+
+#[tokio::test]
+async fn metered_transport_keeps_results_and_rpc_errors() {
+    let receipt = metered("claim_receipt")
+        .get_transaction_receipt(tx_hash(0x55))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(claims_in_receipt(&receipt).len(), 1);
+
+    // El revert sigue llegando como respuesta JSON-RPC con su motivo
+    let abi = parse_abi(&["function claimAndRegister(bytes)"]).unwrap();
+    let call = Contract::new(
+        LOOP.parse::<Address>().unwrap(),
+        abi,
+        Arc::new(metered("revert")),
+    )
+    .method::<Bytes, ()>("claimAndRegister", Bytes::default())
+    .unwrap();
+    match ClientError::from(call.call().await.unwrap_err()) {
+        ClientError::Revert { reason, .. } => {
+            assert_eq!(reason, "Invalid eligibility signature")
+        }
+        other => panic!("se esperaba un revert: {other:?}"),
+    }
+}
+
+fn engine(name: &str) -> (TxEngine<ReplayTransport>, Address) {
+    let policy = ResubmitPolicy {
+        poll_interval: Duration::from_millis(1),
+        ..ResubmitPolicy::default()
+    };
+    let mut engine = TxEngine::with_provider(replay(name), 31337, 1, policy);
+    let from = engine.add_wallet(ENGINE_KEY.parse().unwrap());
+    (engine, from)
+}
+
+fn transfer() -> TypedTransaction {
+    TransactionRequest::new()
+        .to(USER.parse::<Address>().unwrap())
+        .value(1)
+        .gas(21_000)
+        .into()
+}
+
+#[tokio::test]
+async fn engine_bumps_gas_when_underpriced() {
+    let (engine, from) = engine("send_underpriced");
+    let outcome = engine.submit(from, transfer()).await.unwrap();
+
+    assert!(outcome.succeeded());
+    assert_eq!(outcome.retries, 1);
+    assert_eq!(outcome.nonce, U256::from(5));
+    assert_eq!(outcome.gas_price, U256::from(1_150_000_000u64));
+    assert_eq!(outcome.tx_hash, tx_hash(0x66));
+    assert_eq!(
+        engine.provider().as_ref().count("eth_sendRawTransaction"),
+        2
+    );
+}
+
+#[tokio::test]
+async fn engine_resyncs_nonce_when_too_low() {
+    let (engine, from) = engine("send_nonce_too_low");
+    let outcome = engine.submit(from, transfer()).await.unwrap();
+
+    assert_eq!(outcome.retries, 1);
+    assert_eq!(outcome.nonce, U256::from(6));
+    assert_eq!(outcome.gas_price, U256::from(1_000_000_000u64));
+    assert_eq!(
+        engine.provider().as_ref().count("eth_getTransactionCount"),
+        2
+    );
+}
+
+#[tokio::test]
+async fn engine_gives_up_on_non_retryable_errors() {
+    let (engine, from) = engine("send_rejected");
+    let error = engine.submit(from, transfer()).await.unwrap_err();
+
+    assert!(error.to_string().contains("insufficient funds"));
+    assert_eq!(
+        engine.provider().as_ref().count("eth_sendRawTransaction"),
+        1
+    );
+}
+
+#[tokio::test]
+#[ignore = "necesita anvil y los artifacts de ../out"]
+async fn recorded_fixture_replays_like_the_node() {
+    let net = TestNet::spawn().await;
+    let organization = net.address("organization");
+    let (tx, _) = create_loop_confirmed(&net.env, organization, U256::from(60), U256::from(5))
+        .await
+        .unwrap();
+
+    let recorder = RecordingTransport::connect(&net.anvil.endpoint()).unwrap();
+    let live = find_loop_created_event_with(&recorder.provider(), organization, tx)
+        .await
+        .unwrap();
+    assert!(live.is_some());
+
+    let path = std::env::temp_dir().join(format!("loop_created_{}.json", net.anvil.port()));
+    recorder.save(&path).unwrap();
+    let replayed = find_loop_created_event_with(
+        &Provider::new(ReplayTransport::from_file(&path).unwrap()),
+        organization,
+        tx,
+    )
+    .await
+    .unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(live, replayed);
+}
@@ -0,0 +1,113 @@
+//! Cálculo de slots de diamond storage contra la codificación ABI de los
+//! mappings de Solidity y decodificación de strings, sin nodo.
+
+use client_test::access::{RoleSet, RoleSource};
+use client_test::diamond::DiamondState;
+use client_test::storage::*;
+use ethers::abi::{self, Token};
+use ethers::prelude::*;
+use ethers::utils::{id, keccak256};
+
+/// `keccak256(abi.encode(key, slot))`, como calcula Solidity `mapping[key]`.
+fn solidity_mapping_slot(key: Token, slot: U256) -> U256 {
+    U256::from_big_endian(&keccak256(abi::encode(&[key, Token::Uint(slot)])))
+}
+
+#[test]
+fn access_control_slots_match_solidity_layout() {
+    let base = U256::from_big_endian(&keccak256("access-control.storage"));
+    assert_eq!(namespace_slot(ACCESS_CONTROL_STORAGE_NAMESPACE), base);
+
+    let user = Address::repeat_byte(0xab);
+    assert_eq!(
+        user_roles_slot(user),
+        solidity_mapping_slot(Token::Address(user), base)
+    );
+
+    // bytes4 va alineado a la izquierda, no como un entero
+    let selector = id("withdrawDeposit(address)");
+    assert_eq!(
+        function_roles_slot(selector),
+        solidity_mapping_slot(Token::FixedBytes(selector.to_vec()), base + 1)
+    );
+    assert_eq!(&selector_key(selector).as_bytes()[..4], &selector);
+}
+
+#[test]
+fn role_source_depends_on_routed_getters() {
+    let mut state = DiamondState::default();
+    state
+        .routes
+        .insert(id("claimAndRegister(bytes)"), Address::repeat_byte(1));
+    assert_eq!(RoleSource::of(&state), RoleSource::Storage);
+
+    state
+        .routes
+        .insert(id("userRoles(address)"), Address::repeat_byte(2));
+    assert_eq!(RoleSource::of(&state), RoleSource::Facet);
+
+    // Lo que devuelve el storage se interpreta igual que `_canCall`
+    let loop_admin = RoleSet::from_roles(&[1]);
+    assert!(loop_admin.can_call(&RoleSet::from_roles(&[1, 3])));
+    assert!(!loop_admin.can_call(&RoleSet::from_roles(&[3])));
+    assert!(RoleSet::from_roles(&[0]).can_call(&RoleSet::default()));
+}
+
+#[test]
+fn loop_mapping_slots_match_solidity_layout() {
+    let base = U256::from_big_endian(&keccak256("diamond.loop.storage"));
+    let user = Address::repeat_byte(0x42);
+    assert_eq!(
+        claimer_slot(user),
+        solidity_mapping_slot(Token::Address(user), base + loop_slots::CLAIMERS)
+    );
+    let period = U256::from(7);
+    assert_eq!(
+        period_slot(period),
+        solidity_mapping_slot(Token::Uint(period), base + loop_slots::PERIODS)
+    );
+    assert_ne!(claimer_slot(user), claimer_slot(Address::repeat_byte(0x43)));
+}
+
+#[test]
+fn decodes_short_and_long_strings() {
+    // "1Hive": bytes alineados a la izquierda y len * 2 en el último byte
+    let mut word = H256::zero();
+    word.0[..5].copy_from_slice(b"1Hive");
+    word.0[31] = 10;
+    assert_eq!(
+        decode_string_slot(word).unwrap(),
+        StoredString::Short("1Hive".into())
+    );
+    assert_eq!(
+        decode_string_slot(H256::zero()).unwrap(),
+        StoredString::Short(String::new())
+    );
+
+    // 40 bytes: el slot guarda len * 2 + 1 y los datos van en keccak256(slot)
+    let word = H256::from_low_u64_be(81);
+    assert_eq!(
+        decode_string_slot(word).unwrap(),
+        StoredString::Long { len: 40 }
+    );
+    let slot = U256::from(3);
+    let mut preimage = [0u8; 32];
+    slot.to_big_endian(&mut preimage);
+    assert_eq!(
+        string_data_slot(slot),
+        U256::from_big_endian(&keccak256(preimage))
+    );
+}
+
+#[test]
+fn rejects_corrupt_string_lengths() {
+    // Una dirección o un hash en el slot: longitud impar enorme
+    assert!(decode_string_slot(H256::repeat_byte(0xff)).is_err());
+    let too_long = U256::from(MAX_STORAGE_STRING_LEN + 1) * 2 + 1;
+    assert!(decode_string_slot(H256::from_uint(&too_long)).is_err());
+
+    // Último byte par pero con más de 31 bytes declarados
+    let mut word = H256::zero();
+    word.0[31] = 64;
+    assert!(decode_string_slot(word).is_err());
+}
@@ -0,0 +1,169 @@
+//! Cantidades legibles del token, el plan de `token mint` contra un fixture
+//! construido aquí y operaciones ERC20 contra anvil.
+
+mod common;
+
+use client_test::functions::*;
+use client_test::transport::{ReplayTransport, RpcExchange, RpcFixture};
+use common::{mined, TestNet, ONE_TOKEN};
+use ethers::abi::{encode, Token};
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use serde_json::json;
+
+const TOKEN: &str = "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9";
+
+fn token() -> Address {
+    TOKEN.parse().unwrap()
+}
+
+/// `eth_getStorageAt(token, slot)` que devuelve `value`.
+fn storage_at(slot: U256, value: U256) -> RpcExchange {
+    RpcExchange {
+        method: "eth_getStorageAt".into(),
+        params: json!([token(), slot, "latest"]),
+        result: json!(H256::from_uint(&value)),
+        error: None,
+    }
+}
+
+fn set_storage_at(slot: U256, value: U256) -> RpcExchange {
+    RpcExchange {
+        method: "anvil_setStorageAt".into(),
+        params: json!([token(), H256::from_uint(&slot), H256::from_uint(&value)]),
+        result: json!(true),
+        error: None,
+    }
+}
+
+#[test]
+fn parses_and_formats_human_amounts() {
+    let amount = parse_token_amount("1500.5", 18).unwrap();
+    assert_eq!(amount, U256::from(1_500_500_000_000_000_000_000u128));
+    assert_eq!(format_token_amount(amount, 18), "1500.5");
+    assert_eq!(format_token_amount(U256::from(ONE_TOKEN) * 3, 18), "3");
+    assert_eq!(
+        format_token_amount(U256::from(1), 18),
+        "0.000000000000000001"
+    );
+    assert_eq!(format_token_amount(U256::from(125), 2), "1.25");
+    assert_eq!(
+        parse_token_amount(" 42 ", 6).unwrap(),
+        U256::from(42_000_000)
+    );
+
+    // Más decimales que el token, negativos o basura
+    assert!(parse_token_amount("0.001", 2).is_err());
+    assert!(parse_token_amount("-1", 18).is_err());
+    assert!(parse_token_amount("uno", 18).is_err());
+}
+
+#[test]
+fn balance_slot_matches_openzeppelin_layout() {
+    // `_balances` es la primera variable de `ERC20`: keccak256(abi.encode(holder, 0))
+    let holder = Address::repeat_byte(0x42);
+    let expected = keccak256(encode(&[Token::Address(holder), Token::Uint(U256::zero())]));
+    assert_eq!(erc20_balance_slot(holder), U256::from_big_endian(&expected));
+}
+
+#[tokio::test]
+async fn mint_plan_adds_amount_to_balance_and_supply() {
+    let to = Address::repeat_byte(0x42);
+    let amount = U256::from(250) * U256::from(ONE_TOKEN);
+    let supply_slot = U256::from(2);
+    let provider = ReplayTransport::provider(RpcFixture {
+        exchanges: vec![
+            storage_at(erc20_balance_slot(to), U256::from(7)),
+            storage_at(supply_slot, U256::from(1_000)),
+        ],
+    });
+
+    let writes = mint_plan(&provider, token(), to, amount).await.unwrap();
+    assert_eq!(
+        writes,
+        vec![
+            StorageWrite {
+                slot: erc20_balance_slot(to),
+                before: U256::from(7),
+                after: amount + 7,
+            },
+            StorageWrite {
+                slot: supply_slot,
+                before: U256::from(1_000),
+                after: amount + 1_000,
+            },
+        ]
+    );
+    // Sólo lee: el dry run no toca el storage
+    assert_eq!(provider.as_ref().count("anvil_setStorageAt"), 0);
+
+    // Se escribe exactamente lo planificado, slot a slot
+    let replay = ReplayTransport::provider(RpcFixture {
+        exchanges: writes
+            .iter()
+            .map(|write| set_storage_at(write.slot, write.after))
+            .collect(),
+    });
+    apply_storage_writes(&replay, token(), &writes)
+        .await
+        .unwrap();
+    assert_eq!(replay.as_ref().count("anvil_setStorageAt"), 2);
+}
+
+#[tokio::test]
+async fn mint_plan_rejects_supply_overflow() {
+    let to = Address::repeat_byte(0x42);
+    let provider = ReplayTransport::provider(RpcFixture {
+        exchanges: vec![
+            storage_at(erc20_balance_slot(to), U256::zero()),
+            storage_at(U256::from(2), U256::MAX),
+        ],
+    });
+    assert!(mint_plan(&provider, token(), to, U256::one())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+#[ignore = "necesita anvil y los artifacts de ../out"]
+async fn token_operations_on_anvil() {
+    let net = TestNet::spawn().await;
+    let env = &net.env;
+    let token = net.address("test_token_address");
+    let signer = env.signer().unwrap().address();
+    let user = net.user(0).address();
+
+    let info = token_info(env, token).await.unwrap();
+    assert_eq!(info.symbol, "HNY");
+    assert_eq!(info.decimals, 18);
+    assert_eq!(info.display(info.total_supply), "1000000 HNY");
+
+    // Mint local: sube el balance y el totalSupply
+    mint_local(env, token, user, info.parse("250.25").unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        info.format(token_balance(env, token, user).await.unwrap()),
+        "250.25"
+    );
+    let supply = token_info(env, token).await.unwrap().total_supply;
+    assert_eq!(info.format(supply), "1000250.25");
+
+    mined(approve_call(env, token, user, info.parse("10").unwrap()).unwrap()).await;
+    assert_eq!(
+        token_allowance(env, token, signer, user).await.unwrap(),
+        info.parse("10").unwrap()
+    );
+
+    // Fondeo con cantidad legible
+    let loop_address = net.address("loop");
+    let before = token_balance(env, token, loop_address).await.unwrap();
+    let (_, amount, call) = fund_loop_amount_call(env, loop_address, "1500.5")
+        .await
+        .unwrap();
+    mined(call).await;
+    assert_eq!(
+        token_balance(env, token, loop_address).await.unwrap() - before,
+        amount
+    );
+}
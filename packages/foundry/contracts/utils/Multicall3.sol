@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: MIT
+pragma solidity >=0.8.20;
+
+/// @title Multicall3
+/// @notice Copia de Multicall3 (github.com/mds1/multicall) con la misma ABI.
+/// El cliente la instala con `anvil_setCode` en la dirección canónica
+/// (0xcA11bde05977b3631167028862bE2a173976CA11) cuando la red local no la trae,
+/// para agrupar lecturas de muchos loops y usuarios en un solo `eth_call`.
+contract Multicall3 {
+  struct Call {
+    address target;
+    bytes callData;
+  }
+
+  struct Call3 {
+    address target;
+    bool allowFailure;
+    bytes callData;
+  }
+
+  struct Call3Value {
+    address target;
+    bool allowFailure;
+    uint256 value;
+    bytes callData;
+  }
+
+  struct Result {
+    bool success;
+    bytes returnData;
+  }
+
+  /// @notice Ejecuta todas las llamadas; revierte si alguna falla.
+  function aggregate(Call[] calldata calls)
+    public
+    payable
+    returns (uint256 blockNumber, bytes[] memory returnData)
+  {
+    blockNumber = block.number;
+    uint256 length = calls.length;
+    returnData = new bytes[](length);
+    for (uint256 i = 0; i < length; i++) {
+      bool success;
+      (success, returnData[i]) = calls[i].target.call(calls[i].callData);
+      require(success, "Multicall3: call failed");
+    }
+  }
+
+  /// @notice Como `aggregate` pero, sin `requireSuccess`, los fallos se devuelven.
+  function tryAggregate(bool requireSuccess, Call[] calldata calls)
+    public
+    payable
+    returns (Result[] memory returnData)
+  {
+    uint256 length = calls.length;
+    returnData = new Result[](length);
+    for (uint256 i = 0; i < length; i++) {
+      Result memory result = returnData[i];
+      (result.success, result.returnData) =
+        calls[i].target.call(calls[i].callData);
+      if (requireSuccess) require(result.success, "Multicall3: call failed");
+    }
+  }
+
+  function tryBlockAndAggregate(bool requireSuccess, Call[] calldata calls)
+    public
+    payable
+    returns (
+      uint256 blockNumber,
+      bytes32 blockHash,
+      Result[] memory returnData
+    )
+  {
+    blockNumber = block.number;
+    blockHash = blockhash(block.number);
+    returnData = tryAggregate(requireSuccess, calls);
+  }
+
+  function blockAndAggregate(Call[] calldata calls)
+    public
+    payable
+    returns (
+      uint256 blockNumber,
+      bytes32 blockHash,
+      Result[] memory returnData
+    )
+  {
+    (blockNumber, blockHash, returnData) = tryBlockAndAggregate(true, calls);
+  }
+
+  /// @notice Cada llamada decide si su fallo revierte todo el lote.
+  function aggregate3(Call3[] calldata calls)
+    public
+    payable
+    returns (Result[] memory returnData)
+  {
+    uint256 length = calls.length;
+    returnData = new Result[](length);
+    for (uint256 i = 0; i < length; i++) {
+      Result memory result = returnData[i];
+      Call3 calldata calli = calls[i];
+      (result.success, result.returnData) = calli.target.call(calli.callData);
+      require(
+        calli.allowFailure || result.success, "Multicall3: call failed"
+      );
+    }
+  }
+
+  /// @notice `aggregate3` con ETH; la suma de `value` tiene que ser `msg.value`.
+  function aggregate3Value(Call3Value[] calldata calls)
+    public
+    payable
+    returns (Result[] memory returnData)
+  {
+    uint256 valAccumulator;
+    uint256 length = calls.length;
+    returnData = new Result[](length);
+    for (uint256 i = 0; i < length; i++) {
+      Result memory result = returnData[i];
+      Call3Value calldata calli = calls[i];
+      uint256 val = calli.value;
+      valAccumulator += val;
+      (result.success, result.returnData) =
+        calli.target.call{ value: val }(calli.callData);
+      require(
+        calli.allowFailure || result.success, "Multicall3: call failed"
+      );
+    }
+    require(msg.value == valAccumulator, "Multicall3: value mismatch");
+  }
+
+  function getBlockHash(uint256 blockNumber)
+    public
+    view
+    returns (bytes32 blockHash)
+  {
+    blockHash = blockhash(blockNumber);
+  }
+
+  function getBlockNumber() public view returns (uint256 blockNumber) {
+    blockNumber = block.number;
+  }
+
+  function getCurrentBlockCoinbase() public view returns (address coinbase) {
+    coinbase = block.coinbase;
+  }
+
+  function getCurrentBlockDifficulty()
+    public
+    view
+    returns (uint256 difficulty)
+  {
+    difficulty = block.prevrandao;
+  }
+
+  function getCurrentBlockGasLimit() public view returns (uint256 gaslimit) {
+    gaslimit = block.gaslimit;
+  }
+
+  function getCurrentBlockTimestamp() public view returns (uint256 timestamp) {
+    timestamp = block.timestamp;
+  }
+
+  function getEthBalance(address addr) public view returns (uint256 balance) {
+    balance = addr.balance;
+  }
+
+  function getLastBlockHash() public view returns (bytes32 blockHash) {
+    unchecked {
+      blockHash = blockhash(block.number - 1);
+    }
+  }
+
+  function getBasefee() public view returns (uint256 basefee) {
+    basefee = block.basefee;
+  }
+
+  function getChainId() public view returns (uint256 chainid) {
+    chainid = block.chainid;
+  }
+}